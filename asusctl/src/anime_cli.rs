@@ -53,6 +53,8 @@ pub enum AnimeActions {
     Gif(AnimeGif),
     #[options(help = "display an animated diagonal/pixel-perfect GIF")]
     PixelGif(AnimeGifDiagonal),
+    #[options(help = "play an APNG, animated WebP, or a directory of PNG frames")]
+    Video(AnimeVideo),
     #[options(help = "change which builtin animations are shown")]
     SetBuiltins(Builtins),
 }
@@ -152,3 +154,35 @@ pub struct AnimeGifDiagonal {
     )]
     pub loops: u32,
 }
+
+#[derive(Options)]
+pub struct AnimeVideo {
+    #[options(help = "print help message")]
+    pub help: bool,
+    #[options(
+        meta = "",
+        help = "full path to the APNG/WebP file, or a directory of PNG frames"
+    )]
+    pub path: String,
+    #[options(meta = "", default = "1.0", help = "scale 1.0 == normal")]
+    pub scale: f32,
+    #[options(meta = "", default = "0.0", help = "x position (float)")]
+    pub x_pos: f32,
+    #[options(meta = "", default = "0.0", help = "y position (float)")]
+    pub y_pos: f32,
+    #[options(meta = "", default = "0.0", help = "the angle in radians")]
+    pub angle: f32,
+    #[options(meta = "", default = "1.0", help = "brightness 0.0-1.0")]
+    pub bright: f32,
+    #[options(
+        meta = "",
+        default = "1",
+        help = "how many loops to play - 0 is infinite"
+    )]
+    pub loops: u32,
+    #[options(
+        meta = "",
+        help = "milliseconds per frame for PNG directories or files without timing"
+    )]
+    pub frame_time: Option<u64>,
+}
@@ -0,0 +1,868 @@
+//! `asusctl apply`, bring the laptop to the state described in a RON file.
+//!
+//! Only the settings given in the file are looked at. Each is compared with
+//! what asusd reports and those that differ make up the plan, which is printed
+//! and then set in order unless `--dry-run` is given. Everything in the file is
+//! checked against what the laptop supports before anything is set, so a file
+//! that can't be applied changes nothing. Applying the same file twice makes
+//! no changes the second time.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Debug;
+use std::io::Read;
+
+use gumdrop::Options;
+use log::error;
+use rog_anime::usb::Brightness;
+use rog_anime::Animations;
+use rog_aura::keyboard::{AuraPowerState, LaptopAuraPower};
+use rog_aura::{AuraDeviceType, AuraEffect, Colour, LedBrightness};
+use rog_dbus::asus_armoury::AsusArmouryProxyBlocking;
+use rog_dbus::zbus_anime::AnimeProxyBlocking;
+use rog_dbus::zbus_aura::AuraProxyBlocking;
+use rog_dbus::zbus_fan_curves::FanCurvesProxyBlocking;
+use rog_dbus::zbus_platform::PlatformProxyBlocking;
+use rog_dbus::zbus_slash::SlashProxyBlocking;
+use rog_platform::platform::PlatformProfile;
+use rog_slash::SlashMode;
+use ron::extensions::Extensions;
+use serde::{Deserialize, Serialize};
+use zbus::blocking::Connection;
+
+use crate::cli_opts::ApplyCommand;
+use crate::find_iface;
+use crate::json::{Attribute, CliError, ProfileCurves, Report};
+
+/// The desired state. Settings that are not given are left as they are.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct State {
+    pub platform: PlatformState,
+    /// Armoury attribute names and the value of each
+    pub armoury: BTreeMap<String, i32>,
+    /// Only the fans listed for a profile are changed
+    pub fan_curves: Vec<ProfileCurves>,
+    pub aura: Vec<AuraState>,
+    /// Applied to every AniMe device
+    pub anime: Option<AnimeState>,
+    /// Applied to every Slash device
+    pub slash: Option<SlashState>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlatformState {
+    pub profile: Option<PlatformProfile>,
+    pub profile_on_ac: Option<PlatformProfile>,
+    pub profile_on_battery: Option<PlatformProfile>,
+    pub epp_linked: Option<bool>,
+    pub charge_limit: Option<u8>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuraState {
+    /// The device to set, every aura device if not given
+    pub device_type: Option<AuraDeviceType>,
+    pub brightness: Option<LedBrightness>,
+    pub effect: Option<AuraEffect>,
+    /// Only the zones listed are changed
+    pub power: Vec<AuraPowerState>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AnimeState {
+    pub display_enabled: Option<bool>,
+    pub brightness: Option<Brightness>,
+    pub builtins_enabled: Option<bool>,
+    pub builtin_animations: Option<Animations>,
+    pub off_when_lid_closed: Option<bool>,
+    pub off_when_suspended: Option<bool>,
+    pub off_when_unplugged: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SlashState {
+    pub enabled: Option<bool>,
+    pub mode: Option<SlashMode>,
+    pub brightness: Option<u8>,
+    pub interval: Option<u8>,
+    pub show_battery_warning: Option<bool>,
+    pub show_on_battery: Option<bool>,
+    pub show_on_boot: Option<bool>,
+    pub show_on_shutdown: Option<bool>,
+    pub show_on_sleep: Option<bool>,
+    pub show_on_lid_closed: Option<bool>,
+}
+
+/// A setting that differs from the state file, named by its place in the
+/// file such as `platform.profile` or `aura.Keyboard.effect`
+#[derive(Debug, Serialize)]
+pub struct Change {
+    pub setting: String,
+    pub current: String,
+    pub desired: String,
+}
+
+/// The result of `apply` for `--json`
+#[derive(Debug, Serialize)]
+pub struct Applied {
+    pub changes: Vec<Change>,
+    /// `true` if nothing was set because of `--dry-run`
+    pub dry_run: bool,
+}
+
+type Set = Box<dyn Fn() -> zbus::Result<()>>;
+
+/// The changes to make, in the order they are made
+#[derive(Default)]
+struct Plan {
+    steps: Vec<(Change, Set)>,
+}
+
+impl Plan {
+    /// Add a change if `desired` is given and isn't what `current` reads.
+    /// `current` is only read if there is something to compare.
+    fn add<T>(
+        &mut self,
+        setting: &str,
+        desired: Option<&T>,
+        current: impl FnOnce() -> zbus::Result<T>,
+        set: impl Fn(T) -> zbus::Result<()> + 'static,
+    ) -> zbus::Result<()>
+    where
+        T: PartialEq + Debug + Clone + 'static,
+    {
+        let Some(desired) = desired else {
+            return Ok(());
+        };
+        let current = current()?;
+        if current != *desired {
+            let change = Change {
+                setting: setting.to_owned(),
+                current: format!("{current:?}"),
+                desired: format!("{desired:?}"),
+            };
+            let value = desired.clone();
+            self.steps
+                .push((change, Box::new(move || set(value.clone()))));
+        }
+        Ok(())
+    }
+}
+
+pub fn handle_apply(
+    conn: &Connection,
+    supported_interfaces: &[String],
+    cmd: &ApplyCommand,
+    report: &mut Option<Report>,
+) -> Result<(), Box<dyn Error>> {
+    let Some(path) = cmd.file.as_ref().filter(|_| !cmd.help) else {
+        if report.is_some() {
+            return Err(CliError::usage("A state file is required"));
+        }
+        println!("{}", cmd.self_usage());
+        return Ok(());
+    };
+    let state = read_state(path)?;
+    let plan = plan(conn, supported_interfaces, &state)?;
+
+    if report.is_none() {
+        if plan.steps.is_empty() {
+            println!("Nothing to change, the laptop matches {path}");
+            return Ok(());
+        }
+        println!("Plan, {} change(s):", plan.steps.len());
+        for (change, _) in &plan.steps {
+            println!(
+                "  {}: {} -> {}",
+                change.setting, change.current, change.desired
+            );
+        }
+    }
+
+    if !cmd.dry_run {
+        for (change, set) in &plan.steps {
+            set().inspect_err(|e| error!("Could not set {}: {e}", change.setting))?;
+        }
+    }
+
+    if let Some(report) = report {
+        report.apply = Some(Applied {
+            changes: plan.steps.into_iter().map(|(change, _)| change).collect(),
+            dry_run: cmd.dry_run,
+        });
+    } else if cmd.dry_run {
+        println!("Dry run, nothing was changed");
+    } else {
+        println!("Applied {} change(s)", plan.steps.len());
+    }
+    Ok(())
+}
+
+/// Read the state from a file, or from stdin if `path` is `-`. Options may be
+/// written without `Some(..)`.
+fn read_state(path: &str) -> Result<State, Box<dyn Error>> {
+    let text = if path == "-" {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text)?;
+        text
+    } else {
+        std::fs::read_to_string(path)
+            .map_err(|e| CliError::invalid_argument(&format!("Could not read {path}: {e}")))?
+    };
+    parse_state(&text).map_err(|e| CliError::invalid_argument(&format!("{path}: {e}")).into())
+}
+
+fn parse_state(text: &str) -> ron::error::SpannedResult<State> {
+    ron::Options::default()
+        .with_default_extension(Extensions::IMPLICIT_SOME)
+        .from_str(text)
+}
+
+/// The checks that don't need the laptop
+fn check_state(state: &State) -> Result<(), Box<dyn Error>> {
+    if let Some(limit) = state.platform.charge_limit {
+        if !(20..=100).contains(&limit) {
+            return Err(CliError::invalid_argument(
+                "The charge limit must be between 20 and 100",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Compare the state with the laptop, failing if the state has anything the
+/// laptop does not support
+fn plan(
+    conn: &Connection,
+    supported_interfaces: &[String],
+    state: &State,
+) -> Result<Plan, Box<dyn Error>> {
+    check_state(state)?;
+    let has = |iface: &str| supported_interfaces.iter().any(|i| i == iface);
+    let mut plan = Plan::default();
+
+    let platform = PlatformProxyBlocking::new(conn)?;
+    let p = &state.platform;
+    let profiles: Vec<PlatformProfile> = [
+        p.profile, p.profile_on_ac, p.profile_on_battery,
+    ]
+    .into_iter()
+    .flatten()
+    .chain(state.fan_curves.iter().map(|c| c.profile))
+    .collect();
+    if !profiles.is_empty() {
+        let choices = platform.platform_profile_choices()?;
+        if let Some(profile) = profiles.iter().find(|p| !choices.contains(p)) {
+            return Err(CliError::not_supported(&format!(
+                "The {profile:?} profile is not supported, the choices are {choices:?}"
+            )));
+        }
+    }
+    let proxy = platform.clone();
+    plan.add(
+        "platform.profile",
+        p.profile.as_ref(),
+        || platform.platform_profile(),
+        move |v| proxy.set_platform_profile(v),
+    )?;
+    let proxy = platform.clone();
+    plan.add(
+        "platform.profile_on_ac",
+        p.profile_on_ac.as_ref(),
+        || platform.platform_profile_on_ac(),
+        move |v| proxy.set_platform_profile_on_ac(v),
+    )?;
+    let proxy = platform.clone();
+    plan.add(
+        "platform.profile_on_battery",
+        p.profile_on_battery.as_ref(),
+        || platform.platform_profile_on_battery(),
+        move |v| proxy.set_platform_profile_on_battery(v),
+    )?;
+    let proxy = platform.clone();
+    plan.add(
+        "platform.epp_linked",
+        p.epp_linked.as_ref(),
+        || platform.platform_profile_linked_epp(),
+        move |v| proxy.set_platform_profile_linked_epp(v),
+    )?;
+    let proxy = platform.clone();
+    plan.add(
+        "platform.charge_limit",
+        p.charge_limit.as_ref(),
+        || platform.charge_control_end_threshold(),
+        move |v| proxy.set_charge_control_end_threshold(v),
+    )?;
+
+    if !state.armoury.is_empty() {
+        let attrs = if has("xyz.ljones.AsusArmoury") {
+            find_iface::<AsusArmouryProxyBlocking>("xyz.ljones.AsusArmoury")?
+        } else {
+            Vec::new()
+        };
+        for (name, value) in &state.armoury {
+            let Some(attr) = attrs
+                .iter()
+                .find(|a| a.name().is_ok_and(|n| <&str>::from(n) == name))
+            else {
+                return Err(CliError::not_supported(&format!(
+                    "There is no armoury attribute {name}"
+                )));
+            };
+            let info = Attribute::query(attr)?;
+            let in_range = match (&info.possible_values, info.min, info.max) {
+                (Some(possible), ..) if !possible.is_empty() => possible.contains(value),
+                (_, Some(min), Some(max)) if min <= max => (min..=max).contains(value),
+                _ => true,
+            };
+            if !in_range {
+                return Err(CliError::invalid_argument(&format!(
+                    "{value} is out of range for {name}"
+                )));
+            }
+            let proxy = attr.clone();
+            plan.add(
+                &format!("armoury.{name}"),
+                Some(value),
+                || attr.current_value(),
+                move |v| proxy.set_current_value(v),
+            )?;
+        }
+    }
+
+    if !state.fan_curves.is_empty() {
+        if !has("xyz.ljones.FanCurves") {
+            return Err(CliError::not_supported(
+                "The state has fan curves but this laptop has no fan curve control",
+            ));
+        }
+        let fans = FanCurvesProxyBlocking::new(conn)?;
+        for wanted in &state.fan_curves {
+            let profile = wanted.profile;
+            let current = fans.fan_curve_data(profile)?;
+            for curve in &wanted.curves {
+                let Some(now) = current.iter().find(|c| c.fan == curve.fan) else {
+                    return Err(CliError::not_supported(&format!(
+                        "There is no {:?} fan curve for {profile:?}",
+                        curve.fan
+                    )));
+                };
+                if now.pwm != curve.pwm || now.temp != curve.temp || now.enabled != curve.enabled {
+                    let change = Change {
+                        setting: format!("fan_curves.{profile:?}.{:?}", curve.fan),
+                        current: String::from(now),
+                        desired: String::from(curve),
+                    };
+                    let (proxy, curve) = (fans.clone(), curve.clone());
+                    plan.steps.push((
+                        change,
+                        Box::new(move || proxy.set_fan_curve(profile, curve.clone())),
+                    ));
+                }
+            }
+        }
+    }
+
+    if !state.aura.is_empty() {
+        let auras = if has("xyz.ljones.Aura") {
+            find_iface::<AuraProxyBlocking>("xyz.ljones.Aura")?
+        } else {
+            Vec::new()
+        };
+        for wanted in &state.aura {
+            let mut found = false;
+            for aura in &auras {
+                let device_type = aura.device_type()?;
+                if wanted.device_type.is_some_and(|t| t != device_type) {
+                    continue;
+                }
+                found = true;
+                plan_aura(&mut plan, aura, device_type, wanted)?;
+            }
+            if !found {
+                return Err(CliError::not_supported(&match wanted.device_type {
+                    Some(t) => format!("There is no {t:?} aura device"),
+                    None => "The state has aura settings but there is no aura device".to_owned(),
+                }));
+            }
+        }
+    }
+
+    if let Some(wanted) = &state.anime {
+        let animes = if has("xyz.ljones.Anime") {
+            find_iface::<AnimeProxyBlocking>("xyz.ljones.Anime")?
+        } else {
+            Vec::new()
+        };
+        if animes.is_empty() {
+            return Err(CliError::not_supported(
+                "The state has AniMe settings but there is no AniMe device",
+            ));
+        }
+        for (i, anime) in animes.iter().enumerate() {
+            let prefix = section("anime", i, animes.len());
+            plan_anime(&mut plan, anime, &prefix, wanted)?;
+        }
+    }
+
+    if let Some(wanted) = &state.slash {
+        let slashes = if has("xyz.ljones.Slash") {
+            find_iface::<SlashProxyBlocking>("xyz.ljones.Slash")?
+        } else {
+            Vec::new()
+        };
+        if slashes.is_empty() {
+            return Err(CliError::not_supported(
+                "The state has Slash settings but there is no Slash device",
+            ));
+        }
+        for (i, slash) in slashes.iter().enumerate() {
+            let prefix = section("slash", i, slashes.len());
+            plan_slash(&mut plan, slash, &prefix, wanted)?;
+        }
+    }
+
+    Ok(plan)
+}
+
+/// The name of a section, numbered if there is more than one device
+fn section(name: &str, index: usize, count: usize) -> String {
+    if count > 1 {
+        format!("{name}[{index}]")
+    } else {
+        name.to_owned()
+    }
+}
+
+fn plan_aura(
+    plan: &mut Plan,
+    aura: &AuraProxyBlocking<'static>,
+    device_type: AuraDeviceType,
+    wanted: &AuraState,
+) -> Result<(), Box<dyn Error>> {
+    let prefix = format!("aura.{device_type:?}");
+
+    if let Some(brightness) = wanted.brightness {
+        if !aura.supported_brightness()?.contains(&brightness) {
+            return Err(CliError::not_supported(&format!(
+                "{prefix} does not support {brightness:?} brightness"
+            )));
+        }
+    }
+    let proxy = aura.clone();
+    plan.add(
+        &format!("{prefix}.brightness"),
+        wanted.brightness.as_ref(),
+        || aura.brightness(),
+        move |v| proxy.set_brightness(v),
+    )?;
+
+    if let Some(effect) = &wanted.effect {
+        if !aura.supported_basic_modes()?.contains(&effect.mode) {
+            return Err(CliError::not_supported(&format!(
+                "{prefix} does not support the {:?} mode",
+                effect.mode
+            )));
+        }
+        let current = aura.led_mode_data()?;
+        if current != *effect {
+            let change = Change {
+                setting: format!("{prefix}.effect"),
+                current: describe_effect(&current),
+                desired: describe_effect(effect),
+            };
+            let (proxy, effect) = (aura.clone(), effect.clone());
+            plan.steps.push((
+                change,
+                Box::new(move || proxy.set_led_mode_data(effect.clone())),
+            ));
+        }
+    }
+
+    if !wanted.power.is_empty() {
+        if let Some((change, power)) = merge_power(&prefix, &aura.led_power()?, &wanted.power)? {
+            let proxy = aura.clone();
+            plan.steps
+                .push((change, Box::new(move || proxy.set_led_power(power.clone()))));
+        }
+    }
+    Ok(())
+}
+
+/// Set the zones in `wanted` on `current`, leaving the others as they are.
+/// `None` if nothing changes.
+fn merge_power(
+    prefix: &str,
+    current: &LaptopAuraPower,
+    wanted: &[AuraPowerState],
+) -> Result<Option<(Change, LaptopAuraPower)>, Box<dyn Error>> {
+    let mut power = current.clone();
+    let (mut from, mut to) = (Vec::new(), Vec::new());
+    for state in wanted {
+        let Some(now) = power.states.iter_mut().find(|s| s.zone == state.zone) else {
+            return Err(CliError::not_supported(&format!(
+                "{prefix} has no {:?} power zone",
+                state.zone
+            )));
+        };
+        if now != state {
+            from.push(describe_power(now));
+            to.push(describe_power(state));
+            *now = *state;
+        }
+    }
+    if power == *current {
+        return Ok(None);
+    }
+    let change = Change {
+        setting: format!("{prefix}.power"),
+        current: from.join("; "),
+        desired: to.join("; "),
+    };
+    Ok(Some((change, power)))
+}
+
+fn plan_anime(
+    plan: &mut Plan,
+    anime: &AnimeProxyBlocking<'static>,
+    prefix: &str,
+    wanted: &AnimeState,
+) -> zbus::Result<()> {
+    let proxy = anime.clone();
+    plan.add(
+        &format!("{prefix}.display_enabled"),
+        wanted.display_enabled.as_ref(),
+        || anime.enable_display(),
+        move |v| proxy.set_enable_display(v),
+    )?;
+    let proxy = anime.clone();
+    plan.add(
+        &format!("{prefix}.brightness"),
+        wanted.brightness.as_ref(),
+        || anime.brightness(),
+        move |v| proxy.set_brightness(v),
+    )?;
+    let proxy = anime.clone();
+    plan.add(
+        &format!("{prefix}.builtins_enabled"),
+        wanted.builtins_enabled.as_ref(),
+        || anime.builtins_enabled(),
+        move |v| proxy.set_builtins_enabled(v),
+    )?;
+    let proxy = anime.clone();
+    plan.add(
+        &format!("{prefix}.builtin_animations"),
+        wanted.builtin_animations.as_ref(),
+        || anime.builtin_animations(),
+        move |v| proxy.set_builtin_animations(v),
+    )?;
+    let proxy = anime.clone();
+    plan.add(
+        &format!("{prefix}.off_when_lid_closed"),
+        wanted.off_when_lid_closed.as_ref(),
+        || anime.off_when_lid_closed(),
+        move |v| proxy.set_off_when_lid_closed(v),
+    )?;
+    let proxy = anime.clone();
+    plan.add(
+        &format!("{prefix}.off_when_suspended"),
+        wanted.off_when_suspended.as_ref(),
+        || anime.off_when_suspended(),
+        move |v| proxy.set_off_when_suspended(v),
+    )?;
+    let proxy = anime.clone();
+    plan.add(
+        &format!("{prefix}.off_when_unplugged"),
+        wanted.off_when_unplugged.as_ref(),
+        || anime.off_when_unplugged(),
+        move |v| proxy.set_off_when_unplugged(v),
+    )
+}
+
+fn plan_slash(
+    plan: &mut Plan,
+    slash: &SlashProxyBlocking<'static>,
+    prefix: &str,
+    wanted: &SlashState,
+) -> zbus::Result<()> {
+    let proxy = slash.clone();
+    plan.add(
+        &format!("{prefix}.enabled"),
+        wanted.enabled.as_ref(),
+        || slash.enabled(),
+        move |v| proxy.set_enabled(v),
+    )?;
+    let proxy = slash.clone();
+    plan.add(
+        &format!("{prefix}.mode"),
+        wanted.mode.as_ref(),
+        || slash.mode(),
+        move |v| proxy.set_mode(v),
+    )?;
+    let proxy = slash.clone();
+    plan.add(
+        &format!("{prefix}.brightness"),
+        wanted.brightness.as_ref(),
+        || slash.brightness(),
+        move |v| proxy.set_brightness(v),
+    )?;
+    let proxy = slash.clone();
+    plan.add(
+        &format!("{prefix}.interval"),
+        wanted.interval.as_ref(),
+        || slash.interval(),
+        move |v| proxy.set_interval(v),
+    )?;
+    let proxy = slash.clone();
+    plan.add(
+        &format!("{prefix}.show_battery_warning"),
+        wanted.show_battery_warning.as_ref(),
+        || slash.show_battery_warning(),
+        move |v| proxy.set_show_battery_warning(v),
+    )?;
+    let proxy = slash.clone();
+    plan.add(
+        &format!("{prefix}.show_on_battery"),
+        wanted.show_on_battery.as_ref(),
+        || slash.show_on_battery(),
+        move |v| proxy.set_show_on_battery(v),
+    )?;
+    let proxy = slash.clone();
+    plan.add(
+        &format!("{prefix}.show_on_boot"),
+        wanted.show_on_boot.as_ref(),
+        || slash.show_on_boot(),
+        move |v| proxy.set_show_on_boot(v),
+    )?;
+    let proxy = slash.clone();
+    plan.add(
+        &format!("{prefix}.show_on_shutdown"),
+        wanted.show_on_shutdown.as_ref(),
+        || slash.show_on_shutdown(),
+        move |v| proxy.set_show_on_shutdown(v),
+    )?;
+    let proxy = slash.clone();
+    plan.add(
+        &format!("{prefix}.show_on_sleep"),
+        wanted.show_on_sleep.as_ref(),
+        || slash.show_on_sleep(),
+        move |v| proxy.set_show_on_sleep(v),
+    )?;
+    let proxy = slash.clone();
+    plan.add(
+        &format!("{prefix}.show_on_lid_closed"),
+        wanted.show_on_lid_closed.as_ref(),
+        || slash.show_on_lid_closed(),
+        move |v| proxy.set_show_on_lid_closed(v),
+    )
+}
+
+fn hex(c: &Colour) -> String {
+    format!("#{:02x}{:02x}{:02x}", c.r, c.g, c.b)
+}
+
+fn describe_effect(effect: &AuraEffect) -> String {
+    format!(
+        "{:?} {:?}, {} {}, {:?}, {:?}",
+        effect.mode,
+        effect.zone,
+        hex(&effect.colour1),
+        hex(&effect.colour2),
+        effect.speed,
+        effect.direction
+    )
+}
+
+/// Such as `Keyboard: boot, awake`
+fn describe_power(state: &AuraPowerState) -> String {
+    let on: Vec<&str> = [
+        ("boot", state.boot),
+        ("awake", state.awake),
+        ("sleep", state.sleep),
+        ("shutdown", state.shutdown),
+    ]
+    .iter()
+    .filter(|(_, on)| *on)
+    .map(|(name, _)| *name)
+    .collect();
+    if on.is_empty() {
+        format!("{:?}: off", state.zone)
+    } else {
+        format!("{:?}: {}", state.zone, on.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use rog_aura::keyboard::{AuraPowerState, LaptopAuraPower};
+    use rog_aura::{AuraModeNum, LedBrightness, PowerZones};
+    use rog_platform::platform::PlatformProfile;
+
+    use super::{check_state, merge_power, parse_state, Plan};
+
+    fn power(zone: PowerZones, on: bool) -> AuraPowerState {
+        AuraPowerState {
+            zone,
+            boot: on,
+            awake: on,
+            sleep: on,
+            shutdown: on,
+        }
+    }
+
+    #[test]
+    fn readme_example() {
+        let readme = include_str!("../../README.md");
+        let example = readme
+            .split("## Applying a state file")
+            .nth(1)
+            .and_then(|s| s.split("```ron").nth(1))
+            .and_then(|s| s.split("```").next())
+            .expect("no state file example in the README");
+        let state = parse_state(example).unwrap();
+        check_state(&state).unwrap();
+
+        assert_eq!(state.platform.profile, Some(PlatformProfile::Performance));
+        assert_eq!(
+            state.platform.profile_on_battery,
+            Some(PlatformProfile::Quiet)
+        );
+        assert_eq!(state.platform.charge_limit, Some(80));
+        assert_eq!(state.armoury.get("ppt_pl1_spl"), Some(&45));
+        assert_eq!(state.fan_curves[0].curves.len(), 1);
+        let aura = &state.aura[0];
+        assert_eq!(aura.device_type, None);
+        assert_eq!(aura.brightness, Some(LedBrightness::High));
+        assert_eq!(aura.effect.as_ref().unwrap().mode, AuraModeNum::Static);
+        assert_eq!(aura.power, [
+            AuraPowerState {
+                zone: PowerZones::Keyboard,
+                boot: true,
+                awake: true,
+                sleep: false,
+                shutdown: false,
+            }
+        ]);
+        assert_eq!(state.anime.unwrap().builtins_enabled, Some(true));
+        assert_eq!(state.slash.unwrap().brightness, Some(128));
+    }
+
+    #[test]
+    fn unknown_fields_rejected() {
+        assert!(parse_state("(platform: (profile: Quiet))").is_ok());
+        assert!(parse_state("(platfrom: (profile: Quiet))").is_err());
+        assert!(parse_state("(platform: (profle: Quiet))").is_err());
+        assert!(parse_state("(slash: (enabeld: true))").is_err());
+        assert!(parse_state("(aura: [(brightnes: High)])").is_err());
+    }
+
+    #[test]
+    fn charge_limit_range() {
+        for (limit, ok) in [
+            (19, false),
+            (20, true),
+            (80, true),
+            (100, true),
+            (101, false),
+        ] {
+            let state = parse_state(&format!("(platform: (charge_limit: {limit}))")).unwrap();
+            assert_eq!(check_state(&state).is_ok(), ok, "{limit}");
+        }
+    }
+
+    #[test]
+    fn plan_only_differences() {
+        let set = Rc::new(Cell::new(None));
+        let mut plan = Plan::default();
+        let s = set.clone();
+        plan.add(
+            "same",
+            Some(&80u8),
+            || Ok(80),
+            move |v| {
+                s.set(Some(v));
+                Ok(())
+            },
+        )
+        .unwrap();
+        plan.add(
+            "unset",
+            None,
+            || -> zbus::Result<u8> { panic!("read") },
+            |_| Ok(()),
+        )
+        .unwrap();
+        assert!(plan.steps.is_empty());
+
+        let s = set.clone();
+        plan.add(
+            "differs",
+            Some(&60u8),
+            || Ok(80),
+            move |v| {
+                s.set(Some(v));
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(plan.steps.len(), 1);
+        let (change, step) = &plan.steps[0];
+        assert_eq!(
+            (
+                change.setting.as_str(),
+                change.current.as_str(),
+                change.desired.as_str()
+            ),
+            ("differs", "80", "60")
+        );
+        assert_eq!(set.get(), None);
+        step().unwrap();
+        assert_eq!(set.get(), Some(60));
+    }
+
+    #[test]
+    fn power_merge() {
+        let current = LaptopAuraPower {
+            states: vec![
+                power(PowerZones::Keyboard, true),
+                power(PowerZones::Logo, true),
+                power(PowerZones::Lightbar, false),
+            ],
+        };
+
+        // Matching zones are no change
+        let same = [power(
+            PowerZones::Logo,
+            true,
+        )];
+        assert!(merge_power("aura", &current, &same).unwrap().is_none());
+
+        let wanted = [
+            power(PowerZones::Logo, false),
+            power(PowerZones::Lightbar, false),
+        ];
+        let (change, merged) = merge_power("aura", &current, &wanted).unwrap().unwrap();
+        assert_eq!(change.setting, "aura.power");
+        assert_eq!(change.current, "Logo: boot, awake, sleep, shutdown");
+        assert_eq!(change.desired, "Logo: off");
+        assert_eq!(merged.states, [
+            power(PowerZones::Keyboard, true),
+            power(PowerZones::Logo, false),
+            power(PowerZones::Lightbar, false),
+        ]);
+
+        assert!(merge_power("aura", &current, &[power(
+            PowerZones::Lid,
+            true
+        )])
+        .is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use cli_docs::Shell;
 use gumdrop::Options;
 use rog_platform::platform::PlatformProfile;
 
@@ -15,6 +16,8 @@ pub struct CliStart {
     pub version: bool,
     #[options(help = "show supported functions of this laptop")]
     pub show_supported: bool,
+    #[options(help = "print the results as JSON, this must come before any command")]
+    pub json: bool,
     #[options(meta = "", help = "<off, low, med, high>")]
     pub kbd_bright: Option<LedBrightness>,
     #[options(help = "Toggle to next keyboard brightness")]
@@ -56,6 +59,16 @@ pub enum CliCommand {
     Armoury(ArmouryCommand),
     #[options(name = "backlight", help = "Set screen backlight levels")]
     Backlight(BacklightCommand),
+    #[options(help = "Show the state of everything asusd controls")]
+    Status(StatusCommand),
+    #[options(help = "Interactive terminal interface for all of the above")]
+    Tui(TuiCommand),
+    #[options(help = "Bring the laptop to the state described in a RON file")]
+    Apply(ApplyCommand),
+    #[options(help = "Print a shell completion script")]
+    Completions(CompletionsCommand),
+    #[options(help = "Write the man pages of asusctl and each command to a directory")]
+    ManPages(ManPagesCommand),
 }
 
 #[derive(Debug, Clone, Options)]
@@ -88,6 +101,52 @@ pub struct LedModeCommand {
     pub command: Option<SetAuraBuiltin>,
 }
 
+#[derive(Options)]
+pub struct StatusCommand {
+    #[options(help = "print help message")]
+    pub help: bool,
+    #[options(help = "keep running and redraw whenever asusd reports a change")]
+    pub watch: bool,
+}
+
+#[derive(Debug, Clone, Options)]
+pub struct TuiCommand {
+    #[options(help = "print help message")]
+    pub help: bool,
+}
+
+#[derive(Debug, Clone, Options)]
+pub struct ApplyCommand {
+    #[options(help = "print help message")]
+    pub help: bool,
+    #[options(help = "print what would be changed without changing it")]
+    pub dry_run: bool,
+    #[options(free, help = "the state file, or - to read it from stdin")]
+    pub file: Option<String>,
+}
+
+#[derive(Debug, Clone, Options)]
+pub struct CompletionsCommand {
+    #[options(help = "print help message")]
+    pub help: bool,
+    #[options(free, help = "the shell to complete in <bash, zsh, fish>")]
+    pub shell: Option<Shell>,
+    #[options(
+        no_short,
+        meta = "",
+        help = "print the values this laptop supports for a completion, used by the scripts"
+    )]
+    pub values: Option<String>,
+}
+
+#[derive(Debug, Clone, Options)]
+pub struct ManPagesCommand {
+    #[options(help = "print help message")]
+    pub help: bool,
+    #[options(free, help = "the directory to write the pages to")]
+    pub dir: Option<String>,
+}
+
 #[derive(Options)]
 pub struct GraphicsCommand {
     #[options(help = "print help message")]
@@ -121,4 +180,16 @@ pub struct BacklightCommand {
         help = "Set screenpad brightness to sync with primary display"
     )]
     pub sync_screenpad_brightness: Option<bool>,
+    #[options(
+        meta = "",
+        help = "Turn the screenpad off after this many seconds without input, 0 to disable"
+    )]
+    pub screenpad_idle_timeout: Option<u32>,
+    #[options(
+        meta = "",
+        help = "Set screenpad brightness from the ambient light sensor"
+    )]
+    pub screenpad_ambient: Option<bool>,
+    #[options(help = "Turn the screenpad on or off with a fade")]
+    pub toggle_screenpad: bool,
 }
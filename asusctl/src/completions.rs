@@ -0,0 +1,152 @@
+//! Shell completions and man pages of asusctl, and the values the completion
+//! scripts ask asusd for.
+
+use std::fs;
+use std::path::Path;
+
+use cli_docs::{Command, Values};
+use gumdrop::Options;
+use rog_aura::AuraModeNum;
+use rog_dbus::asus_armoury::AsusArmouryProxyBlocking;
+use rog_dbus::zbus_aura::AuraProxyBlocking;
+use rog_dbus::zbus_platform::PlatformProxyBlocking;
+use rog_slash::SlashMode;
+use zbus::blocking::Connection;
+
+use crate::cli_opts::{CliStart, CompletionsCommand, ManPagesCommand};
+use crate::find_iface;
+
+const PROFILES: &str = "profiles";
+const AURA_MODES: &str = "aura-modes";
+const ARMOURY: &str = "armoury";
+
+/// The whole command line of asusctl
+fn cli() -> Command {
+    let mut cli = Command::from_options::<CliStart>("asusctl", "control ASUS ROG laptops");
+    let profiles = Values::Query(PROFILES.to_owned());
+    cli.set_values(&["profile"], "profile-set", profiles.clone());
+    cli.set_values(&["fan-curve"], "mod-profile", profiles);
+    cli.set_values(&["slash"], "mode", Values::List(SlashMode::list().to_vec()));
+    cli.set_values(
+        &["scsi"],
+        "mode",
+        Values::List(rog_scsi::AuraMode::list().to_vec()),
+    );
+    for command in [
+        "image", "pixel-image", "gif", "pixel-gif", "video",
+    ] {
+        cli.set_values(
+            &[
+                "anime", command,
+            ],
+            "path",
+            Values::Path,
+        );
+    }
+    cli.set_values(&["armoury"], "free", Values::Query(ARMOURY.to_owned()));
+    cli.set_values(&["apply"], "file", Values::Path);
+    cli.set_values(&["man-pages"], "dir", Values::Path);
+    if let Some(aura) = cli.find_mut(&["aura"]) {
+        aura.command_query = Some(AURA_MODES.to_owned());
+    }
+    cli
+}
+
+pub fn handle_completions(cmd: &CompletionsCommand) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(key) = &cmd.values {
+        print_values(key);
+        return Ok(());
+    }
+    match cmd.shell {
+        Some(shell) if !cmd.help => {
+            print!("{}", cli().completions(shell));
+        }
+        _ => println!("{}", cmd.self_usage()),
+    }
+    Ok(())
+}
+
+pub fn handle_man_pages(cmd: &ManPagesCommand) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(dir) = cmd.dir.as_ref().filter(|_| !cmd.help) else {
+        println!("{}", cmd.self_usage());
+        return Ok(());
+    };
+    let dir = Path::new(dir);
+    fs::create_dir_all(dir)?;
+    for (name, page) in cli().man_pages(env!("CARGO_PKG_VERSION")) {
+        fs::write(dir.join(name), page)?;
+    }
+    Ok(())
+}
+
+/// Print one value a line for the completion `key`. Nothing is printed if
+/// asusd can't be reached, the shell then suggests nothing, other than for the
+/// aura modes which are then all printed.
+fn print_values(key: &str) {
+    match key {
+        PROFILES => {
+            if let Some(platform) = platform_proxy() {
+                for profile in platform.platform_profile_choices().unwrap_or_default() {
+                    println!("{}", <&str>::from(profile));
+                }
+            }
+        }
+        AURA_MODES => {
+            let modes = supported_aura_modes();
+            let mut cli = cli();
+            let commands = cli
+                .find_mut(&["aura"])
+                .map(|aura| aura.commands.as_slice())
+                .unwrap_or_default();
+            // The commands are named from the modes, `RainbowCycle` being
+            // `rainbow-cycle`
+            for command in commands.iter().map(|c| &c.name) {
+                let name = command.replace('-', "");
+                if modes.as_ref().is_none_or(|modes| {
+                    modes
+                        .iter()
+                        .any(|mode| <&str>::from(mode).to_lowercase() == name)
+                }) {
+                    println!("{command}");
+                }
+            }
+        }
+        ARMOURY => {
+            if platform_proxy().is_none() {
+                return;
+            }
+            let Ok(attrs) = find_iface::<AsusArmouryProxyBlocking>("xyz.ljones.AsusArmoury") else {
+                return;
+            };
+            for attr in attrs {
+                if let Ok(name) = attr.name() {
+                    println!("{}", <&str>::from(name));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The platform interface if asusd is running
+fn platform_proxy() -> Option<PlatformProxyBlocking<'static>> {
+    let conn = Connection::system().ok()?;
+    let platform = PlatformProxyBlocking::new(&conn).ok()?;
+    platform.version().ok()?;
+    Some(platform)
+}
+
+/// The modes supported by any of the aura devices, `None` if asusd isn't
+/// running
+fn supported_aura_modes() -> Option<Vec<AuraModeNum>> {
+    platform_proxy()?;
+    let mut modes: Vec<AuraModeNum> = Vec::new();
+    for aura in find_iface::<AuraProxyBlocking>("xyz.ljones.Aura").ok()? {
+        for mode in aura.supported_basic_modes().unwrap_or_default() {
+            if !modes.contains(&mode) {
+                modes.push(mode);
+            }
+        }
+    }
+    Some(modes)
+}
@@ -0,0 +1,449 @@
+//! Machine readable output for `asusctl --json`.
+//!
+//! A run prints exactly one JSON object on stdout. Each query fills in its
+//! field of [`Report`] and fields that were not asked for are left out, so a
+//! set command that succeeds prints `{}`. A failure prints an [`ErrorReport`]
+//! instead and exits with status 1. Enum values are the variant names used
+//! over dbus, such as `"Balanced"` or `"Static"`.
+//!
+//! The layout is documented in the README. Fields may be added, but existing
+//! ones are not renamed or removed.
+
+use std::error::Error;
+use std::fmt;
+
+use rog_aura::keyboard::LaptopAuraPower;
+use rog_aura::{AuraDeviceType, AuraModeNum, AuraZone, LedBrightness, PowerZones};
+use rog_dbus::asus_armoury::AsusArmouryProxyBlocking;
+use rog_dbus::scsi_aura::ScsiAuraProxyBlocking;
+use rog_dbus::zbus_aura::AuraProxyBlocking;
+use rog_dbus::zbus_backlight::BacklightProxyBlocking;
+use rog_platform::platform::{PlatformProfile, Properties};
+use rog_profiles::error::ProfileError;
+use rog_profiles::fan_curve_set::CurveData;
+use serde::{Deserialize, Serialize};
+
+use crate::apply::Applied;
+use crate::status::Status;
+
+/// Everything queried by one invocation
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<Version>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supported: Option<Supported>,
+    /// One per aura device
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyboard_brightness: Option<Vec<LedBrightness>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<Profile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fan_curves: Option<Vec<ProfileCurves>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub armoury: Option<Vec<Attribute>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aura: Option<Vec<AuraDevice>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backlight: Option<Vec<Backlight>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slash_modes: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scsi: Option<Vec<ScsiDrive>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scsi_modes: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub apply: Option<Applied>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Version {
+    pub asusctl: String,
+    pub asusd: String,
+    pub product_family: String,
+    pub board_name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Supported {
+    /// dbus interface names, such as `xyz.ljones.Platform`
+    pub interfaces: Vec<String>,
+    pub platform_properties: Vec<Properties>,
+    /// Of the first aura device, `null` if there is none
+    pub aura: Option<SupportedAura>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SupportedAura {
+    pub brightness: Vec<LedBrightness>,
+    pub modes: Vec<AuraModeNum>,
+    pub zones: Vec<AuraZone>,
+    pub power_zones: Vec<PowerZones>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Profile {
+    pub active: PlatformProfile,
+    pub choices: Vec<PlatformProfile>,
+}
+
+/// The curves of each fan, `FanCurvePU`, for one profile
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProfileCurves {
+    pub profile: PlatformProfile,
+    pub curves: Vec<CurveData>,
+}
+
+/// An armoury firmware attribute. Values the attribute does not have are
+/// `null`.
+#[derive(Debug, Serialize)]
+pub struct Attribute {
+    pub name: String,
+    pub current: Option<i32>,
+    pub default: Option<i32>,
+    pub min: Option<i32>,
+    pub max: Option<i32>,
+    pub possible_values: Option<Vec<i32>>,
+}
+
+impl Attribute {
+    pub fn query(attr: &AsusArmouryProxyBlocking) -> zbus::Result<Self> {
+        let available = attr.available_attrs()?;
+        let has = |name: &str| available.iter().any(|a| a == name);
+        Ok(Self {
+            name: <&str>::from(attr.name()?).to_owned(),
+            current: has("current_value")
+                .then(|| attr.current_value())
+                .transpose()?,
+            default: has("default_value")
+                .then(|| attr.default_value())
+                .transpose()?,
+            min: has("min_value").then(|| attr.min_value()).transpose()?,
+            max: has("max_value").then(|| attr.max_value()).transpose()?,
+            possible_values: has("possible_values")
+                .then(|| attr.possible_values())
+                .transpose()?,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuraDevice {
+    pub device_type: AuraDeviceType,
+    pub brightness: LedBrightness,
+    pub mode: AuraModeNum,
+    pub supported_modes: Vec<AuraModeNum>,
+    pub power: LaptopAuraPower,
+}
+
+impl AuraDevice {
+    pub fn query(aura: &AuraProxyBlocking) -> zbus::Result<Self> {
+        Ok(Self {
+            device_type: aura.device_type()?,
+            brightness: aura.brightness()?,
+            mode: aura.led_mode()?,
+            supported_modes: aura.supported_basic_modes()?,
+            power: aura.led_power()?,
+        })
+    }
+}
+
+/// The screenpad values are `null` if the laptop has no screenpad
+#[derive(Debug, Serialize)]
+pub struct Backlight {
+    pub primary_brightness: i32,
+    pub screenpad_brightness: Option<i32>,
+    pub screenpad_gamma: Option<String>,
+    pub screenpad_sync_with_primary: Option<bool>,
+    pub screenpad_idle_timeout: Option<u32>,
+    pub screenpad_ambient_brightness: Option<bool>,
+}
+
+impl Backlight {
+    pub fn query(backlight: &BacklightProxyBlocking) -> zbus::Result<Self> {
+        // asusd has the interface for the primary backlight alone, and gives
+        // `NotSupported` for the screenpad brightness if there is no screenpad
+        let screenpad = backlight.screenpad_brightness().ok();
+        let has = screenpad.is_some();
+        Ok(Self {
+            primary_brightness: backlight.primary_brightness()?,
+            screenpad_brightness: screenpad,
+            screenpad_gamma: has.then(|| backlight.screenpad_gamma()).transpose()?,
+            screenpad_sync_with_primary: has
+                .then(|| backlight.screenpad_sync_with_primary())
+                .transpose()?,
+            screenpad_idle_timeout: has
+                .then(|| backlight.screenpad_idle_timeout())
+                .transpose()?,
+            screenpad_ambient_brightness: has
+                .then(|| backlight.screenpad_ambient_brightness())
+                .transpose()?,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScsiDrive {
+    pub enabled: bool,
+    pub mode: rog_scsi::AuraEffect,
+    /// What the drive is showing, `direct_colours` is empty unless it is in
+    /// direct mode
+    pub device_mode: rog_scsi::AuraEffect,
+    pub direct_colours: Vec<rog_scsi::Colour>,
+}
+
+impl ScsiDrive {
+    pub fn query(scsi: &ScsiAuraProxyBlocking) -> zbus::Result<Self> {
+        Ok(Self {
+            enabled: scsi.enabled()?,
+            mode: scsi.led_mode_data()?,
+            device_mode: scsi.device_effect()?,
+            direct_colours: scsi.device_direct_colours()?,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorReport {
+    pub error: ErrorBody,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// Missing or conflicting arguments
+    Usage,
+    /// An argument could not be parsed or is out of range
+    InvalidArgument,
+    /// The laptop or kernel does not have the feature
+    NotSupported,
+    /// asusd could not be reached or rejected the call
+    Dbus,
+    /// asusctl and asusd are different versions
+    VersionMismatch,
+    Other,
+}
+
+/// An error with its [`ErrorKind`] given by asusctl itself, rather than
+/// worked out from an error returned by asusd
+#[derive(Debug)]
+pub struct CliError {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl CliError {
+    /// Returned in place of printing usage text when `--json` is set
+    pub fn usage(message: &str) -> Box<Self> {
+        Box::new(Self {
+            kind: ErrorKind::Usage,
+            message: message.to_owned(),
+        })
+    }
+
+    pub fn invalid_argument(message: &str) -> Box<Self> {
+        Box::new(Self {
+            kind: ErrorKind::InvalidArgument,
+            message: message.to_owned(),
+        })
+    }
+
+    pub fn not_supported(message: &str) -> Box<Self> {
+        Box::new(Self {
+            kind: ErrorKind::NotSupported,
+            message: message.to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for CliError {}
+
+impl ErrorKind {
+    pub fn of(err: &(dyn Error + 'static)) -> Self {
+        if let Some(e) = err.downcast_ref::<CliError>() {
+            e.kind
+        } else if err.is::<std::num::ParseIntError>() {
+            Self::InvalidArgument
+        } else if let Some(ProfileError::NotSupported) = err.downcast_ref::<ProfileError>() {
+            Self::NotSupported
+        } else if let Some(e) = err.downcast_ref::<zbus::Error>() {
+            match e {
+                zbus::Error::FDO(e) => Self::of_fdo(e),
+                zbus::Error::MethodError(name, ..) => match name.as_str() {
+                    "org.freedesktop.DBus.Error.InvalidArgs" => Self::InvalidArgument,
+                    "org.freedesktop.DBus.Error.NotSupported" => Self::NotSupported,
+                    _ => Self::Dbus,
+                },
+                _ => Self::Dbus,
+            }
+        } else if let Some(e) = err.downcast_ref::<zbus::fdo::Error>() {
+            Self::of_fdo(e)
+        } else {
+            Self::Other
+        }
+    }
+
+    fn of_fdo(err: &zbus::fdo::Error) -> Self {
+        match err {
+            zbus::fdo::Error::InvalidArgs(_) => Self::InvalidArgument,
+            zbus::fdo::Error::NotSupported(_) => Self::NotSupported,
+            _ => Self::Dbus,
+        }
+    }
+}
+
+pub fn print<T: Serialize>(value: &T) {
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => println!("{json}"),
+        Err(e) => exit_with_error(ErrorKind::Other, &e.to_string()),
+    }
+}
+
+/// Print `value` on a single line, for streaming output
+pub fn print_line<T: Serialize>(value: &T) {
+    match serde_json::to_string(value) {
+        Ok(json) => println!("{json}"),
+        Err(e) => exit_with_error(ErrorKind::Other, &e.to_string()),
+    }
+}
+
+/// Print an [`ErrorReport`] and exit with status 1
+pub fn exit_with_error(kind: ErrorKind, message: &str) -> ! {
+    print(&ErrorReport {
+        error: ErrorBody {
+            kind,
+            message: message.to_owned(),
+        },
+    });
+    std::process::exit(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use rog_aura::LedBrightness;
+    use rog_platform::platform::PlatformProfile;
+
+    use super::{
+        Attribute, Backlight, ErrorBody, ErrorKind, ErrorReport, Profile, Report, Version,
+    };
+    use crate::apply::{Applied, Change};
+
+    /// Compare against a file in `tests/data/json`, so that a renamed or
+    /// removed field shows up as a changed file in review
+    fn check(value: &impl serde::Serialize, expected: &str) {
+        let json = serde_json::to_string_pretty(value).unwrap();
+        assert_eq!(json, expected.trim_end());
+    }
+
+    #[test]
+    fn empty_report() {
+        check(&Report::default(), "{}");
+    }
+
+    #[test]
+    fn report() {
+        let report = Report {
+            version: Some(Version {
+                asusctl: "6.1.0".to_owned(),
+                asusd: "6.1.0".to_owned(),
+                product_family: "ROG Zephyrus G14".to_owned(),
+                board_name: "GA402X".to_owned(),
+            }),
+            keyboard_brightness: Some(vec![LedBrightness::Med]),
+            profile: Some(Profile {
+                active: PlatformProfile::Balanced,
+                choices: vec![
+                    PlatformProfile::Balanced,
+                    PlatformProfile::Performance,
+                    PlatformProfile::Quiet,
+                ],
+            }),
+            armoury: Some(vec![
+                Attribute {
+                    name: "ppt_pl1_spl".to_owned(),
+                    current: Some(45),
+                    default: Some(45),
+                    min: Some(15),
+                    max: Some(80),
+                    possible_values: None,
+                },
+            ]),
+            backlight: Some(vec![
+                Backlight {
+                    primary_brightness: 120,
+                    screenpad_brightness: Some(80),
+                    screenpad_gamma: Some("1.0".to_owned()),
+                    screenpad_sync_with_primary: Some(false),
+                    screenpad_idle_timeout: Some(0),
+                    screenpad_ambient_brightness: Some(false),
+                },
+                Backlight {
+                    primary_brightness: 50,
+                    screenpad_brightness: None,
+                    screenpad_gamma: None,
+                    screenpad_sync_with_primary: None,
+                    screenpad_idle_timeout: None,
+                    screenpad_ambient_brightness: None,
+                },
+            ]),
+            slash_modes: Some(vec![
+                "Bounce".to_owned(),
+                "Static".to_owned(),
+            ]),
+            apply: Some(Applied {
+                changes: vec![Change {
+                    setting: "platform.charge_limit".to_owned(),
+                    current: "100".to_owned(),
+                    desired: "80".to_owned(),
+                }],
+                dry_run: true,
+            }),
+            ..Default::default()
+        };
+        check(&report, include_str!("../tests/data/json/report.json"));
+    }
+
+    #[test]
+    fn error_report() {
+        let report = ErrorReport {
+            error: ErrorBody {
+                kind: ErrorKind::NotSupported,
+                message: "Slash is not supported on this laptop".to_owned(),
+            },
+        };
+        check(
+            &report,
+            include_str!("../tests/data/json/error_report.json"),
+        );
+    }
+
+    #[test]
+    fn error_kinds() {
+        let kinds = [
+            ErrorKind::Usage,
+            ErrorKind::InvalidArgument,
+            ErrorKind::NotSupported,
+            ErrorKind::Dbus,
+            ErrorKind::VersionMismatch,
+            ErrorKind::Other,
+        ];
+        assert_eq!(
+            serde_json::to_string(&kinds).unwrap(),
+            r#"["usage","invalid_argument","not_supported","dbus","version_mismatch","other"]"#
+        );
+    }
+}
@@ -3,15 +3,19 @@ use std::env::args;
 use std::path::Path;
 use std::process::Command;
 use std::thread::sleep;
+use std::time::Duration;
 
 use anime_cli::{AnimeActions, AnimeCommand};
 use aura_cli::{LedPowerCommand1, LedPowerCommand2};
 use dmi_id::DMIID;
 use fan_curve_cli::FanCurveCommand;
 use gumdrop::{Opt, Options};
+use json::{CliError, ErrorKind, Report};
 use log::{error, info};
 use rog_anime::usb::get_anime_type;
-use rog_anime::{AnimTime, AnimeDataBuffer, AnimeDiagonal, AnimeGif, AnimeImage, AnimeType, Vec2};
+use rog_anime::{
+    AnimTime, AnimeDataBuffer, AnimeDiagonal, AnimeGif, AnimeImage, AnimeType, AnimeVideo, Vec2,
+};
 use rog_aura::keyboard::{AuraPowerState, LaptopAuraPower};
 use rog_aura::{self, AuraDeviceType, AuraEffect, PowerZones};
 use rog_dbus::asus_armoury::AsusArmouryProxyBlocking;
@@ -35,48 +39,99 @@ use zbus::blocking::Connection;
 use crate::aura_cli::{AuraPowerStates, LedBrightness};
 use crate::cli_opts::*;
 use crate::slash_cli::SlashCommand;
+use crate::status::Status;
 
 mod anime_cli;
+mod apply;
 mod aura_cli;
 mod cli_opts;
+mod completions;
 mod fan_curve_cli;
+mod json;
 mod scsi_cli;
 mod slash_cli;
+mod status;
+mod tui;
 
 fn main() {
+    let args: Vec<String> = args().skip(1).collect();
+    let json = args.iter().any(|arg| arg == "--json");
+    // The completion scripts read the values printed by `completions`
+    let docs = args
+        .iter()
+        .find(|arg| !arg.starts_with('-'))
+        .is_some_and(|arg| arg == "completions" || arg == "man-pages");
+
     let mut logger = env_logger::Builder::new();
     logger
         .parse_default_env()
-        .target(env_logger::Target::Stdout)
+        // Keep stdout for the JSON document alone
+        .target(if json || docs {
+            env_logger::Target::Stderr
+        } else {
+            env_logger::Target::Stdout
+        })
         .format_timestamp(None)
         .filter_level(log::LevelFilter::Debug)
         .init();
 
     let self_version = env!("CARGO_PKG_VERSION");
-    println!("Starting version {self_version}");
-    let args: Vec<String> = args().skip(1).collect();
-
     let missing_argument_k = gumdrop::Error::missing_argument(Opt::Short('k'));
     let parsed = match CliStart::parse_args_default(&args) {
         Ok(p) => p,
         Err(err) if err.to_string() == missing_argument_k.to_string() => CliStart {
             kbd_bright: Some(LedBrightness::new(None)),
+            json,
             ..Default::default()
         },
         Err(err) => {
+            if json {
+                json::exit_with_error(ErrorKind::Usage, &err.to_string());
+            }
             println!("Error: {}", err);
             return;
         }
     };
+    let json = parsed.json;
 
-    let conn = Connection::system().unwrap();
+    // These need neither asusd nor the system bus
+    let docs_result = match &parsed.command {
+        Some(CliCommand::Completions(_) | CliCommand::ManPages(_)) if json => {
+            json::exit_with_error(ErrorKind::Usage, "this command can not be used with --json")
+        }
+        Some(CliCommand::Completions(cmd)) => Some(completions::handle_completions(cmd)),
+        Some(CliCommand::ManPages(cmd)) => Some(completions::handle_man_pages(cmd)),
+        _ => None,
+    };
+    if let Some(result) = docs_result {
+        if let Err(err) = result {
+            println!("Error: {err}");
+        }
+        return;
+    }
+    if !json {
+        println!("Starting version {self_version}");
+    }
+
+    let conn = match Connection::system() {
+        Ok(conn) => conn,
+        Err(e) if json => json::exit_with_error(ErrorKind::Dbus, &e.to_string()),
+        Err(e) => panic!("Could not connect to the system bus: {e}"),
+    };
     if let Ok(platform_proxy) = PlatformProxyBlocking::new(&conn).map_err(|e| {
+        if json {
+            json::exit_with_error(ErrorKind::Dbus, &e.to_string());
+        }
         check_service("asusd");
         println!("\nError: {e}\n");
         print_info();
     }) {
         let asusd_version = match platform_proxy.version() {
             Ok(version) => version,
+            Err(e) if json => json::exit_with_error(
+                ErrorKind::Dbus,
+                &format!("Could not get asusd version: {e}"),
+            ),
             Err(e) => {
                 error!(
                     "Could not get asusd version: {e:?}\nIs asusd.service running? {}",
@@ -87,12 +142,22 @@ fn main() {
         };
 
         if asusd_version != self_version {
+            if json {
+                json::exit_with_error(
+                    ErrorKind::VersionMismatch,
+                    &format!("asusctl = {self_version}, asusd = {asusd_version}"),
+                );
+            }
             println!("Version mismatch: asusctl = {self_version}, asusd = {asusd_version}");
             return;
         }
 
         let supported_properties = match platform_proxy.supported_properties() {
             Ok(props) => props,
+            Err(e) if json => json::exit_with_error(
+                ErrorKind::Dbus,
+                &format!("Could not get supported properties: {e}"),
+            ),
             Err(e) => {
                 error!("Could not get supported properties: {e:?}");
                 return;
@@ -100,20 +165,47 @@ fn main() {
         };
         let supported_interfaces = match list_iface_blocking() {
             Ok(ifaces) => ifaces,
+            Err(e) if json => json::exit_with_error(
+                ErrorKind::Dbus,
+                &format!("Could not get supported interfaces: {e}"),
+            ),
             Err(e) => {
                 error!("Could not get supported interfaces: {e:?}");
                 return;
             }
         };
 
+        let mut report = json.then(Report::default);
         if parsed.version {
-            println!("asusctl v{}", env!("CARGO_PKG_VERSION"));
-            println!();
-            print_info();
+            if let Some(report) = report.as_mut() {
+                let dmi = DMIID::new().unwrap_or_default();
+                report.version = Some(json::Version {
+                    asusctl: self_version.to_owned(),
+                    asusd: asusd_version,
+                    product_family: dmi.product_family.trim().to_owned(),
+                    board_name: dmi.board_name.trim().to_owned(),
+                });
+            } else {
+                println!("asusctl v{}", env!("CARGO_PKG_VERSION"));
+                println!();
+                print_info();
+            }
         }
 
-        if let Err(err) = do_parsed(&parsed, &supported_interfaces, &supported_properties, conn) {
-            print_error_help(&*err, &supported_interfaces, &supported_properties);
+        match do_parsed(
+            &parsed,
+            &supported_interfaces,
+            &supported_properties,
+            conn,
+            &mut report,
+        ) {
+            Ok(()) => {
+                if let Some(report) = report {
+                    json::print(&report);
+                }
+            }
+            Err(err) if json => json::exit_with_error(ErrorKind::of(&*err), &err.to_string()),
+            Err(err) => print_error_help(&*err, &supported_interfaces, &supported_properties),
         }
     }
 }
@@ -179,7 +271,7 @@ where
         }
     }
     if paths.len() > 1 {
-        println!("Multiple asusd interfaces devices found");
+        info!("Multiple asusd interfaces devices found");
     }
     if !paths.is_empty() {
         let mut ctrl = Vec::new();
@@ -195,7 +287,16 @@ where
         return Ok(ctrl);
     }
 
-    Err(format!("Did not find {iface_name}").into())
+    let msg = format!("Did not find {iface_name}");
+    Err(CliError::not_supported(&msg))
+}
+
+fn query_aura_devices() -> Result<Vec<json::AuraDevice>, Box<dyn std::error::Error>> {
+    let mut devices = Vec::new();
+    for aura in find_iface::<AuraProxyBlocking>("xyz.ljones.Aura")? {
+        devices.push(json::AuraDevice::query(&aura)?);
+    }
+    Ok(devices)
 }
 
 fn do_parsed(
@@ -203,32 +304,47 @@ fn do_parsed(
     supported_interfaces: &[String],
     supported_properties: &[Properties],
     conn: Connection,
+    report: &mut Option<Report>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     match &parsed.command {
-        Some(CliCommand::Aura(mode)) => handle_led_mode(mode)?,
-        Some(CliCommand::AuraPowerOld(pow)) => handle_led_power1(pow)?,
-        Some(CliCommand::AuraPower(pow)) => handle_led_power2(pow)?,
+        Some(CliCommand::Aura(mode)) => handle_led_mode(mode, report)?,
+        Some(CliCommand::AuraPowerOld(pow)) => handle_led_power1(pow, report)?,
+        Some(CliCommand::AuraPower(pow)) => handle_led_power2(pow, report)?,
         Some(CliCommand::Profile(cmd)) => {
-            handle_throttle_profile(&conn, supported_properties, cmd)?
+            handle_throttle_profile(&conn, supported_properties, cmd, report)?
         }
         Some(CliCommand::FanCurve(cmd)) => {
-            handle_fan_curve(&conn, cmd)?;
+            handle_fan_curve(&conn, cmd, report)?;
+        }
+        Some(CliCommand::Graphics(_)) if report.is_some() => {
+            return Err(CliError::not_supported(
+                "Please use supergfxctl for graphics switching",
+            ));
         }
         Some(CliCommand::Graphics(_)) => do_gfx(),
-        Some(CliCommand::Anime(cmd)) => handle_anime(cmd)?,
-        Some(CliCommand::Slash(cmd)) => handle_slash(cmd)?,
-        Some(CliCommand::Scsi(cmd)) => handle_scsi(cmd)?,
-        Some(CliCommand::Armoury(cmd)) => handle_armoury_command(cmd)?,
-        Some(CliCommand::Backlight(cmd)) => handle_backlight(cmd)?,
+        Some(CliCommand::Anime(cmd)) => handle_anime(cmd, report)?,
+        Some(CliCommand::Slash(cmd)) => handle_slash(cmd, report)?,
+        Some(CliCommand::Scsi(cmd)) => handle_scsi(cmd, report)?,
+        Some(CliCommand::Armoury(cmd)) => handle_armoury_command(cmd, report)?,
+        Some(CliCommand::Backlight(cmd)) => handle_backlight(cmd, report)?,
+        Some(CliCommand::Status(cmd)) => handle_status(&conn, supported_interfaces, cmd, report)?,
+        Some(CliCommand::Tui(cmd)) => handle_tui(&conn, supported_interfaces, cmd, report)?,
+        Some(CliCommand::Apply(cmd)) => {
+            apply::handle_apply(&conn, supported_interfaces, cmd, report)?
+        }
+        Some(CliCommand::Completions(cmd)) => completions::handle_completions(cmd)?,
+        Some(CliCommand::ManPages(cmd)) => completions::handle_man_pages(cmd)?,
         None => {
-            if (!parsed.show_supported
+            let missing = !parsed.show_supported
                 && parsed.kbd_bright.is_none()
                 && parsed.chg_limit.is_none()
                 && !parsed.next_kbd_bright
                 && !parsed.prev_kbd_bright
-                && !parsed.one_shot_chg)
-                || parsed.help
-            {
+                && !parsed.one_shot_chg;
+            if report.is_some() && (parsed.help || (missing && !parsed.version)) {
+                return Err(CliError::usage("Missing arg or command"));
+            }
+            if report.is_none() && (missing || parsed.help) {
                 println!("{}", CliStart::usage());
                 println!();
                 if let Some(cmdlist) = CliStart::command_list() {
@@ -315,11 +431,20 @@ fn do_parsed(
                 match brightness.level() {
                     None => {
                         let level = aura.brightness()?;
-                        println!("Current keyboard led brightness: {level:?}");
+                        if let Some(report) = report.as_mut() {
+                            report
+                                .keyboard_brightness
+                                .get_or_insert_with(Vec::new)
+                                .push(level);
+                        } else {
+                            println!("Current keyboard led brightness: {level:?}");
+                        }
                     }
                     Some(level) => aura.set_brightness(rog_aura::LedBrightness::from(level))?,
                 }
             }
+        } else if report.is_some() {
+            return Err(CliError::not_supported("No aura interface found"));
         } else {
             println!("No aura interface found");
         }
@@ -331,6 +456,8 @@ fn do_parsed(
                 let brightness = aura.brightness()?;
                 aura.set_brightness(brightness.next())?;
             }
+        } else if report.is_some() {
+            return Err(CliError::not_supported("No aura interface found"));
         } else {
             println!("No aura interface found");
         }
@@ -342,12 +469,35 @@ fn do_parsed(
                 let brightness = aura.brightness()?;
                 aura.set_brightness(brightness.prev())?;
             }
+        } else if report.is_some() {
+            return Err(CliError::not_supported("No aura interface found"));
         } else {
             println!("No aura interface found");
         }
     }
 
-    if parsed.show_supported {
+    if let (true, Some(report)) = (parsed.show_supported, report.as_mut()) {
+        let aura = match find_iface::<AuraProxyBlocking>("xyz.ljones.Aura") {
+            Ok(aura) => {
+                // TODO: multiple RGB check
+                let Some(aura) = aura.first() else {
+                    return Err(CliError::not_supported("No aura interface found"));
+                };
+                Some(json::SupportedAura {
+                    brightness: aura.supported_brightness()?,
+                    modes: aura.supported_basic_modes()?,
+                    zones: aura.supported_basic_zones()?,
+                    power_zones: aura.supported_power_zones()?,
+                })
+            }
+            Err(_) => None,
+        };
+        report.supported = Some(json::Supported {
+            interfaces: supported_interfaces.to_vec(),
+            platform_properties: supported_properties.to_vec(),
+            aura,
+        });
+    } else if parsed.show_supported {
         println!("Supported Core Functions:\n{:#?}", supported_interfaces);
         println!(
             "Supported Platform Properties:\n{:#?}",
@@ -381,6 +531,49 @@ fn do_parsed(
     Ok(())
 }
 
+fn handle_status(
+    conn: &Connection,
+    supported_interfaces: &[String],
+    cmd: &StatusCommand,
+    report: &mut Option<Report>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if cmd.help {
+        if report.is_some() {
+            return Err(CliError::usage("Help requested"));
+        }
+        println!("{}", cmd.self_usage());
+        return Ok(());
+    }
+
+    if cmd.watch {
+        return status::watch(conn, supported_interfaces, report.is_some());
+    }
+
+    let status = Status::query(conn, supported_interfaces)?;
+    if let Some(report) = report {
+        report.status = Some(status);
+    } else {
+        status.print();
+    }
+    Ok(())
+}
+
+fn handle_tui(
+    conn: &Connection,
+    supported_interfaces: &[String],
+    cmd: &TuiCommand,
+    report: &mut Option<Report>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if report.is_some() {
+        return Err(CliError::usage("tui can not be used with --json"));
+    }
+    if cmd.help {
+        println!("{}", cmd.self_usage());
+        return Ok(());
+    }
+    tui::run(conn, supported_interfaces)
+}
+
 fn do_gfx() {
     println!(
         "Please use supergfxctl for graphics switching. supergfxctl is the result of making \
@@ -389,12 +582,32 @@ fn do_gfx() {
     println!("This command will be removed in future");
 }
 
-fn handle_backlight(cmd: &BacklightCommand) -> Result<(), Box<dyn std::error::Error>> {
+fn handle_backlight(
+    cmd: &BacklightCommand,
+    report: &mut Option<Report>,
+) -> Result<(), Box<dyn std::error::Error>> {
     if (cmd.screenpad_brightness.is_none()
         && cmd.screenpad_gamma.is_none()
-        && cmd.sync_screenpad_brightness.is_none())
+        && cmd.sync_screenpad_brightness.is_none()
+        && cmd.screenpad_idle_timeout.is_none()
+        && cmd.screenpad_ambient.is_none()
+        && !cmd.toggle_screenpad)
         || cmd.help
     {
+        if let Some(report) = report {
+            if cmd.help {
+                return Err(CliError::usage("Help requested"));
+            }
+            let backlights = find_iface::<BacklightProxyBlocking>("xyz.ljones.Backlight")?;
+            report.backlight = Some(
+                backlights
+                    .iter()
+                    .map(json::Backlight::query)
+                    .collect::<Result<_, _>>()?,
+            );
+            return Ok(());
+        }
+
         println!("Missing arg or command\n\n{}", cmd.self_usage());
 
         let backlights = find_iface::<BacklightProxyBlocking>("xyz.ljones.Backlight")?;
@@ -406,6 +619,11 @@ fn handle_backlight(cmd: &BacklightCommand) -> Result<(), Box<dyn std::error::Er
                 "  Sync with primary: {}",
                 backlight.screenpad_sync_with_primary()?
             );
+            println!("  Idle timeout: {}s", backlight.screenpad_idle_timeout()?);
+            println!(
+                "  Ambient brightness: {}",
+                backlight.screenpad_ambient_brightness()?
+            );
         }
 
         return Ok(());
@@ -424,12 +642,27 @@ fn handle_backlight(cmd: &BacklightCommand) -> Result<(), Box<dyn std::error::Er
         if let Some(sync) = cmd.sync_screenpad_brightness {
             backlight.set_screenpad_sync_with_primary(sync)?;
         }
+
+        if let Some(timeout) = cmd.screenpad_idle_timeout {
+            backlight.set_screenpad_idle_timeout(timeout)?;
+        }
+
+        if let Some(ambient) = cmd.screenpad_ambient {
+            backlight.set_screenpad_ambient_brightness(ambient)?;
+        }
+
+        if cmd.toggle_screenpad {
+            backlight.toggle_screenpad()?;
+        }
     }
 
     Ok(())
 }
 
-fn handle_anime(cmd: &AnimeCommand) -> Result<(), Box<dyn std::error::Error>> {
+fn handle_anime(
+    cmd: &AnimeCommand,
+    report: &mut Option<Report>,
+) -> Result<(), Box<dyn std::error::Error>> {
     if (cmd.command.is_none()
         && cmd.enable_display.is_none()
         && cmd.enable_powersave_anim.is_none()
@@ -441,6 +674,9 @@ fn handle_anime(cmd: &AnimeCommand) -> Result<(), Box<dyn std::error::Error>> {
         && !cmd.clear)
         || cmd.help
     {
+        if report.is_some() {
+            return Err(CliError::usage("Missing arg or command"));
+        }
         println!("Missing arg or command\n\n{}", cmd.self_usage());
         if let Some(lst) = cmd.self_command_list() {
             println!("\n{}", lst);
@@ -471,7 +707,7 @@ fn handle_anime(cmd: &AnimeCommand) -> Result<(), Box<dyn std::error::Error>> {
         if let Some(enable) = cmd.off_when_unplugged {
             proxy.set_off_when_unplugged(enable)?;
         }
-        if cmd.off_with_his_head.is_some() {
+        if cmd.off_with_his_head.is_some() && report.is_none() {
             println!("Did Alice _really_ make it back from Wonderland?");
         }
 
@@ -492,13 +728,16 @@ fn handle_anime(cmd: &AnimeCommand) -> Result<(), Box<dyn std::error::Error>> {
             match action {
                 AnimeActions::Image(image) => {
                     if image.help_requested() || image.path.is_empty() {
+                        if report.is_some() {
+                            return Err(CliError::usage("Missing arg or command"));
+                        }
                         println!("Missing arg or command\n\n{}", image.self_usage());
                         if let Some(lst) = image.self_command_list() {
                             println!("\n{}", lst);
                         }
                         return Ok(());
                     }
-                    verify_brightness(image.bright);
+                    verify_brightness(image.bright, report.is_some())?;
 
                     let matrix = AnimeImage::from_png(
                         Path::new(&image.path),
@@ -513,13 +752,16 @@ fn handle_anime(cmd: &AnimeCommand) -> Result<(), Box<dyn std::error::Error>> {
                 }
                 AnimeActions::PixelImage(image) => {
                     if image.help_requested() || image.path.is_empty() {
+                        if report.is_some() {
+                            return Err(CliError::usage("Missing arg or command"));
+                        }
                         println!("Missing arg or command\n\n{}", image.self_usage());
                         if let Some(lst) = image.self_command_list() {
                             println!("\n{}", lst);
                         }
                         return Ok(());
                     }
-                    verify_brightness(image.bright);
+                    verify_brightness(image.bright, report.is_some())?;
 
                     let matrix = AnimeDiagonal::from_png(
                         Path::new(&image.path),
@@ -532,13 +774,16 @@ fn handle_anime(cmd: &AnimeCommand) -> Result<(), Box<dyn std::error::Error>> {
                 }
                 AnimeActions::Gif(gif) => {
                     if gif.help_requested() || gif.path.is_empty() {
+                        if report.is_some() {
+                            return Err(CliError::usage("Missing arg or command"));
+                        }
                         println!("Missing arg or command\n\n{}", gif.self_usage());
                         if let Some(lst) = gif.self_command_list() {
                             println!("\n{}", lst);
                         }
                         return Ok(());
                     }
-                    verify_brightness(gif.bright);
+                    verify_brightness(gif.bright, report.is_some())?;
 
                     let matrix = AnimeGif::from_gif(
                         Path::new(&gif.path),
@@ -566,13 +811,16 @@ fn handle_anime(cmd: &AnimeCommand) -> Result<(), Box<dyn std::error::Error>> {
                 }
                 AnimeActions::PixelGif(gif) => {
                     if gif.help_requested() || gif.path.is_empty() {
+                        if report.is_some() {
+                            return Err(CliError::usage("Missing arg or command"));
+                        }
                         println!("Missing arg or command\n\n{}", gif.self_usage());
                         if let Some(lst) = gif.self_command_list() {
                             println!("\n{}", lst);
                         }
                         return Ok(());
                     }
-                    verify_brightness(gif.bright);
+                    verify_brightness(gif.bright, report.is_some())?;
 
                     let matrix = AnimeGif::from_diagonal_gif(
                         Path::new(&gif.path),
@@ -595,8 +843,47 @@ fn handle_anime(cmd: &AnimeCommand) -> Result<(), Box<dyn std::error::Error>> {
                         }
                     }
                 }
+                AnimeActions::Video(video) => {
+                    if video.help_requested() || video.path.is_empty() {
+                        if report.is_some() {
+                            return Err(CliError::usage("Missing arg or command"));
+                        }
+                        println!("Missing arg or command\n\n{}", video.self_usage());
+                        if let Some(lst) = video.self_command_list() {
+                            println!("\n{}", lst);
+                        }
+                        return Ok(());
+                    }
+                    verify_brightness(video.bright, report.is_some())?;
+
+                    let time = if video.loops == 0 {
+                        AnimTime::Infinite
+                    } else {
+                        AnimTime::Count(video.loops)
+                    };
+                    let matrix = AnimeVideo::new(
+                        Path::new(&video.path),
+                        video.scale,
+                        video.angle,
+                        Vec2::new(video.x_pos, video.y_pos),
+                        time,
+                        video.bright,
+                        video.frame_time.map(Duration::from_millis),
+                        anime_type,
+                    )?;
+
+                    rog_anime::run_video(&matrix, &|frame| {
+                        proxy
+                            .write(frame)
+                            .map_err(|e| rog_anime::error::AnimeError::Dbus(e.to_string()))
+                            .map(|_| false)
+                    });
+                }
                 AnimeActions::SetBuiltins(builtins) => {
                     if builtins.help_requested() || builtins.set.is_none() {
+                        if report.is_some() {
+                            return Err(CliError::usage("Missing arg or command"));
+                        }
                         println!(
                             "\nAny unspecified args will be set to default (first shown var)\n"
                         );
@@ -620,16 +907,24 @@ fn handle_anime(cmd: &AnimeCommand) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn verify_brightness(brightness: f32) {
+fn verify_brightness(brightness: f32, json: bool) -> Result<(), Box<dyn std::error::Error>> {
     if !(0.0..=1.0).contains(&brightness) {
-        println!(
+        let msg = format!(
             "Image and global brightness must be between 0.0 and 1.0 (inclusive), was {}",
             brightness
         );
+        if json {
+            return Err(CliError::invalid_argument(&msg));
+        }
+        println!("{msg}");
     }
+    Ok(())
 }
 
-fn handle_slash(cmd: &SlashCommand) -> Result<(), Box<dyn std::error::Error>> {
+fn handle_slash(
+    cmd: &SlashCommand,
+    report: &mut Option<Report>,
+) -> Result<(), Box<dyn std::error::Error>> {
     if (cmd.brightness.is_none()
         && cmd.interval.is_none()
         && cmd.show_on_boot.is_none()
@@ -644,6 +939,9 @@ fn handle_slash(cmd: &SlashCommand) -> Result<(), Box<dyn std::error::Error>> {
         && !cmd.disable)
         || cmd.help
     {
+        if report.is_some() {
+            return Err(CliError::usage("Missing arg or command"));
+        }
         println!("Missing arg or command\n\n{}", cmd.self_usage());
         if let Some(lst) = cmd.self_command_list() {
             println!("\n{}", lst);
@@ -689,18 +987,35 @@ fn handle_slash(cmd: &SlashCommand) -> Result<(), Box<dyn std::error::Error>> {
     }
     if cmd.list {
         let res = SlashMode::list();
-        for p in &res {
-            println!("{:?}", p);
+        if let Some(report) = report {
+            report.slash_modes = Some(res.to_vec());
+        } else {
+            for p in &res {
+                println!("{:?}", p);
+            }
         }
     }
 
     Ok(())
 }
 
-fn handle_scsi(cmd: &ScsiCommand) -> Result<(), Box<dyn std::error::Error>> {
-    if (!cmd.list && cmd.enable.is_none() && cmd.mode.is_none() && cmd.colours.is_empty())
+fn handle_scsi(
+    cmd: &ScsiCommand,
+    report: &mut Option<Report>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if (!cmd.list
+        && !cmd.read
+        && !cmd.save
+        && cmd.enable.is_none()
+        && cmd.mode.is_none()
+        && cmd.save_to_device.is_none()
+        && cmd.colours.is_empty()
+        && cmd.direct.is_empty())
         || cmd.help
     {
+        if report.is_some() {
+            return Err(CliError::usage("Missing arg or command"));
+        }
         println!("Missing arg or command\n\n{}", cmd.self_usage());
         if let Some(lst) = cmd.self_command_list() {
             println!("\n{}", lst);
@@ -714,9 +1029,12 @@ fn handle_scsi(cmd: &ScsiCommand) -> Result<(), Box<dyn std::error::Error>> {
             scsi.set_enabled(enable)?;
         }
 
+        if let Some(save) = cmd.save_to_device {
+            scsi.set_save_to_device(save)?;
+        }
+
         if let Some(mode) = cmd.mode {
-            dbg!(mode as u8);
-            scsi.set_led_mode(mode).unwrap();
+            scsi.set_led_mode(mode)?;
         }
 
         let mut mode = scsi.led_mode_data()?;
@@ -753,23 +1071,62 @@ fn handle_scsi(cmd: &ScsiCommand) -> Result<(), Box<dyn std::error::Error>> {
             scsi.set_led_mode_data(mode.clone())?;
         }
 
-        // let mode_ret = scsi.led_mode_data()?;
-        // assert_eq!(mode, mode_ret);
-        println!("{mode}");
+        if !cmd.direct.is_empty() {
+            scsi.set_direct_colours(&cmd.direct)?;
+        }
+
+        if cmd.save {
+            scsi.save()?;
+        }
+
+        if let Some(report) = report.as_mut() {
+            report
+                .scsi
+                .get_or_insert_with(Vec::new)
+                .push(json::ScsiDrive::query(&scsi)?);
+        } else if cmd.read {
+            let direct = scsi.device_direct_colours()?;
+            if direct.is_empty() {
+                println!("Drive is showing:\n{}", scsi.device_effect()?);
+            } else {
+                println!("Drive is showing direct colours:");
+                for (led, colour) in direct.iter().enumerate() {
+                    println!("  LED {led}: {colour:?}");
+                }
+            }
+        } else {
+            // let mode_ret = scsi.led_mode_data()?;
+            // assert_eq!(mode, mode_ret);
+            println!("{mode}");
+        }
     }
 
     if cmd.list {
         let res = AuraMode::list();
-        for p in &res {
-            println!("{:?}", p);
+        if let Some(report) = report {
+            report.scsi_modes = Some(res.to_vec());
+        } else {
+            for p in &res {
+                println!("{:?}", p);
+            }
         }
     }
 
     Ok(())
 }
 
-fn handle_led_mode(mode: &LedModeCommand) -> Result<(), Box<dyn std::error::Error>> {
+fn handle_led_mode(
+    mode: &LedModeCommand,
+    report: &mut Option<Report>,
+) -> Result<(), Box<dyn std::error::Error>> {
     if mode.command.is_none() && !mode.prev_mode && !mode.next_mode {
+        if let Some(report) = report {
+            if mode.help {
+                return Err(CliError::usage("Help requested"));
+            }
+            report.aura = Some(query_aura_devices()?);
+            return Ok(());
+        }
         if !mode.help {
             println!("Missing arg or command\n");
         }
@@ -806,6 +1163,9 @@ fn handle_led_mode(mode: &LedModeCommand) -> Result<(), Box<dyn std::error::Erro
     }
 
     if mode.next_mode && mode.prev_mode {
+        if report.is_some() {
+            return Err(CliError::usage("Please specify either next or previous"));
+        }
         println!("Please specify either next or previous");
         return Ok(());
     }
@@ -834,6 +1194,9 @@ fn handle_led_mode(mode: &LedModeCommand) -> Result<(), Box<dyn std::error::Erro
         }
     } else if let Some(mode) = mode.command.as_ref() {
         if mode.help_requested() {
+            if report.is_some() {
+                return Err(CliError::usage("Help requested"));
+            }
             println!("{}", mode.self_usage());
             return Ok(());
         }
@@ -845,11 +1208,14 @@ fn handle_led_mode(mode: &LedModeCommand) -> Result<(), Box<dyn std::error::Erro
     Ok(())
 }
 
-fn handle_led_power1(power: &LedPowerCommand1) -> Result<(), Box<dyn std::error::Error>> {
+fn handle_led_power1(
+    power: &LedPowerCommand1,
+    report: &mut Option<Report>,
+) -> Result<(), Box<dyn std::error::Error>> {
     let aura = find_iface::<AuraProxyBlocking>("xyz.ljones.Aura")?;
     for aura in aura {
         let dev_type = aura.device_type()?;
-        if !dev_type.is_old_laptop() && !dev_type.is_tuf_laptop() {
+        if !dev_type.is_old_laptop() && !dev_type.is_tuf_laptop() && report.is_none() {
             println!("This option applies only to keyboards 2021+");
         }
 
@@ -859,6 +1225,13 @@ fn handle_led_power1(power: &LedPowerCommand1) -> Result<(), Box<dyn std::error:
             && !power.keyboard
             && !power.lightbar
         {
+            if let Some(report) = report {
+                if power.help {
+                    return Err(CliError::usage("Help requested"));
+                }
+                report.aura = Some(query_aura_devices()?);
+                return Ok(());
+            }
             if !power.help {
                 println!("Missing arg or command\n");
             }
@@ -872,7 +1245,11 @@ fn handle_led_power1(power: &LedPowerCommand1) -> Result<(), Box<dyn std::error:
         }
     }
 
-    println!("These options are for keyboards of product ID 0x1866 or TUF only");
+    const ONLY_1866: &str = "These options are for keyboards of product ID 0x1866 or TUF only";
+    if report.is_some() {
+        return Err(CliError::not_supported(ONLY_1866));
+    }
+    println!("{ONLY_1866}");
     Ok(())
 }
 
@@ -905,16 +1282,28 @@ fn handle_led_power_1_do_1866(
     Ok(())
 }
 
-fn handle_led_power2(power: &LedPowerCommand2) -> Result<(), Box<dyn std::error::Error>> {
+fn handle_led_power2(
+    power: &LedPowerCommand2,
+    report: &mut Option<Report>,
+) -> Result<(), Box<dyn std::error::Error>> {
     let aura = find_iface::<AuraProxyBlocking>("xyz.ljones.Aura")?;
     for aura in aura {
         let dev_type = aura.device_type()?;
         if !dev_type.is_new_laptop() {
-            println!("This option applies only to keyboards 2021+");
+            if report.is_none() {
+                println!("This option applies only to keyboards 2021+");
+            }
             continue;
         }
 
         if power.command().is_none() {
+            if let Some(report) = report {
+                if power.help {
+                    return Err(CliError::usage("Help requested"));
+                }
+                report.aura = Some(query_aura_devices()?);
+                return Ok(());
+            }
             if !power.help {
                 println!("Missing arg or command\n");
             }
@@ -934,6 +1323,9 @@ fn handle_led_power2(power: &LedPowerCommand2) -> Result<(), Box<dyn std::error:
 
         if let Some(pow) = power.command.as_ref() {
             if pow.help_requested() {
+                if report.is_some() {
+                    return Err(CliError::usage("Help requested"));
+                }
                 println!("{}", pow.self_usage());
                 return Ok(());
             }
@@ -973,13 +1365,19 @@ fn handle_throttle_profile(
     conn: &Connection,
     supported: &[Properties],
     cmd: &ProfileCommand,
+    report: &mut Option<Report>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     if !supported.contains(&Properties::ThrottlePolicy) {
-        println!("Profiles not supported by either this kernel or by the laptop.");
+        if report.is_none() {
+            println!("Profiles not supported by either this kernel or by the laptop.");
+        }
         return Err(ProfileError::NotSupported.into());
     }
 
     if !cmd.next && !cmd.list && cmd.profile_set.is_none() && !cmd.profile_get {
+        if report.is_some() {
+            return Err(CliError::usage("Missing arg or command"));
+        }
         if !cmd.help {
             println!("Missing arg or command\n");
         }
@@ -1001,6 +1399,16 @@ fn handle_throttle_profile(
         proxy.set_platform_profile(profile)?;
     }
 
+    if let Some(report) = report {
+        if cmd.list || cmd.profile_get {
+            report.profile = Some(json::Profile {
+                active: proxy.platform_profile()?,
+                choices,
+            });
+        }
+        return Ok(());
+    }
+
     if cmd.list {
         for p in &choices {
             println!("{:?}", p);
@@ -1017,14 +1425,21 @@ fn handle_throttle_profile(
 fn handle_fan_curve(
     conn: &Connection,
     cmd: &FanCurveCommand,
+    report: &mut Option<Report>,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let json = report.is_some();
     let Ok(fan_proxy) = FanCurvesProxyBlocking::new(conn).map_err(|e| {
-        println!("Fan-curves not supported by either this kernel or by the laptop: {e:?}");
+        if !json {
+            println!("Fan-curves not supported by either this kernel or by the laptop: {e:?}");
+        }
     }) else {
         return Err(ProfileError::NotSupported.into());
     };
 
     if !cmd.get_enabled && !cmd.default && cmd.mod_profile.is_none() {
+        if json {
+            return Err(CliError::usage("Missing arg or command"));
+        }
         if !cmd.help {
             println!("Missing arg or command\n");
         }
@@ -1036,13 +1451,15 @@ fn handle_fan_curve(
         return Ok(());
     }
 
+    const REQUIRES_PROFILE: &str =
+        "--enable-fan-curves, --enable-fan-curve, --fan, and --data options require --mod-profile";
     if (cmd.enable_fan_curves.is_some() || cmd.fan.is_some() || cmd.data.is_some())
         && cmd.mod_profile.is_none()
     {
-        println!(
-            "--enable-fan-curves, --enable-fan-curve, --fan, and --data options require \
-             --mod-profile"
-        );
+        if json {
+            return Err(CliError::usage(REQUIRES_PROFILE));
+        }
+        println!("{REQUIRES_PROFILE}");
         return Ok(());
     }
 
@@ -1050,8 +1467,15 @@ fn handle_fan_curve(
     if cmd.get_enabled {
         let profile = plat_proxy.platform_profile()?;
         let curves = fan_proxy.fan_curve_data(profile)?;
-        for curve in curves.iter() {
-            println!("{}", String::from(curve));
+        if let Some(report) = report.as_mut() {
+            report
+                .fan_curves
+                .get_or_insert_with(Vec::new)
+                .push(json::ProfileCurves { profile, curves });
+        } else {
+            for curve in curves.iter() {
+                println!("{}", String::from(curve));
+            }
         }
     }
 
@@ -1063,8 +1487,18 @@ fn handle_fan_curve(
     if let Some(profile) = cmd.mod_profile {
         if cmd.enable_fan_curves.is_none() && cmd.data.is_none() {
             let data = fan_proxy.fan_curve_data(profile)?;
-            let ron = ron::ser::to_string_pretty(&data, PrettyConfig::new().depth_limit(4))?;
-            println!("\nFan curves for {:?}\n\n{}", profile, ron);
+            if let Some(report) = report.as_mut() {
+                report
+                    .fan_curves
+                    .get_or_insert_with(Vec::new)
+                    .push(json::ProfileCurves {
+                        profile,
+                        curves: data,
+                    });
+            } else {
+                let ron = ron::ser::to_string_pretty(&data, PrettyConfig::new().depth_limit(4))?;
+                println!("\nFan curves for {:?}\n\n{}", profile, ron);
+            }
         }
 
         if let Some(enabled) = cmd.enable_fan_curves {
@@ -1074,6 +1508,8 @@ fn handle_fan_curve(
         if let Some(enabled) = cmd.enable_fan_curve {
             if let Some(fan) = cmd.fan {
                 fan_proxy.set_profile_fan_curve_enabled(profile, fan, enabled)?;
+            } else if json {
+                return Err(CliError::usage("--enable-fan-curve requires --fan"));
             } else {
                 println!(
                     "--enable-fan-curves, --enable-fan-curve, --fan, and --data options require \
@@ -1169,10 +1605,28 @@ fn print_firmware_attr(attr: &AsusArmouryProxyBlocking) -> Result<(), Box<dyn st
     Ok(())
 }
 
-fn handle_armoury_command(cmd: &ArmouryCommand) -> Result<(), Box<dyn std::error::Error>> {
+fn handle_armoury_command(
+    cmd: &ArmouryCommand,
+    report: &mut Option<Report>,
+) -> Result<(), Box<dyn std::error::Error>> {
     {
         if cmd.free.is_empty() || cmd.free.len() % 2 != 0 || cmd.help {
             const USAGE: &str = "Usage: asusctl platform panel_overdrive 1 nv_dynamic_boost 5";
+            if let Some(report) = report {
+                if !cmd.free.is_empty() || cmd.help {
+                    return Err(CliError::usage(
+                        "Each attribute label must be paired with a setting",
+                    ));
+                }
+                let attrs = find_iface::<AsusArmouryProxyBlocking>("xyz.ljones.AsusArmoury")?;
+                report.armoury = Some(
+                    attrs
+                        .iter()
+                        .map(json::Attribute::query)
+                        .collect::<Result<_, _>>()?,
+                );
+                return Ok(());
+            }
             if cmd.free.len() % 2 != 0 {
                 println!(
                     "Incorrect number of args, each attribute label must be paired with a setting:"
@@ -1193,19 +1647,36 @@ fn handle_armoury_command(cmd: &ArmouryCommand) -> Result<(), Box<dyn std::error
 
         if let Ok(attr) = find_iface::<AsusArmouryProxyBlocking>("xyz.ljones.AsusArmoury") {
             for cmd in cmd.free.chunks(2) {
+                let mut found = false;
                 for attr in attr.iter() {
                     let name = attr.name()?;
                     if <&str>::from(name) == cmd[0] {
+                        found = true;
                         let mut value: i32 = cmd[1].parse()?;
                         if value == -1 {
                             info!("Setting to default");
                             value = attr.default_value()?;
                         }
                         attr.set_current_value(value)?;
-                        print_firmware_attr(attr)?;
+                        if let Some(report) = report.as_mut() {
+                            report
+                                .armoury
+                                .get_or_insert_with(Vec::new)
+                                .push(json::Attribute::query(attr)?);
+                        } else {
+                            print_firmware_attr(attr)?;
+                        }
                     }
                 }
+                if !found && report.is_some() {
+                    return Err(CliError::invalid_argument(&format!(
+                        "No firmware attribute named {}",
+                        cmd[0]
+                    )));
+                }
             }
+        } else if report.is_some() {
+            return Err(CliError::not_supported("No asus-armoury interface found"));
         }
     }
     Ok(())
@@ -30,6 +30,22 @@ pub struct ScsiCommand {
     )]
     pub colours: Vec<Colour>,
 
+    #[options(
+        no_short,
+        meta = "",
+        help = "Set each LED to a colour <hex> directly, specify up to 4 with repeated arg"
+    )]
+    pub direct: Vec<Colour>,
+
+    #[options(help = "read back the state the drive is currently showing")]
+    pub read: bool,
+
+    #[options(no_short, help = "store the current state on the drive so it shows on power up")]
+    pub save: bool,
+
+    #[options(no_short, meta = "", help = "Store each effect on the drive as it is set")]
+    pub save_to_device: Option<bool>,
+
     #[options(help = "list available animations")]
     pub list: bool,
 }
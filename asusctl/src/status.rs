@@ -0,0 +1,274 @@
+//! `asusctl status`, the state of everything asusd controls in one view.
+
+use std::error::Error;
+
+use rog_anime::usb::Brightness;
+use rog_dbus::asus_armoury::AsusArmouryProxyBlocking;
+use rog_dbus::zbus_anime::AnimeProxyBlocking;
+use rog_dbus::zbus_aura::AuraProxyBlocking;
+use rog_dbus::zbus_backlight::BacklightProxyBlocking;
+use rog_dbus::zbus_fan_curves::FanCurvesProxyBlocking;
+use rog_dbus::zbus_platform::PlatformProxyBlocking;
+use rog_dbus::zbus_slash::SlashProxyBlocking;
+use rog_platform::cpu::CPUEPP;
+use rog_platform::platform::PlatformProfile;
+use rog_slash::SlashMode;
+use serde::Serialize;
+use zbus::blocking::{Connection, MessageIterator};
+use zbus::message::Type;
+use zbus::MatchRule;
+
+use crate::find_iface;
+use crate::json::{self, Attribute, AuraDevice, Backlight, ProfileCurves};
+
+/// Width of the labels in the text view
+const LABEL: usize = 22;
+
+#[derive(Debug, Default, Serialize)]
+pub struct Status {
+    pub platform: Platform,
+    pub fan_curves: Vec<ProfileCurves>,
+    pub armoury: Vec<Attribute>,
+    pub aura: Vec<AuraDevice>,
+    pub anime: Vec<Anime>,
+    pub slash: Vec<Slash>,
+    pub backlight: Vec<Backlight>,
+}
+
+/// Values the laptop does not support are `null`
+#[derive(Debug, Default, Serialize)]
+pub struct Platform {
+    pub profile: Option<PlatformProfile>,
+    pub profile_on_ac: Option<PlatformProfile>,
+    pub profile_on_battery: Option<PlatformProfile>,
+    pub epp: Option<CPUEPP>,
+    pub epp_linked: Option<bool>,
+    pub charge_limit: Option<u8>,
+    pub power_plugged: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Anime {
+    pub display_enabled: bool,
+    pub brightness: Brightness,
+    pub builtins_enabled: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Slash {
+    pub enabled: bool,
+    pub mode: SlashMode,
+    pub brightness: u8,
+    pub interval: u8,
+}
+
+impl Status {
+    /// Sections for interfaces asusd does not have are left empty
+    pub fn query(conn: &Connection, interfaces: &[String]) -> Result<Self, Box<dyn Error>> {
+        let has = |iface: &str| interfaces.iter().any(|i| i == iface);
+        let mut status = Self::default();
+
+        let platform = PlatformProxyBlocking::new(conn)?;
+        status.platform = Platform {
+            profile: platform.platform_profile().ok(),
+            profile_on_ac: platform.platform_profile_on_ac().ok(),
+            profile_on_battery: platform.platform_profile_on_battery().ok(),
+            epp: platform.cpu_epp().ok(),
+            epp_linked: platform.platform_profile_linked_epp().ok(),
+            charge_limit: platform.charge_control_end_threshold().ok(),
+            power_plugged: platform.power_plugged().ok(),
+        };
+
+        if has("xyz.ljones.FanCurves") {
+            let fans = FanCurvesProxyBlocking::new(conn)?;
+            for profile in platform.platform_profile_choices().unwrap_or_default() {
+                if let Ok(curves) = fans.fan_curve_data(profile) {
+                    status.fan_curves.push(ProfileCurves { profile, curves });
+                }
+            }
+        }
+
+        if has("xyz.ljones.AsusArmoury") {
+            for attr in find_iface::<AsusArmouryProxyBlocking>("xyz.ljones.AsusArmoury")? {
+                status.armoury.push(Attribute::query(&attr)?);
+            }
+        }
+
+        if has("xyz.ljones.Aura") {
+            for aura in find_iface::<AuraProxyBlocking>("xyz.ljones.Aura")? {
+                status.aura.push(AuraDevice::query(&aura)?);
+            }
+        }
+
+        if has("xyz.ljones.Anime") {
+            for anime in find_iface::<AnimeProxyBlocking>("xyz.ljones.Anime")? {
+                status.anime.push(Anime {
+                    display_enabled: anime.enable_display()?,
+                    brightness: anime.brightness()?,
+                    builtins_enabled: anime.builtins_enabled()?,
+                });
+            }
+        }
+
+        if has("xyz.ljones.Slash") {
+            for slash in find_iface::<SlashProxyBlocking>("xyz.ljones.Slash")? {
+                status.slash.push(Slash {
+                    enabled: slash.enabled()?,
+                    mode: slash.mode()?,
+                    brightness: slash.brightness()?,
+                    interval: slash.interval()?,
+                });
+            }
+        }
+
+        if has("xyz.ljones.Backlight") {
+            for backlight in find_iface::<BacklightProxyBlocking>("xyz.ljones.Backlight")? {
+                status.backlight.push(Backlight::query(&backlight)?);
+            }
+        }
+
+        Ok(status)
+    }
+
+    pub fn print(&self) {
+        let p = &self.platform;
+        println!("Platform");
+        if let Some(profile) = p.profile {
+            let mut value = format!("{profile:?}");
+            if let (Some(ac), Some(bat)) = (p.profile_on_ac, p.profile_on_battery) {
+                value.push_str(&format!(" ({ac:?} on AC, {bat:?} on battery)"));
+            }
+            line("Profile", value);
+        }
+        if let Some(epp) = p.epp {
+            let linked = match p.epp_linked {
+                Some(true) => ", linked to profile",
+                _ => "",
+            };
+            line("EPP", format!("{}{linked}", String::from(epp)));
+        }
+        if let Some(limit) = p.charge_limit {
+            line("Charge limit", format!("{limit}%"));
+        }
+        if let Some(plugged) = p.power_plugged {
+            line("Power", if plugged { "AC" } else { "battery" });
+        }
+
+        if !self.fan_curves.is_empty() {
+            println!("Fan curves");
+            for profile in &self.fan_curves {
+                let fans: Vec<String> = profile
+                    .curves
+                    .iter()
+                    .map(|c| format!("{:?} {}", c.fan, on_off(c.enabled)))
+                    .collect();
+                line(&format!("{:?}", profile.profile), fans.join(", "));
+            }
+        }
+
+        if !self.armoury.is_empty() {
+            println!("Armoury");
+            for attr in &self.armoury {
+                let mut value = attr
+                    .current
+                    .map(|c| c.to_string())
+                    .unwrap_or_else(|| "-".to_owned());
+                if let (Some(min), Some(max)) = (attr.min, attr.max) {
+                    value.push_str(&format!(" ({min}..{max})"));
+                } else if let Some(possible) = &attr.possible_values {
+                    value.push_str(&format!(" {possible:?}"));
+                }
+                line(&attr.name, value);
+            }
+        }
+
+        if !self.aura.is_empty() {
+            println!("Aura");
+            for aura in &self.aura {
+                line(
+                    &format!("{:?}", aura.device_type),
+                    format!("{:?}, brightness {:?}", aura.mode, aura.brightness),
+                );
+            }
+        }
+
+        for anime in &self.anime {
+            println!("AniMe");
+            line("Display", on_off(anime.display_enabled));
+            line("Brightness", format!("{:?}", anime.brightness));
+            line("Builtin animations", on_off(anime.builtins_enabled));
+        }
+
+        for slash in &self.slash {
+            println!("Slash");
+            line("Enabled", on_off(slash.enabled));
+            line("Mode", format!("{:?}", slash.mode));
+            line("Brightness", slash.brightness.to_string());
+            line("Interval", slash.interval.to_string());
+        }
+
+        for backlight in &self.backlight {
+            println!("Backlight");
+            line("Primary", format!("{}%", backlight.primary_brightness));
+            if let (Some(brightness), Some(gamma)) =
+                (backlight.screenpad_brightness, &backlight.screenpad_gamma)
+            {
+                line(
+                    "Screenpad",
+                    format!(
+                        "{brightness}%, gamma {gamma}{}",
+                        if backlight.screenpad_sync_with_primary == Some(true) {
+                            ", synced to primary"
+                        } else {
+                            ""
+                        }
+                    ),
+                );
+            }
+        }
+    }
+}
+
+fn line(label: &str, value: impl AsRef<str>) {
+    println!("  {:<LABEL$}{}", format!("{label}:"), value.as_ref());
+}
+
+pub fn on_off(on: bool) -> &'static str {
+    if on {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+/// Print the status, then again each time asusd emits `PropertiesChanged`
+/// for any of its objects. Text output clears the terminal before each
+/// redraw, JSON output prints one object per line.
+pub fn watch(conn: &Connection, interfaces: &[String], json: bool) -> Result<(), Box<dyn Error>> {
+    let mut changes = property_changes(conn)?;
+    loop {
+        let status = Status::query(conn, interfaces)?;
+        if json {
+            json::print_line(&status);
+        } else {
+            print!("\x1b[2J\x1b[H");
+            status.print();
+        }
+        match changes.next() {
+            Some(Ok(_)) => {}
+            Some(Err(e)) => return Err(e.into()),
+            None => return Ok(()),
+        }
+    }
+}
+
+/// Every `PropertiesChanged` signal asusd emits, for any of its objects
+pub fn property_changes(conn: &Connection) -> zbus::Result<MessageIterator> {
+    let rule = MatchRule::builder()
+        .msg_type(Type::Signal)
+        .interface("org.freedesktop.DBus.Properties")?
+        .member("PropertiesChanged")?
+        .path_namespace("/xyz/ljones")?
+        .build();
+    MessageIterator::for_match_rule(rule, conn, None)
+}
@@ -0,0 +1,247 @@
+//! Aura modes, colours and brightness for each aura device
+
+use std::error::Error;
+
+use ratatui::crossterm::event::KeyCode;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListState, Paragraph};
+use ratatui::Frame;
+use rog_aura::{Colour, Speed};
+
+use super::{cycle, App, LABEL};
+
+/// Change of a colour channel for one key press
+const COLOUR_STEP: u8 = 16;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    #[default]
+    Brightness,
+    /// Channel of the first colour, 0 to 2 for red, green and blue
+    Colour1(usize),
+    Colour2(usize),
+    Speed,
+}
+
+impl Field {
+    const ALL: [Self; 8] = [
+        Self::Brightness,
+        Self::Colour1(0),
+        Self::Colour1(1),
+        Self::Colour1(2),
+        Self::Colour2(0),
+        Self::Colour2(1),
+        Self::Colour2(2),
+        Self::Speed,
+    ];
+}
+
+#[derive(Debug, Default)]
+pub struct State {
+    device: usize,
+    /// Index in to the supported modes of the device
+    mode: usize,
+    field: Field,
+}
+
+fn channel(colour: &mut Colour, index: usize) -> &mut u8 {
+    match index {
+        0 => &mut colour.r,
+        1 => &mut colour.g,
+        _ => &mut colour.b,
+    }
+}
+
+/// The channels of `colour` followed by a swatch of it, with the selected
+/// channel highlighted
+fn colour_spans(colour: Colour, selected: Option<usize>) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span> = [
+        ("R", colour.r),
+        ("G", colour.g),
+        ("B", colour.b),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(i, (name, value))| {
+        let span = Span::raw(format!("{name} {value:<4}"));
+        if selected == Some(i) {
+            span.reversed()
+        } else {
+            span
+        }
+    })
+    .collect();
+    spans.push(Span::styled(
+        "      ",
+        Style::new().bg(Color::Rgb(colour.r, colour.g, colour.b)),
+    ));
+    spans
+}
+
+pub fn draw(app: &App, frame: &mut Frame, area: Rect) {
+    let state = &app.aura;
+    let Some(device) = app.status.aura.get(state.device) else {
+        frame.render_widget(
+            Paragraph::new("No aura devices found").block(Block::bordered().title(" Aura ")),
+            area,
+        );
+        return;
+    };
+    let [modes, settings] = Layout::horizontal([
+        Constraint::Length(24),
+        Constraint::Min(0),
+    ])
+    .areas(area);
+
+    let items: Vec<String> = device
+        .supported_modes
+        .iter()
+        .map(|mode| {
+            let active = if *mode == device.mode { "*" } else { " " };
+            format!("{active} {mode}")
+        })
+        .collect();
+    let mut list_state = ListState::default().with_selected(Some(state.mode));
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(Block::bordered().title(" Modes "))
+            .highlight_style(Style::new().reversed()),
+        modes,
+        &mut list_state,
+    );
+
+    let label = |name: &str| Span::raw(format!("{name:<LABEL$}"));
+    let highlight = |span: Span<'static>, field: Field| {
+        if state.field == field {
+            span.reversed()
+        } else {
+            span
+        }
+    };
+    let mut lines = vec![
+        Line::from(vec![
+            label("Device"),
+            Span::raw(format!("{:?}", device.device_type)),
+        ]),
+        Line::from(vec![
+            label("Brightness"),
+            highlight(
+                Span::raw(format!("{:?}", device.brightness)),
+                Field::Brightness,
+            ),
+        ]),
+    ];
+    if let Some(Some(effect)) = app.aura_effects.get(state.device) {
+        let selected = |colour: fn(usize) -> Field| (0..3).find(|c| state.field == colour(*c));
+        let mut colour1 = vec![label(
+            "Colour 1",
+        )];
+        colour1.extend(colour_spans(effect.colour1, selected(Field::Colour1)));
+        let mut colour2 = vec![label(
+            "Colour 2",
+        )];
+        colour2.extend(colour_spans(effect.colour2, selected(Field::Colour2)));
+        lines.push(Line::from(colour1));
+        lines.push(Line::from(colour2));
+        lines.push(Line::from(vec![
+            label("Speed"),
+            highlight(Span::raw(format!("{:?}", effect.speed)), Field::Speed),
+        ]));
+    }
+    let title = format!(" Device {} of {} ", state.device + 1, app.status.aura.len());
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(title)),
+        settings,
+    );
+}
+
+pub fn key(app: &mut App, code: KeyCode) -> Result<Option<String>, Box<dyn Error>> {
+    let state = &mut app.aura;
+    let (Some(device), Some(aura)) = (
+        app.status.aura.get(state.device),
+        app.proxies.aura.get(state.device),
+    ) else {
+        return Ok(None);
+    };
+
+    let forward = match code {
+        KeyCode::Up => {
+            state.mode = state.mode.saturating_sub(1);
+            return Ok(None);
+        }
+        KeyCode::Down => {
+            state.mode = (state.mode + 1).min(device.supported_modes.len().saturating_sub(1));
+            return Ok(None);
+        }
+        KeyCode::Char('c') => {
+            let i = Field::ALL
+                .iter()
+                .position(|f| *f == state.field)
+                .unwrap_or(0);
+            state.field = Field::ALL[(i + 1) % Field::ALL.len()];
+            return Ok(None);
+        }
+        KeyCode::Char('n') => {
+            state.device = (state.device + 1) % app.status.aura.len();
+            state.mode = 0;
+            return Ok(None);
+        }
+        KeyCode::Enter => {
+            let Some(mode) = device.supported_modes.get(state.mode).copied() else {
+                return Ok(None);
+            };
+            aura.set_led_mode(mode)?;
+            return Ok(Some(format!("Mode set to {mode}")));
+        }
+        KeyCode::Right => true,
+        KeyCode::Left => false,
+        _ => return Ok(None),
+    };
+
+    if state.field == Field::Brightness {
+        let brightness = if forward {
+            device.brightness.next()
+        } else {
+            device.brightness.prev()
+        };
+        aura.set_brightness(brightness)?;
+        return Ok(Some(format!("Brightness set to {brightness:?}")));
+    }
+
+    let Some(Some(mut effect)) = app.aura_effects.get(state.device).cloned() else {
+        return Ok(None);
+    };
+    match state.field {
+        Field::Colour1(c) | Field::Colour2(c) => {
+            let colour = if matches!(state.field, Field::Colour1(_)) {
+                &mut effect.colour1
+            } else {
+                &mut effect.colour2
+            };
+            let value = channel(colour, c);
+            *value = if forward {
+                value.saturating_add(COLOUR_STEP)
+            } else {
+                value.saturating_sub(COLOUR_STEP)
+            };
+        }
+        Field::Speed => {
+            effect.speed = cycle(
+                &[
+                    Speed::Low,
+                    Speed::Med,
+                    Speed::High,
+                ],
+                effect.speed,
+                forward,
+            )
+            .unwrap_or_default();
+        }
+        Field::Brightness => {}
+    }
+    let mode = effect.mode;
+    aura.set_led_mode_data(effect)?;
+    Ok(Some(format!("{mode} effect updated")))
+}
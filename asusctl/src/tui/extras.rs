@@ -0,0 +1,189 @@
+//! AniMe Matrix and Slash lightbar toggles
+
+use std::error::Error;
+use std::str::FromStr;
+
+use ratatui::crossterm::event::KeyCode;
+use ratatui::layout::Rect;
+use ratatui::style::{Style, Stylize};
+use ratatui::widgets::{Block, List, ListState, Paragraph};
+use ratatui::Frame;
+use rog_anime::usb::Brightness;
+use rog_slash::SlashMode;
+
+use super::{cycle, App, LABEL};
+use crate::status::{on_off, Status};
+
+/// Change of the Slash brightness for one key press
+const SLASH_BRIGHTNESS_STEP: u8 = 16;
+/// Highest Slash animation interval
+const SLASH_MAX_INTERVAL: u8 = 5;
+
+#[derive(Debug, Default)]
+pub struct State {
+    selected: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Row {
+    AnimeDisplay(usize),
+    AnimeBrightness(usize),
+    AnimeBuiltins(usize),
+    SlashEnabled(usize),
+    SlashMode(usize),
+    SlashBrightness(usize),
+    SlashInterval(usize),
+}
+
+fn rows(status: &Status) -> Vec<Row> {
+    let mut rows = Vec::new();
+    for i in 0..status.anime.len() {
+        rows.extend([
+            Row::AnimeDisplay(i),
+            Row::AnimeBrightness(i),
+            Row::AnimeBuiltins(i),
+        ]);
+    }
+    for i in 0..status.slash.len() {
+        rows.extend([
+            Row::SlashEnabled(i),
+            Row::SlashMode(i),
+            Row::SlashBrightness(i),
+            Row::SlashInterval(i),
+        ]);
+    }
+    rows
+}
+
+impl Row {
+    fn describe(self, status: &Status) -> (&'static str, String) {
+        match self {
+            Self::AnimeDisplay(i) => (
+                "AniMe display",
+                on_off(status.anime[i].display_enabled).to_owned(),
+            ),
+            Self::AnimeBrightness(i) => (
+                "AniMe brightness",
+                format!("{:?}", status.anime[i].brightness),
+            ),
+            Self::AnimeBuiltins(i) => (
+                "AniMe builtin animations",
+                on_off(status.anime[i].builtins_enabled).to_owned(),
+            ),
+            Self::SlashEnabled(i) => ("Slash", on_off(status.slash[i].enabled).to_owned()),
+            Self::SlashMode(i) => ("Slash mode", status.slash[i].mode.to_string()),
+            Self::SlashBrightness(i) => {
+                ("Slash brightness", status.slash[i].brightness.to_string())
+            }
+            Self::SlashInterval(i) => ("Slash interval", status.slash[i].interval.to_string()),
+        }
+    }
+}
+
+pub fn draw(app: &App, frame: &mut Frame, area: Rect) {
+    let rows = rows(&app.status);
+    if rows.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No AniMe Matrix or Slash lightbar found")
+                .block(Block::bordered().title(" AniMe/Slash ")),
+            area,
+        );
+        return;
+    }
+    let items: Vec<String> = rows
+        .into_iter()
+        .map(|row| {
+            let (label, value) = row.describe(&app.status);
+            format!("{label:<LABEL$}{value}")
+        })
+        .collect();
+    let mut state = ListState::default().with_selected(Some(app.extras.selected));
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(Block::bordered().title(" AniMe/Slash "))
+            .highlight_style(Style::new().reversed()),
+        area,
+        &mut state,
+    );
+}
+
+pub fn key(app: &mut App, code: KeyCode) -> Result<Option<String>, Box<dyn Error>> {
+    let rows = rows(&app.status);
+    let forward = match code {
+        KeyCode::Up => {
+            app.extras.selected = app.extras.selected.saturating_sub(1);
+            return Ok(None);
+        }
+        KeyCode::Down => {
+            app.extras.selected = (app.extras.selected + 1).min(rows.len().saturating_sub(1));
+            return Ok(None);
+        }
+        KeyCode::Right | KeyCode::Enter => true,
+        KeyCode::Left => false,
+        _ => return Ok(None),
+    };
+    let Some(row) = rows.get(app.extras.selected).copied() else {
+        return Ok(None);
+    };
+
+    let status = &app.status;
+    let message = match row {
+        Row::AnimeDisplay(i) => {
+            let enabled = !status.anime[i].display_enabled;
+            app.proxies.anime[i].set_enable_display(enabled)?;
+            format!("AniMe display {}", on_off(enabled))
+        }
+        Row::AnimeBrightness(i) => {
+            let levels = [
+                Brightness::Off,
+                Brightness::Low,
+                Brightness::Med,
+                Brightness::High,
+            ];
+            let brightness =
+                cycle(&levels, status.anime[i].brightness, forward).unwrap_or_default();
+            app.proxies.anime[i].set_brightness(brightness)?;
+            format!("AniMe brightness set to {brightness:?}")
+        }
+        Row::AnimeBuiltins(i) => {
+            let enabled = !status.anime[i].builtins_enabled;
+            app.proxies.anime[i].set_builtins_enabled(enabled)?;
+            format!("AniMe builtin animations {}", on_off(enabled))
+        }
+        Row::SlashEnabled(i) => {
+            let enabled = !status.slash[i].enabled;
+            app.proxies.slash[i].set_enabled(enabled)?;
+            format!("Slash {}", on_off(enabled))
+        }
+        Row::SlashMode(i) => {
+            let modes: Vec<SlashMode> = SlashMode::list()
+                .iter()
+                .filter_map(|m| SlashMode::from_str(m).ok())
+                .collect();
+            let mode = cycle(&modes, status.slash[i].mode, forward).unwrap_or_default();
+            app.proxies.slash[i].set_mode(mode)?;
+            format!("Slash mode set to {mode}")
+        }
+        Row::SlashBrightness(i) => {
+            let brightness = status.slash[i].brightness;
+            let brightness = if forward {
+                brightness.saturating_add(SLASH_BRIGHTNESS_STEP)
+            } else {
+                brightness.saturating_sub(SLASH_BRIGHTNESS_STEP)
+            };
+            app.proxies.slash[i].set_brightness(brightness)?;
+            format!("Slash brightness set to {brightness}")
+        }
+        Row::SlashInterval(i) => {
+            let interval = status.slash[i].interval;
+            let interval = if forward {
+                (interval + 1).min(SLASH_MAX_INTERVAL)
+            } else {
+                interval.saturating_sub(1)
+            };
+            app.proxies.slash[i].set_interval(interval)?;
+            format!("Slash interval set to {interval}")
+        }
+    };
+    Ok(Some(message))
+}
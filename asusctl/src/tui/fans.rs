@@ -0,0 +1,227 @@
+//! Fan curve editor. Points are edited locally, keeping the curve rising,
+//! and the whole curve is written with `Enter`.
+
+use std::error::Error;
+
+use ratatui::crossterm::event::KeyCode;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::Stylize;
+use ratatui::symbols::Marker;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Axis, Block, Chart, Dataset, GraphType, Paragraph};
+use ratatui::Frame;
+use rog_platform::platform::PlatformProfile;
+use rog_profiles::fan_curve_set::CurveData;
+
+use super::App;
+
+/// Percent of fan speed changed by one key press
+const SPEED_STEP: u8 = 5;
+/// Highest temperature on the chart and that a point can be moved to
+const MAX_TEMP: u8 = 110;
+
+#[derive(Debug, Default)]
+pub struct State {
+    profile: usize,
+    fan: usize,
+    point: usize,
+    /// A curve with changes not yet written, for the selected profile and fan
+    edited: Option<CurveData>,
+}
+
+fn pwm_to_percent(pwm: u8) -> u8 {
+    (pwm as u32 * 100 / 255) as u8
+}
+
+fn percent_to_pwm(percent: u8) -> u8 {
+    ((percent.min(100) as u32 * 255 + 50) / 100) as u8
+}
+
+impl State {
+    /// The profile and curve selected, with any changes not yet written
+    fn curve(&self, app: &App) -> Option<(PlatformProfile, CurveData)> {
+        let profile = app.status.fan_curves.get(self.profile)?;
+        let curve = match &self.edited {
+            Some(edited) => edited.clone(),
+            None => profile.curves.get(self.fan)?.clone(),
+        };
+        Some((profile.profile, curve))
+    }
+}
+
+pub fn draw(app: &App, frame: &mut Frame, area: Rect) {
+    let state = &app.fans;
+    let Some((profile, curve)) = state.curve(app) else {
+        frame.render_widget(
+            Paragraph::new("Fan curves are not supported on this laptop")
+                .block(Block::bordered().title(" Fan curves ")),
+            area,
+        );
+        return;
+    };
+
+    let [summary, chart, points] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(10),
+        Constraint::Length(3),
+    ])
+    .areas(area);
+
+    let mut text = format!(
+        "Profile {profile:?}    Fan {:?}    {}",
+        curve.fan,
+        if curve.enabled { "enabled" } else { "disabled" }
+    );
+    if state.edited.is_some() {
+        text.push_str("    modified, Enter to write");
+    }
+    frame.render_widget(
+        Paragraph::new(text).block(Block::bordered().title(" Fan curves ")),
+        summary,
+    );
+
+    let line: Vec<(f64, f64)> = curve
+        .temp
+        .iter()
+        .zip(curve.pwm.iter())
+        .map(|(t, p)| (*t as f64, pwm_to_percent(*p) as f64))
+        .collect();
+    let selected = [line[state.point]];
+    let datasets = vec![
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .cyan()
+            .data(&line),
+        Dataset::default()
+            .marker(Marker::Block)
+            .graph_type(GraphType::Scatter)
+            .yellow()
+            .data(&selected),
+    ];
+    let max_temp = MAX_TEMP as f64;
+    frame.render_widget(
+        Chart::new(datasets)
+            .block(Block::bordered())
+            .x_axis(
+                Axis::default()
+                    .title("°C")
+                    .bounds([
+                        0.0, max_temp,
+                    ])
+                    .labels([
+                        "0", "55", "110",
+                    ]),
+            )
+            .y_axis(
+                Axis::default()
+                    .title("%")
+                    .bounds([
+                        0.0, 100.0,
+                    ])
+                    .labels([
+                        "0", "50", "100",
+                    ]),
+            ),
+        chart,
+    );
+
+    let spans: Vec<Span> = line
+        .iter()
+        .enumerate()
+        .map(|(i, (t, p))| {
+            let span = Span::raw(format!(" {t}°C {p}% "));
+            if i == state.point {
+                span.reversed()
+            } else {
+                span
+            }
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(Line::from(spans)).block(Block::bordered().title(" Points ")),
+        points,
+    );
+}
+
+pub fn key(app: &mut App, code: KeyCode) -> Result<Option<String>, Box<dyn Error>> {
+    let profiles = app.status.fan_curves.len();
+    let Some(fans) = app.proxies.fan_curves.as_ref() else {
+        return Ok(None);
+    };
+    let Some((profile, mut curve)) = app.fans.curve(app) else {
+        return Ok(None);
+    };
+    let state = &mut app.fans;
+    let point = state.point;
+
+    match code {
+        KeyCode::Char('p') => {
+            state.profile = (state.profile + 1) % profiles.max(1);
+            state.fan = 0;
+            state.edited = None;
+        }
+        KeyCode::Char('f') => {
+            let count = app.status.fan_curves[state.profile].curves.len();
+            state.fan = (state.fan + 1) % count.max(1);
+            state.edited = None;
+        }
+        KeyCode::Left => state.point = point.saturating_sub(1),
+        KeyCode::Right => state.point = (point + 1).min(curve.pwm.len() - 1),
+        KeyCode::Up | KeyCode::Down => {
+            // Between the neighbouring points so the curve keeps rising
+            let low = if point > 0 { curve.pwm[point - 1] } else { 0 };
+            let high = curve.pwm.get(point + 1).copied().unwrap_or(255);
+            let percent = pwm_to_percent(curve.pwm[point]);
+            let percent = if code == KeyCode::Up {
+                percent.saturating_add(SPEED_STEP)
+            } else {
+                percent.saturating_sub(SPEED_STEP)
+            };
+            curve.pwm[point] = percent_to_pwm(percent).clamp(low, high.max(low));
+            state.edited = Some(curve);
+        }
+        KeyCode::Char('+') | KeyCode::Char('-') => {
+            let low = if point > 0 { curve.temp[point - 1] } else { 0 };
+            let high = curve.temp.get(point + 1).copied().unwrap_or(MAX_TEMP);
+            let temp = if code == KeyCode::Char('+') {
+                curve.temp[point].saturating_add(1)
+            } else {
+                curve.temp[point].saturating_sub(1)
+            };
+            curve.temp[point] = temp.clamp(low, high.max(low));
+            state.edited = Some(curve);
+        }
+        KeyCode::Enter => {
+            if state.edited.is_none() {
+                return Ok(None);
+            }
+            fans.set_fan_curve(profile, curve.clone())?;
+            state.edited = None;
+            return Ok(Some(format!("Wrote {:?} curve for {profile:?}", curve.fan)));
+        }
+        KeyCode::Char('r') if state.edited.is_some() => {
+            state.edited = None;
+            return Ok(Some("Reverted changes".to_owned()));
+        }
+        KeyCode::Char('e') => {
+            let enabled = !curve.enabled;
+            fans.set_profile_fan_curve_enabled(profile, curve.fan, enabled)?;
+            if let Some(edited) = &mut state.edited {
+                edited.enabled = enabled;
+            }
+            return Ok(Some(format!(
+                "{:?} curve for {profile:?} {}",
+                curve.fan,
+                if enabled { "enabled" } else { "disabled" }
+            )));
+        }
+        KeyCode::Char('d') => {
+            state.edited = None;
+            fans.reset_profile_curves(profile)?;
+            return Ok(Some(format!("Reset curves for {profile:?} to defaults")));
+        }
+        _ => {}
+    }
+    Ok(None)
+}
@@ -0,0 +1,285 @@
+//! `asusctl tui`, an interactive front-end for machines without a desktop,
+//! such as over SSH.
+//!
+//! The state shown is a [`Status`], queried again whenever asusd emits
+//! `PropertiesChanged` so that changes made by other clients or the hotkeys
+//! show up as they happen. Each tab is a module with a `State`, a `draw` and
+//! a `key` handler. Changes are sent to asusd as they are made, apart from
+//! fan curves which are edited locally and written with `Enter`.
+
+mod aura;
+mod extras;
+mod fans;
+mod system;
+
+use std::error::Error;
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Style, Stylize};
+use ratatui::widgets::{Block, Paragraph, Tabs};
+use ratatui::{DefaultTerminal, Frame};
+use rog_aura::AuraEffect;
+use rog_dbus::asus_armoury::AsusArmouryProxyBlocking;
+use rog_dbus::zbus_anime::AnimeProxyBlocking;
+use rog_dbus::zbus_aura::AuraProxyBlocking;
+use rog_dbus::zbus_fan_curves::FanCurvesProxyBlocking;
+use rog_dbus::zbus_platform::PlatformProxyBlocking;
+use rog_dbus::zbus_slash::SlashProxyBlocking;
+use zbus::blocking::Connection;
+
+use crate::find_iface;
+use crate::status::{self, Status};
+
+/// How long to wait for a key before checking for changes from asusd
+const POLL: Duration = Duration::from_millis(100);
+
+/// Width of the labels in lists of settings
+const LABEL: usize = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    System,
+    Fans,
+    Aura,
+    Extras,
+}
+
+impl Tab {
+    const ALL: [Self; 4] = [
+        Self::System,
+        Self::Fans,
+        Self::Aura,
+        Self::Extras,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::System => "System",
+            Self::Fans => "Fan curves",
+            Self::Aura => "Aura",
+            Self::Extras => "AniMe/Slash",
+        }
+    }
+
+    /// The keys the tab uses, shown in the footer
+    fn keys(self) -> &'static str {
+        match self {
+            Self::System | Self::Extras => "↑↓ select  ←→ change  Enter toggle",
+            Self::Fans => {
+                "p profile  f fan  ←→ point  ↑↓ speed  +- temp  Enter write  r revert  e enable  d \
+                 defaults"
+            }
+            Self::Aura => "↑↓ mode  Enter apply  c field  ←→ change  n device",
+        }
+    }
+}
+
+/// Proxies for each interface asusd has, found once at startup
+pub struct Proxies<'a> {
+    platform: PlatformProxyBlocking<'a>,
+    fan_curves: Option<FanCurvesProxyBlocking<'a>>,
+    armoury: Vec<AsusArmouryProxyBlocking<'static>>,
+    aura: Vec<AuraProxyBlocking<'static>>,
+    anime: Vec<AnimeProxyBlocking<'static>>,
+    slash: Vec<SlashProxyBlocking<'static>>,
+}
+
+impl<'a> Proxies<'a> {
+    fn new(conn: &'a Connection, interfaces: &[String]) -> Result<Self, Box<dyn Error>> {
+        let has = |iface: &str| interfaces.iter().any(|i| i == iface);
+        Ok(Self {
+            platform: PlatformProxyBlocking::new(conn)?,
+            fan_curves: has("xyz.ljones.FanCurves")
+                .then(|| FanCurvesProxyBlocking::new(conn))
+                .transpose()?,
+            armoury: find_iface("xyz.ljones.AsusArmoury").unwrap_or_default(),
+            aura: find_iface("xyz.ljones.Aura").unwrap_or_default(),
+            anime: find_iface("xyz.ljones.Anime").unwrap_or_default(),
+            slash: find_iface("xyz.ljones.Slash").unwrap_or_default(),
+        })
+    }
+}
+
+pub struct App<'a> {
+    conn: &'a Connection,
+    interfaces: &'a [String],
+    proxies: Proxies<'a>,
+    status: Status,
+    /// The effect of the current mode of each aura device, which
+    /// [`Status`] does not include
+    aura_effects: Vec<Option<AuraEffect>>,
+    tab: Tab,
+    system: system::State,
+    fans: fans::State,
+    aura: aura::State,
+    extras: extras::State,
+    /// The outcome of the last change, shown in the footer
+    message: Option<String>,
+    quit: bool,
+}
+
+impl<'a> App<'a> {
+    fn new(conn: &'a Connection, interfaces: &'a [String]) -> Result<Self, Box<dyn Error>> {
+        let mut app = Self {
+            conn,
+            interfaces,
+            proxies: Proxies::new(conn, interfaces)?,
+            status: Status::default(),
+            aura_effects: Vec::new(),
+            tab: Tab::System,
+            system: Default::default(),
+            fans: Default::default(),
+            aura: Default::default(),
+            extras: Default::default(),
+            message: None,
+            quit: false,
+        };
+        app.refresh()?;
+        Ok(app)
+    }
+
+    fn refresh(&mut self) -> Result<(), Box<dyn Error>> {
+        self.status = Status::query(self.conn, self.interfaces)?;
+        self.aura_effects = self
+            .proxies
+            .aura
+            .iter()
+            .map(|aura| aura.led_mode_data().ok())
+            .collect();
+        Ok(())
+    }
+
+    fn key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => self.quit = true,
+            KeyCode::Tab => self.tab = Tab::ALL[(self.tab as usize + 1) % Tab::ALL.len()],
+            KeyCode::BackTab => {
+                self.tab = Tab::ALL[(self.tab as usize + Tab::ALL.len() - 1) % Tab::ALL.len()]
+            }
+            KeyCode::Char(c @ '1'..='4') => self.tab = Tab::ALL[c as usize - '1' as usize],
+            code => {
+                let result = match self.tab {
+                    Tab::System => system::key(self, code),
+                    Tab::Fans => fans::key(self, code),
+                    Tab::Aura => aura::key(self, code),
+                    Tab::Extras => extras::key(self, code),
+                };
+                // Not every change is a property, such as writing a fan curve, so
+                // query again rather than wait for a signal
+                match result.and_then(|changed| {
+                    if changed.is_some() {
+                        self.refresh()?;
+                    }
+                    Ok(changed)
+                }) {
+                    Ok(Some(message)) => self.message = Some(message),
+                    Ok(None) => {}
+                    Err(e) => self.message = Some(format!("Error: {e}")),
+                }
+            }
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [tabs, body, footer] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        frame.render_widget(
+            Tabs::new(
+                Tab::ALL
+                    .iter()
+                    .enumerate()
+                    .map(|(i, tab)| format!("{} {}", i + 1, tab.title())),
+            )
+            .select(self.tab as usize)
+            .highlight_style(Style::new().reversed())
+            .block(Block::bordered().title(" asusctl ")),
+            tabs,
+        );
+
+        match self.tab {
+            Tab::System => system::draw(self, frame, body),
+            Tab::Fans => fans::draw(self, frame, body),
+            Tab::Aura => aura::draw(self, frame, body),
+            Tab::Extras => extras::draw(self, frame, body),
+        }
+
+        let footer_text = match &self.message {
+            Some(message) => message.clone(),
+            None => format!("Tab switch  q quit  {}", self.tab.keys()),
+        };
+        frame.render_widget(Paragraph::new(footer_text).dim(), footer);
+    }
+}
+
+/// The value after, or before, `current` in `choices`, wrapping around at
+/// either end. The first choice if `current` is not one of them.
+fn cycle<T: PartialEq + Copy>(choices: &[T], current: T, forward: bool) -> Option<T> {
+    let len = choices.len();
+    let index = match choices.iter().position(|c| *c == current) {
+        Some(i) if forward => (i + 1) % len,
+        Some(i) => (i + len - 1) % len,
+        None => 0,
+    };
+    choices.get(index).copied()
+}
+
+/// Notify on each `PropertiesChanged` from asusd. The dbus iterator blocks,
+/// so it is read on its own thread.
+fn watch_changes(conn: &Connection) -> zbus::Result<Receiver<()>> {
+    let changes = status::property_changes(conn)?;
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for change in changes {
+            if change.is_err() || tx.send(()).is_err() {
+                break;
+            }
+        }
+    });
+    Ok(rx)
+}
+
+pub fn run(conn: &Connection, interfaces: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut app = App::new(conn, interfaces)?;
+    let changes = watch_changes(conn)?;
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app, &changes);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    changes: &Receiver<()>,
+) -> Result<(), Box<dyn Error>> {
+    while !app.quit {
+        terminal.draw(|frame| app.draw(frame))?;
+
+        if event::poll(POLL)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.message = None;
+                    app.key(key.code, key.modifiers);
+                }
+            }
+        }
+
+        // A single change can emit several signals, query once for all of them
+        if changes.try_iter().count() > 0 {
+            if let Err(e) = app.refresh() {
+                app.message = Some(format!("Error: {e}"));
+            }
+        }
+    }
+    Ok(())
+}
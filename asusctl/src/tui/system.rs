@@ -0,0 +1,215 @@
+//! Platform profiles, charging and the armoury firmware attributes
+
+use std::error::Error;
+
+use ratatui::crossterm::event::KeyCode;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::widgets::{Block, List, ListState, Paragraph};
+use ratatui::Frame;
+use rog_platform::platform::PlatformProfile;
+
+use super::{cycle, App, LABEL};
+use crate::status::{on_off, Status};
+
+/// Steps of the charge limit
+const CHARGE_STEP: u8 = 5;
+
+#[derive(Debug, Default)]
+pub struct State {
+    selected: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Row {
+    Profile,
+    ProfileOnAc,
+    ProfileOnBattery,
+    ChargeLimit,
+    EppLinked,
+    /// Index in to [`Status::armoury`]
+    Attribute(usize),
+}
+
+/// The settings the laptop has, in the order they are listed
+fn rows(status: &Status) -> Vec<Row> {
+    let p = &status.platform;
+    let mut rows = Vec::new();
+    if p.profile.is_some() {
+        rows.push(Row::Profile);
+    }
+    if p.profile_on_ac.is_some() {
+        rows.push(Row::ProfileOnAc);
+    }
+    if p.profile_on_battery.is_some() {
+        rows.push(Row::ProfileOnBattery);
+    }
+    if p.charge_limit.is_some() {
+        rows.push(Row::ChargeLimit);
+    }
+    if p.epp_linked.is_some() {
+        rows.push(Row::EppLinked);
+    }
+    rows.extend((0..status.armoury.len()).map(Row::Attribute));
+    rows
+}
+
+impl Row {
+    fn describe(self, status: &Status) -> (String, String) {
+        let p = &status.platform;
+        let profile = |p: Option<PlatformProfile>| p.map(|p| format!("{p:?}")).unwrap_or_default();
+        match self {
+            Self::Profile => ("Profile".to_owned(), profile(p.profile)),
+            Self::ProfileOnAc => ("Profile on AC".to_owned(), profile(p.profile_on_ac)),
+            Self::ProfileOnBattery => (
+                "Profile on battery".to_owned(),
+                profile(p.profile_on_battery),
+            ),
+            Self::ChargeLimit => (
+                "Charge limit".to_owned(),
+                p.charge_limit.map(|l| format!("{l}%")).unwrap_or_default(),
+            ),
+            Self::EppLinked => (
+                "EPP linked to profile".to_owned(),
+                on_off(p.epp_linked == Some(true)).to_owned(),
+            ),
+            Self::Attribute(i) => {
+                let attr = &status.armoury[i];
+                let mut value = attr
+                    .current
+                    .map(|c| c.to_string())
+                    .unwrap_or_else(|| "-".to_owned());
+                if let (Some(min), Some(max)) = (attr.min, attr.max) {
+                    value.push_str(&format!("  ({min}..{max})"));
+                } else if let Some(possible) = &attr.possible_values {
+                    value.push_str(&format!("  {possible:?}"));
+                }
+                (attr.name.clone(), value)
+            }
+        }
+    }
+}
+
+pub fn draw(app: &App, frame: &mut Frame, area: Rect) {
+    let [summary, settings] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+    ])
+    .areas(area);
+
+    let p = &app.status.platform;
+    let mut text = Vec::new();
+    if let Some(epp) = p.epp {
+        text.push(format!("EPP {}", String::from(epp)));
+    }
+    if let Some(plugged) = p.power_plugged {
+        text.push(if plugged { "On AC" } else { "On battery" }.to_owned());
+    }
+    frame.render_widget(
+        Paragraph::new(text.join("    ")).block(Block::bordered().title(" Platform ")),
+        summary,
+    );
+
+    let items: Vec<String> = rows(&app.status)
+        .into_iter()
+        .map(|row| {
+            let (label, value) = row.describe(&app.status);
+            format!("{label:<LABEL$}{value}")
+        })
+        .collect();
+    let mut state = ListState::default().with_selected(Some(app.system.selected));
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(Block::bordered().title(" Settings "))
+            .highlight_style(Style::new().reversed()),
+        settings,
+        &mut state,
+    );
+}
+
+pub fn key(app: &mut App, code: KeyCode) -> Result<Option<String>, Box<dyn Error>> {
+    let rows = rows(&app.status);
+    let forward = match code {
+        KeyCode::Up => {
+            app.system.selected = app.system.selected.saturating_sub(1);
+            return Ok(None);
+        }
+        KeyCode::Down => {
+            app.system.selected = (app.system.selected + 1).min(rows.len().saturating_sub(1));
+            return Ok(None);
+        }
+        KeyCode::Right | KeyCode::Enter => true,
+        KeyCode::Left => false,
+        _ => return Ok(None),
+    };
+    let Some(row) = rows.get(app.system.selected).copied() else {
+        return Ok(None);
+    };
+
+    let platform = &app.proxies.platform;
+    let p = &app.status.platform;
+    let choices = platform.platform_profile_choices().unwrap_or_default();
+    let next_profile =
+        |current: Option<PlatformProfile>| current.and_then(|c| cycle(&choices, c, forward));
+    let message = match row {
+        Row::Profile => {
+            let Some(profile) = next_profile(p.profile) else {
+                return Ok(None);
+            };
+            platform.set_platform_profile(profile)?;
+            format!("Profile set to {profile:?}")
+        }
+        Row::ProfileOnAc => {
+            let Some(profile) = next_profile(p.profile_on_ac) else {
+                return Ok(None);
+            };
+            platform.set_platform_profile_on_ac(profile)?;
+            format!("Profile on AC set to {profile:?}")
+        }
+        Row::ProfileOnBattery => {
+            let Some(profile) = next_profile(p.profile_on_battery) else {
+                return Ok(None);
+            };
+            platform.set_platform_profile_on_battery(profile)?;
+            format!("Profile on battery set to {profile:?}")
+        }
+        Row::ChargeLimit => {
+            let limit = p.charge_limit.unwrap_or(100);
+            let limit = if forward {
+                limit.saturating_add(CHARGE_STEP).min(100)
+            } else {
+                limit.saturating_sub(CHARGE_STEP)
+            };
+            platform.set_charge_control_end_threshold(limit)?;
+            format!("Charge limit set to {limit}%")
+        }
+        Row::EppLinked => {
+            let linked = p.epp_linked != Some(true);
+            platform.set_platform_profile_linked_epp(linked)?;
+            format!("EPP linked to profile {}", on_off(linked))
+        }
+        Row::Attribute(i) => {
+            let attr = &app.status.armoury[i];
+            let Some(current) = attr.current else {
+                return Ok(None);
+            };
+            let value = if let Some(possible) = &attr.possible_values {
+                cycle(possible, current, forward).unwrap_or(current)
+            } else {
+                let step = app.proxies.armoury[i]
+                    .scalar_increment()
+                    .unwrap_or(1)
+                    .max(1);
+                let value = if forward {
+                    current + step
+                } else {
+                    current - step
+                };
+                value.clamp(attr.min.unwrap_or(i32::MIN), attr.max.unwrap_or(i32::MAX))
+            };
+            app.proxies.armoury[i].set_current_value(value)?;
+            format!("{} set to {value}", attr.name)
+        }
+    };
+    Ok(Some(message))
+}
@@ -1,4 +1,5 @@
 use std::path::Path;
+use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread::sleep;
@@ -6,7 +7,7 @@ use std::time::{Duration, Instant};
 
 use config_traits::StdConfig;
 use rog_anime::error::AnimeError;
-use rog_anime::{ActionData, ActionLoader, AnimTime, Fade, Sequences, Vec2};
+use rog_anime::{ActionData, ActionLoader, AnimTime, Fade, GeneratorKind, Sequences, Vec2};
 use rog_dbus::zbus_anime::AnimeProxyBlocking;
 use ron::ser::PrettyConfig;
 use serde::{Deserialize, Serialize};
@@ -99,6 +100,28 @@ impl CtrlAnimeInner<'static> {
                             .map(|_| false)
                     });
                 }
+                ActionData::Video(video) => {
+                    rog_anime::run_video(video, &|output| {
+                        if self.do_early_return.load(Ordering::Acquire) {
+                            return Ok(true); // Do safe exit
+                        }
+                        self.client
+                            .write(output)
+                            .map_err(|e| AnimeError::Dbus(format!("{}", e)))
+                            .map(|_| false)
+                    });
+                }
+                ActionData::Generator(generator) => {
+                    rog_anime::run_generator(generator, &|output| {
+                        if self.do_early_return.load(Ordering::Acquire) {
+                            return Ok(true); // Do safe exit
+                        }
+                        self.client
+                            .write(output)
+                            .map_err(|e| AnimeError::Dbus(format!("{}", e)))
+                            .map(|_| false)
+                    });
+                }
                 ActionData::Image(image) => {
                     self.client.write(image.as_ref().clone()).ok();
                 }
@@ -296,6 +319,45 @@ impl CtrlAnime<'static> {
         Err(zbus::fdo::Error::Failed("UserConfig lock fail".into()))
     }
 
+    pub fn insert_generator(
+        &mut self,
+        index: u32,
+        kind: &str,
+        seed: u64,
+        time: Timer,
+        brightness: f32,
+    ) -> zbus::fdo::Result<String> {
+        if let Ok(mut config) = self.config.try_lock() {
+            let kind = GeneratorKind::from_str(kind)?;
+            let action = ActionLoader::Generator {
+                kind,
+                seed,
+                time: time.into(),
+                brightness,
+                frame_time: None,
+            };
+
+            // Must make the inner run loop return early
+            self.inner_early_return.store(true, Ordering::SeqCst);
+
+            if let Ok(mut controller) = self.inner.lock() {
+                controller
+                    .sequences
+                    .insert(index as usize, &action)
+                    .map_err(|err| zbus::fdo::Error::Failed(err.to_string()))?;
+            }
+            config.anime.push(action);
+            config.write();
+
+            let ron = ron::ser::to_string_pretty(&*config, PrettyConfig::new().depth_limit(4))
+                .expect("Parse config to RON failed");
+            // Release the inner run loop again
+            self.inner_early_return.store(false, Ordering::SeqCst);
+            return Ok(ron);
+        }
+        Err(zbus::fdo::Error::Failed("UserConfig lock fail".into()))
+    }
+
     pub fn insert_pause(&mut self, index: u32, millis: u64) -> zbus::fdo::Result<String> {
         if let Ok(mut config) = self.config.try_lock() {
             let action = ActionLoader::Pause(Duration::from_millis(millis));
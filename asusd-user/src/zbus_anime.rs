@@ -59,6 +59,17 @@ trait Daemon {
         brightness: f64,
     ) -> zbus::Result<String>;
 
+    /// InsertGenerator method
+    fn insert_generator(
+        &self,
+        index: u32,
+        kind: &str,
+        seed: u64,
+        time: u32,
+        count: u32,
+        brightness: f64,
+    ) -> zbus::Result<String>;
+
     /// InsertPause method
     fn insert_pause(&self, index: u32, millis: u64) -> zbus::Result<String>;
 
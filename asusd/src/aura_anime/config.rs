@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use config_traits::{StdConfig, StdConfigLoad};
+use config_traits::{ConfigVersion, StdConfig, StdConfigVersioned};
 use rog_anime::error::AnimeError;
 use rog_anime::usb::Brightness;
 use rog_anime::{
@@ -9,12 +9,14 @@ use rog_anime::{
 use serde::{Deserialize, Serialize};
 
 const CONFIG_FILE: &str = "anime.ron";
+const CONFIG_VERSION: u32 = 1;
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct AniMeConfigCached {
     pub system: Vec<ActionData>,
     pub boot: Vec<ActionData>,
     pub wake: Vec<ActionData>,
+    pub sleep: Vec<ActionData>,
     pub shutdown: Vec<ActionData>,
 }
 
@@ -42,6 +44,12 @@ impl AniMeConfigCached {
         }
         self.wake = wake;
 
+        let mut sleep = Vec::with_capacity(config.sleep.len());
+        for ani in &config.sleep {
+            sleep.push(ActionData::from_anime_action(anime_type, ani)?);
+        }
+        self.sleep = sleep;
+
         let mut shutdown = Vec::with_capacity(config.shutdown.len());
         for ani in &config.shutdown {
             shutdown.push(ActionData::from_anime_action(anime_type, ani)?);
@@ -54,12 +62,23 @@ impl AniMeConfigCached {
 /// Config for base system actions for the anime display
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct AniMeConfig {
+    #[serde(default)]
+    pub version: ConfigVersion<CONFIG_VERSION>,
     #[serde(skip)]
     pub anime_type: AnimeType,
     pub system: Vec<ActionLoader>,
     pub boot: Vec<ActionLoader>,
     pub wake: Vec<ActionLoader>,
+    /// Played when the system prepares to sleep, only if the builtin
+    /// animations are disabled
+    #[serde(default)]
+    pub sleep: Vec<ActionLoader>,
     pub shutdown: Vec<ActionLoader>,
+    /// The firmware has no storage for user frames, so the custom `sleep` and
+    /// `shutdown` sequences are played by asusd before the display is turned
+    /// off. This is the longest they are allowed to run for.
+    #[serde(default = "default_event_anim_budget")]
+    pub event_anim_budget: Duration,
     // pub brightness: f32,
     pub display_enabled: bool,
     pub display_brightness: Brightness,
@@ -69,16 +88,93 @@ pub struct AniMeConfig {
     pub off_when_lid_closed: bool,
     pub brightness_on_battery: Brightness,
     pub builtin_anims: Animations,
+    /// Software brightness applied to every frame asusd writes
+    #[serde(default)]
+    pub output: AniMeOutput,
+}
+
+/// Dim the display between two local hours of the day. The hours may wrap
+/// past midnight, e.g `start_hour: 22, end_hour: 7`.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct NightDim {
+    pub start_hour: u32,
+    pub end_hour: u32,
+    /// Brightness multiplier, `0.0`-`1.0`
+    pub brightness: f32,
+}
+
+impl NightDim {
+    pub fn contains(&self, hour: u32) -> bool {
+        if self.start_hour <= self.end_hour {
+            self.start_hour <= hour && hour < self.end_hour
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+}
+
+/// The software output stage for frames written by asusd. This works on top of
+/// the 4-step hardware `Brightness`.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct AniMeOutput {
+    /// Global brightness multiplier, `0.0`-`1.0`
+    pub brightness: f32,
+    /// Gamma correction of each LED, `1.0` is linear. Values above `1.0` make
+    /// the low end dimmer.
+    pub gamma: f32,
+    pub night_dim: Option<NightDim>,
+    /// Crossfade time between consecutive actions, zero is a hard cut
+    pub crossfade: Duration,
+}
+
+impl Default for AniMeOutput {
+    fn default() -> Self {
+        Self {
+            brightness: 1.0,
+            gamma: 1.0,
+            night_dim: None,
+            crossfade: Duration::ZERO,
+        }
+    }
+}
+
+impl AniMeOutput {
+    /// The total brightness multiplier to use at this `hour` of the day
+    pub fn scale(&self, hour: u32) -> f32 {
+        let mut scale = self.brightness;
+        if let Some(night) = self.night_dim {
+            if night.contains(hour) {
+                scale *= night.brightness;
+            }
+        }
+        scale.clamp(0.0, 1.0)
+    }
+
+    /// Apply gamma and `scale` to a single LED. The result is capped at 254 as
+    /// the device does not like 255.
+    pub fn apply(&self, led: u8, scale: f32) -> u8 {
+        let v = (led as f32 / 255.0).powf(self.gamma) * 255.0 * scale;
+        v.round().min(254.0) as u8
+    }
+}
+
+/// Kept short so the display is off before the system goes down
+fn default_event_anim_budget() -> Duration {
+    Duration::from_secs(5)
 }
 
 impl Default for AniMeConfig {
     fn default() -> Self {
         AniMeConfig {
+            version: ConfigVersion,
             anime_type: AnimeType::GA402,
             system: Vec::new(),
             boot: Vec::new(),
             wake: Vec::new(),
+            sleep: Vec::new(),
             shutdown: Vec::new(),
+            event_anim_budget: default_event_anim_budget(),
             // brightness: 1.0,
             display_enabled: true,
             display_brightness: Brightness::Med,
@@ -88,6 +184,7 @@ impl Default for AniMeConfig {
             off_when_lid_closed: true,
             brightness_on_battery: Brightness::Low,
             builtin_anims: Animations::default(),
+            output: AniMeOutput::default(),
         }
     }
 }
@@ -102,11 +199,13 @@ impl StdConfig for AniMeConfig {
     }
 
     fn config_dir() -> std::path::PathBuf {
-        std::path::PathBuf::from(crate::CONFIG_PATH_BASE)
+        crate::config_dir()
     }
 }
 
-impl StdConfigLoad for AniMeConfig {}
+impl StdConfigVersioned for AniMeConfig {
+    const VERSION: u32 = CONFIG_VERSION;
+}
 
 impl From<&AniMeConfig> for DeviceState {
     fn from(config: &AniMeConfig) -> Self {
@@ -124,6 +223,16 @@ impl From<&AniMeConfig> for DeviceState {
 }
 
 impl AniMeConfig {
+    /// The hardware brightness for the power source. Dimming on battery is
+    /// done by the device alone, the output stage does not scale for it again.
+    pub fn brightness(&self, power_plugged: bool) -> Brightness {
+        if power_plugged {
+            self.display_brightness
+        } else {
+            self.brightness_on_battery
+        }
+    }
+
     // fn clamp_config_brightness(mut config: &mut AnimeConfig) {
     //     if config.brightness < 0.0 || config.brightness > 1.0 {
     //         warn!(
@@ -180,3 +289,80 @@ impl AniMeConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rog_anime::usb::Brightness;
+
+    use super::{AniMeConfig, AniMeOutput, NightDim};
+
+    #[test]
+    fn output_default_is_passthrough() {
+        let output = AniMeOutput::default();
+        let scale = output.scale(23);
+        assert_eq!(scale, 1.0);
+        for led in 0..=254 {
+            assert_eq!(output.apply(led, scale), led);
+        }
+        assert_eq!(output.apply(255, scale), 254);
+    }
+
+    #[test]
+    fn output_scale() {
+        let output = AniMeOutput {
+            brightness: 0.8,
+            night_dim: Some(NightDim {
+                start_hour: 22,
+                end_hour: 7,
+                brightness: 0.5,
+            }),
+            ..Default::default()
+        };
+        assert_eq!(output.scale(12), 0.8);
+        assert_eq!(output.scale(23), 0.4);
+        assert_eq!(output.scale(3), 0.4);
+        assert_eq!(output.scale(7), 0.8);
+    }
+
+    /// What is seen for a frame LED, the hardware level as a fraction of
+    /// `High` times the LED value asusd writes
+    fn effective(config: &AniMeConfig, power_plugged: bool, led: u8) -> f32 {
+        let scale = config.output.scale(12);
+        config.brightness(power_plugged) as u8 as f32 / Brightness::High as u8 as f32
+            * config.output.apply(led, scale) as f32
+    }
+
+    #[test]
+    fn battery_dims_once() {
+        let mut config = AniMeConfig {
+            display_brightness: Brightness::Med,
+            brightness_on_battery: Brightness::Low,
+            output: AniMeOutput {
+                brightness: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(config.brightness(true), Brightness::Med);
+        assert_eq!(config.brightness(false), Brightness::Low);
+        // `Med` to `Low` halves it, and only the hardware level changes
+        assert_eq!(effective(&config, true, 200), 2.0 / 3.0 * 100.0);
+        assert_eq!(effective(&config, false, 200), 1.0 / 3.0 * 100.0);
+
+        // Off on battery leaves frames written on AC untouched
+        config.brightness_on_battery = Brightness::Off;
+        assert_eq!(effective(&config, false, 200), 0.0);
+        assert_eq!(effective(&config, true, 200), 2.0 / 3.0 * 100.0);
+    }
+
+    #[test]
+    fn output_gamma() {
+        let output = AniMeOutput {
+            gamma: 2.0,
+            ..Default::default()
+        };
+        assert_eq!(output.apply(0, 1.0), 0);
+        assert_eq!(output.apply(128, 1.0), 64);
+        assert_eq!(output.apply(255, 1.0), 254);
+    }
+}
@@ -6,7 +6,9 @@ use std::convert::TryFrom;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread::sleep;
+use std::time::Duration;
 
+use chrono::{Local, Timelike};
 use config_traits::StdConfig;
 use futures_util::lock::Mutex;
 use log::{debug, error, info, warn};
@@ -56,7 +58,7 @@ impl AniMe {
                 error!(
                     "Trying to cache the Anime Config failed, will reset to default config: {e:?}"
                 );
-                config.rename_file_old();
+                config.backup("invalid");
                 *config = AniMeConfig::new();
                 config.write();
             } else {
@@ -87,14 +89,12 @@ impl AniMe {
     }
 
     /// Write only a data packet. This will modify the leds brightness using the
-    /// global brightness set in config.
+    /// output stage set in config.
     async fn write_data_buffer(&self, mut buffer: AnimeDataBuffer) -> Result<(), RogError> {
+        let output = self.config.lock().await.output;
+        let scale = output.scale(Local::now().hour());
         for led in buffer.data_mut().iter_mut() {
-            let mut bright = *led as f32;
-            if bright > 254.0 {
-                bright = 254.0;
-            }
-            *led = bright as u8;
+            *led = output.apply(*led, scale);
         }
         let data = AnimePacketType::try_from(buffer)?;
         for row in &data {
@@ -116,6 +116,51 @@ impl AniMe {
             .await
     }
 
+    /// Step from `from` to `to` over `time`. Exits early if the action thread
+    /// is asked to exit.
+    async fn crossfade(&self, from: &AnimeDataBuffer, to: &AnimeDataBuffer, time: Duration) {
+        const STEP: Duration = Duration::from_millis(33);
+        let steps = (time.as_millis() / STEP.as_millis()) as u32;
+        for step in 1..steps {
+            if self.thread_exit.load(Ordering::SeqCst) {
+                return;
+            }
+            if let Ok(frame) = from
+                .blend(to, step as f32 / steps as f32)
+                .map_err(|e| error!("{}", e))
+            {
+                self.write_data_buffer(frame)
+                    .await
+                    .map_err(|err| {
+                        warn!("rog_anime::crossfade {}", err);
+                    })
+                    .ok();
+            }
+            tokio::time::sleep(STEP).await;
+        }
+    }
+
+    /// Run a one-shot action sequence for no longer than `budget`. This is how
+    /// custom sleep and shutdown animations are emulated, as the device can
+    /// only store a selection of the builtin animations.
+    ///
+    /// Returns once the action thread has exited and cleared the display.
+    async fn run_thread_for(&self, actions: Vec<ActionData>, budget: Duration) {
+        if tokio::time::timeout(budget, self.run_thread(actions, true))
+            .await
+            .is_err()
+        {
+            debug!("AniMe event animation ran over {budget:?}, stopping it");
+            self.thread_exit.store(true, Ordering::SeqCst);
+        }
+        // The thread may be mid-write, give it a moment to clear up
+        let mut count = 0;
+        while self.thread_running.load(Ordering::SeqCst) && count < 50 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            count += 1;
+        }
+    }
+
     /// Start an action thread. This is classed as a singleton and there should
     /// be only one running - so the thread uses atomics to signal run/exit.
     ///
@@ -136,7 +181,10 @@ impl AniMe {
 
         let thread_exit = self.thread_exit.clone();
         let thread_running = self.thread_running.clone();
-        let anime_type = self.config.lock().await.anime_type;
+        let (anime_type, crossfade) = {
+            let config = self.config.lock().await;
+            (config.anime_type, config.output.crossfade)
+        };
         let inner = self.clone();
 
         // Loop rules:
@@ -162,31 +210,76 @@ impl AniMe {
             info!("AniMe no previous system thread running (now)");
             thread_exit.store(false, Ordering::SeqCst);
             thread_running.store(true, Ordering::SeqCst);
+            // The last frame written, used as the start of a crossfade
+            let last_frame: std::sync::Mutex<Option<AnimeDataBuffer>> = Default::default();
             'main: loop {
                 for action in &actions {
                     if thread_exit.load(Ordering::SeqCst) {
                         break 'main;
                     }
+                    let first_frame = match action {
+                        ActionData::Animation(frames) => {
+                            frames.frames().first().map(|f| f.frame().clone())
+                        }
+                        ActionData::Image(image) => Some(image.as_ref().clone()),
+                        ActionData::Video(video) => video
+                            .frames()
+                            .ok()
+                            .and_then(|mut frames| frames.next())
+                            .and_then(|frame| frame.ok())
+                            .map(|frame| frame.frame().clone()),
+                        ActionData::Generator(generator) => generator
+                            .frames()
+                            .ok()
+                            .and_then(|mut frames| frames.next())
+                            .and_then(|frame| frame.ok())
+                            .map(|frame| frame.frame().clone()),
+                        _ => None,
+                    };
+                    let last = last_frame.lock().map(|l| l.clone()).unwrap_or_default();
+                    if let (Some(from), Some(to)) = (last, first_frame) {
+                        if !crossfade.is_zero() {
+                            inner.crossfade(&from, &to, crossfade).await;
+                        }
+                    }
+                    // TODO: sort all this out
+                    let write_frame = |frame: AnimeDataBuffer| {
+                        if thread_exit.load(Ordering::Acquire) {
+                            info!("rog-anime: animation sub-loop was asked to exit");
+                            return Ok(true); // Do safe exit
+                        }
+                        if let Ok(mut last) = last_frame.lock() {
+                            *last = Some(frame.clone());
+                        }
+                        let inner = inner.clone();
+                        tokio::task::spawn(async move {
+                            inner
+                                .write_data_buffer(frame)
+                                .await
+                                .map_err(|err| {
+                                    warn!("rog_anime::run_animation:callback {}", err);
+                                })
+                                .ok();
+                        });
+                        Ok(false) // Don't exit yet
+                    };
                     match action {
                         ActionData::Animation(frames) => {
-                            // TODO: sort all this out
-                            rog_anime::run_animation(frames, &|frame| {
-                                if thread_exit.load(Ordering::Acquire) {
-                                    info!("rog-anime: animation sub-loop was asked to exit");
-                                    return Ok(true); // Do safe exit
-                                }
-                                let inner = inner.clone();
-                                tokio::task::spawn(async move {
-                                    inner
-                                        .write_data_buffer(frame)
-                                        .await
-                                        .map_err(|err| {
-                                            warn!("rog_anime::run_animation:callback {}", err);
-                                        })
-                                        .ok();
-                                });
-                                Ok(false) // Don't exit yet
-                            });
+                            rog_anime::run_animation(frames, &write_frame);
+                            if thread_exit.load(Ordering::Acquire) {
+                                info!("rog-anime: sub-loop exited and main loop exiting now");
+                                break 'main;
+                            }
+                        }
+                        ActionData::Video(video) => {
+                            rog_anime::run_video(video, &write_frame);
+                            if thread_exit.load(Ordering::Acquire) {
+                                info!("rog-anime: sub-loop exited and main loop exiting now");
+                                break 'main;
+                            }
+                        }
+                        ActionData::Generator(generator) => {
+                            rog_anime::run_generator(generator, &write_frame);
                             if thread_exit.load(Ordering::Acquire) {
                                 info!("rog-anime: sub-loop exited and main loop exiting now");
                                 break 'main;
@@ -194,6 +287,9 @@ impl AniMe {
                         }
                         ActionData::Image(image) => {
                             once = false;
+                            if let Ok(mut last) = last_frame.lock() {
+                                *last = Some(image.as_ref().clone());
+                            }
                             inner
                                 .write_data_buffer(image.as_ref().clone())
                                 .await
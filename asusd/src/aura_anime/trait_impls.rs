@@ -321,6 +321,16 @@ impl crate::CtrlTask for AniMeZbus {
                     if config.display_enabled {
                         inner.thread_exit.store(true, Ordering::Release); // ensure clean slate
 
+                        if sleeping
+                            && !config.builtin_anims_enabled
+                            && !inner.cache.sleep.is_empty()
+                        {
+                            // Custom sleep animation, played out before the display is turned off
+                            inner
+                                .run_thread_for(inner.cache.sleep.clone(), config.event_anim_budget)
+                                .await;
+                        }
+
                         inner
                             .write_bytes(&pkt_set_enable_display(
                                 !(sleeping && config.off_when_suspended),
@@ -360,11 +370,14 @@ impl crate::CtrlTask for AniMeZbus {
                     let AniMeConfig {
                         display_enabled,
                         builtin_anims_enabled,
+                        event_anim_budget,
                         ..
                     } = *inner.config.lock().await;
                     if display_enabled && !builtin_anims_enabled {
                         if shutting_down {
-                            inner.run_thread(inner.cache.shutdown.clone(), true).await;
+                            inner
+                                .run_thread_for(inner.cache.shutdown.clone(), event_anim_budget)
+                                .await;
                         } else {
                             inner.run_thread(inner.cache.boot.clone(), true).await;
                         }
@@ -404,12 +417,14 @@ impl crate::CtrlTask for AniMeZbus {
                 let inner = inner4.clone();
                 // on power change
                 async move {
-                    let AniMeConfig {
-                        off_when_unplugged,
-                        builtin_anims_enabled,
-                        brightness_on_battery,
-                        ..
-                    } = *inner.config.lock().await;
+                    let (off_when_unplugged, builtin_anims_enabled, brightness) = {
+                        let config = inner.config.lock().await;
+                        (
+                            config.off_when_unplugged,
+                            config.builtin_anims_enabled,
+                            config.brightness(power_plugged),
+                        )
+                    };
                     if off_when_unplugged {
                         if builtin_anims_enabled {
                             inner
@@ -429,7 +444,7 @@ impl crate::CtrlTask for AniMeZbus {
                             .ok();
                     } else {
                         inner
-                            .write_bytes(&pkt_set_brightness(brightness_on_battery))
+                            .write_bytes(&pkt_set_brightness(brightness))
                             .await
                             .map_err(|err| {
                                 warn!("create_sys_event_tasks::off_when_unplugged {}", err);
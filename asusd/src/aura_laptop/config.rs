@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 
-use config_traits::{StdConfig, StdConfigLoad};
+use config_traits::{ConfigVersion, StdConfig, StdConfigVersioned};
 use log::{debug, info, warn};
 use rog_aura::aura_detection::LedSupportData;
 use rog_aura::keyboard::LaptopAuraPower;
@@ -11,9 +11,13 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::RogError;
 
+const CONFIG_VERSION: u32 = 1;
+
 #[derive(Deserialize, Serialize, Default, Debug, Clone)]
 // #[serde(default)]
 pub struct AuraConfig {
+    #[serde(default)]
+    pub version: ConfigVersion<CONFIG_VERSION>,
     #[serde(skip)]
     pub led_type: AuraDeviceType,
     #[serde(skip)]
@@ -46,11 +50,13 @@ impl StdConfig for AuraConfig {
     }
 
     fn config_dir() -> std::path::PathBuf {
-        std::path::PathBuf::from(crate::CONFIG_PATH_BASE)
+        crate::config_dir()
     }
 }
 
-impl StdConfigLoad for AuraConfig {}
+impl StdConfigVersioned for AuraConfig {
+    const VERSION: u32 = CONFIG_VERSION;
+}
 
 impl AuraConfig {
     /// Detect the keyboard type and load from default DB if data available
@@ -64,6 +70,7 @@ impl AuraConfig {
         let support_data = LedSupportData::get_data(prod_id);
         let enabled = LaptopAuraPower::new(device_type, &support_data);
         let mut config = AuraConfig {
+            version: ConfigVersion,
             led_type: device_type,
             support_data,
             config_name: format!("aura_{prod_id}.ron"),
@@ -5,8 +5,8 @@ use config_traits::StdConfig;
 use futures_util::lock::{Mutex, MutexGuard};
 use log::info;
 use rog_aura::keyboard::{AuraLaptopUsbPackets, LedUsbPackets};
-use rog_aura::usb::{AURA_LAPTOP_LED_APPLY, AURA_LAPTOP_LED_SET};
-use rog_aura::{AuraDeviceType, AuraEffect, LedBrightness, PowerZones, AURA_LAPTOP_LED_MSG_LEN};
+use rog_aura::usb::{effect_packets, power_packets, AURA_LAPTOP_LED_SET};
+use rog_aura::{AuraDeviceType, AuraEffect, LedBrightness};
 use rog_platform::hid_raw::HidRaw;
 use rog_platform::keyboard_led::KeyboardBacklight;
 
@@ -93,21 +93,18 @@ impl Aura {
         dev_type: AuraDeviceType,
         mode: &AuraEffect,
     ) -> Result<(), RogError> {
+        let packets = effect_packets(dev_type, mode);
         if matches!(dev_type, AuraDeviceType::LaptopKeyboardTuf) {
             if let Some(platform) = &self.backlight {
-                let buf = [
-                    1, mode.mode as u8, mode.colour1.r, mode.colour1.g, mode.colour1.b,
-                    mode.speed as u8,
-                ];
-                platform.lock().await.set_kbd_rgb_mode(&buf)?;
+                for buf in packets.iter() {
+                    platform.lock().await.set_kbd_rgb_mode(buf)?;
+                }
             }
         } else if let Some(hid_raw) = &self.hid {
-            let bytes: [u8; AURA_LAPTOP_LED_MSG_LEN] = mode.into();
             let hid_raw = hid_raw.lock().await;
-            hid_raw.write_bytes(&bytes)?;
-            hid_raw.write_bytes(&AURA_LAPTOP_LED_SET)?;
-            // Changes won't persist unless apply is set
-            hid_raw.write_bytes(&AURA_LAPTOP_LED_APPLY)?;
+            for bytes in packets.iter() {
+                hid_raw.write_bytes(bytes)?;
+            }
         } else {
             return Err(RogError::NoAuraKeyboard);
         }
@@ -128,35 +125,19 @@ impl Aura {
     /// Set combination state for boot animation/sleep animation/all leds/keys
     /// leds/side leds LED active
     pub async fn set_power_states(&self, config: &AuraConfig) -> Result<(), RogError> {
+        let packets = power_packets(config.led_type, &config.enabled);
         if matches!(config.led_type, rog_aura::AuraDeviceType::LaptopKeyboardTuf) {
             if let Some(backlight) = &self.backlight {
                 // TODO: tuf bool array
-                let buf = config.enabled.to_bytes(config.led_type);
-                backlight.lock().await.set_kbd_rgb_state(&buf)?;
+                for buf in packets.iter() {
+                    backlight.lock().await.set_kbd_rgb_state(buf)?;
+                }
             }
         } else if let Some(hid_raw) = &self.hid {
             let hid_raw = hid_raw.lock().await;
-            if let Some(p) = config.enabled.states.first() {
-                if p.zone == PowerZones::Ally {
-                    let msg = [
-                        0x5d,
-                        0xd1,
-                        0x09,
-                        0x01,
-                        p.new_to_byte() as u8,
-                        0x0,
-                        0x0,
-                    ];
-                    hid_raw.write_bytes(&msg)?;
-                    return Ok(());
-                }
+            for msg in packets.iter() {
+                hid_raw.write_bytes(msg)?;
             }
-
-            let bytes = config.enabled.to_bytes(config.led_type);
-            let msg = [
-                0x5d, 0xbd, 0x01, bytes[0], bytes[1], bytes[2], bytes[3],
-            ];
-            hid_raw.write_bytes(&msg)?;
         }
         Ok(())
     }
@@ -11,8 +11,10 @@ use futures_lite::future::block_on;
 use futures_util::lock::Mutex;
 use log::{debug, error, info, warn};
 use mio::{Events, Interest, Poll, Token};
+use rog_ene::{ene_devices, EneTransport};
 use rog_platform::error::PlatformError;
 use rog_platform::hid_raw::HidRaw;
+use rog_platform::sysfs;
 use udev::{Device, MonitorBuilder};
 use zbus::zvariant::{ObjectPath, OwnedObjectPath};
 use zbus::Connection;
@@ -78,6 +80,14 @@ fn dbus_path_for_scsi(prod_id: &str) -> OwnedObjectPath {
     ObjectPath::from_str_unchecked(&format!("{ASUS_ZBUS_PATH}/{MOD_NAME}/{prod_id}_scsi")).into()
 }
 
+fn dbus_path_for_ene(name: &str) -> OwnedObjectPath {
+    ObjectPath::from_str_unchecked(&format!(
+        "{ASUS_ZBUS_PATH}/{MOD_NAME}/ene_{}",
+        name.to_lowercase()
+    ))
+    .into()
+}
+
 fn dev_prop_matches(dev: &Device, prop: &str, value: &str) -> bool {
     if let Some(p) = dev.property_value(prop) {
         return p == value;
@@ -275,15 +285,49 @@ impl DeviceManager {
         Ok(devices)
     }
 
+    /// ENE controllers on an SMBus can't be enumerated, so each described in
+    /// `ene_devices.ron` is tried in turn
+    async fn init_all_ene_i2c(connection: &Connection) -> Vec<AsusDevice> {
+        let mut devices = Vec::new();
+        for data in ene_devices()
+            .iter()
+            .filter(|d| matches!(d.transport, EneTransport::I2c { .. }))
+        {
+            match DeviceHandle::maybe_ene_i2c(data).await {
+                Ok(dev_type) => {
+                    if let DeviceHandle::Scsi(ene) = dev_type.clone() {
+                        let path = dbus_path_for_ene(&data.name);
+                        let ctrl = ScsiZbus::new(ene);
+                        ctrl.start_tasks(connection, path.clone()).await.unwrap();
+                        devices.push(AsusDevice {
+                            device: dev_type,
+                            dbus_path: path,
+                        });
+                    }
+                }
+                Err(e) => debug!("ENE device {} not found: {e}", data.name),
+            }
+        }
+        devices
+    }
+
     pub async fn find_all_devices(connection: &Connection) -> Vec<AsusDevice> {
         let mut devices: Vec<AsusDevice> = Vec::new();
+        // USB, SCSI and SMBus devices can't be faked, so leave real hardware
+        // alone if running against a fake sysfs tree
+        let hardware = sysfs::is_default_root();
+        if !hardware {
+            info!("Fake sysfs root in use, skipping USB, SCSI and SMBus devices");
+        }
         // HID first, always
-        if let Ok(devs) = &mut Self::init_all_hid(connection).await {
-            devices.append(devs);
+        if hardware {
+            if let Ok(devs) = &mut Self::init_all_hid(connection).await {
+                devices.append(devs);
+            }
         }
         // USB after, need to check if HID picked something up and if so, skip it
-        let mut do_anime = true;
-        let mut do_slash = true;
+        let mut do_anime = hardware;
+        let mut do_slash = hardware;
         let mut do_kb_backlight = true;
         for dev in devices.iter() {
             if matches!(dev.device, DeviceHandle::Slash(_)) {
@@ -361,8 +405,11 @@ impl DeviceManager {
             }
         }
 
-        if let Ok(devs) = &mut Self::init_all_scsi(connection).await {
-            devices.append(devs);
+        if hardware {
+            if let Ok(devs) = &mut Self::init_all_scsi(connection).await {
+                devices.append(devs);
+            }
+            devices.append(&mut Self::init_all_ene_i2c(connection).await);
         }
 
         devices
@@ -379,6 +426,9 @@ impl DeviceManager {
 
         // TODO: The /sysfs/ LEDs don't cause events, so they need to be manually
         // checked for and added
+        if !sysfs::is_default_root() {
+            return Ok(manager);
+        }
 
         std::thread::spawn(move || {
             let mut monitor = MonitorBuilder::new()?.listen()?;
@@ -1,23 +1,49 @@
 use std::collections::BTreeMap;
 
-use config_traits::{StdConfig, StdConfigLoad};
+use config_traits::{ConfigVersion, StdConfig, StdConfigVersioned};
 use rog_aura::AuraDeviceType;
 use rog_scsi::{AuraEffect, AuraMode};
 use serde::{Deserialize, Serialize};
 
 const CONFIG_FILE: &str = "scsi.ron";
+const CONFIG_VERSION: u32 = 1;
+
+fn default_save_to_device() -> bool {
+    true
+}
+
+fn default_config_name() -> String {
+    CONFIG_FILE.to_owned()
+}
 
 /// Config for base system actions for the anime display
 #[derive(Deserialize, Serialize, Debug)]
 pub struct ScsiConfig {
+    #[serde(default)]
+    pub version: ConfigVersion<CONFIG_VERSION>,
     #[serde(skip)]
     pub dev_type: AuraDeviceType,
+    /// SCSI devices share `scsi.ron`, other ENE devices get a file each
+    #[serde(default = "default_config_name")]
+    pub config_name: String,
     pub enabled: bool,
     pub current_mode: AuraMode,
     pub modes: BTreeMap<AuraMode, AuraEffect>,
+    /// Store each effect on the device when set so that the device shows it
+    /// on power up without asusd
+    #[serde(default = "default_save_to_device")]
+    pub save_to_device: bool,
 }
 
 impl ScsiConfig {
+    /// A default config stored in `ene_<name>.ron`
+    pub fn for_ene_device(name: &str) -> Self {
+        Self {
+            config_name: format!("ene_{}.ron", name.to_lowercase()),
+            ..Default::default()
+        }
+    }
+
     pub fn get_effect(&mut self, mode: AuraMode) -> Option<&AuraEffect> {
         self.modes.get(&mode)
     }
@@ -31,9 +57,12 @@ impl ScsiConfig {
 impl Default for ScsiConfig {
     fn default() -> Self {
         ScsiConfig {
+            version: ConfigVersion,
             enabled: true,
             current_mode: AuraMode::Static,
             dev_type: AuraDeviceType::ScsiExtDisk,
+            config_name: default_config_name(),
+            save_to_device: true,
             modes: BTreeMap::from([
                 (AuraMode::Off, AuraEffect::default_with_mode(AuraMode::Off)),
                 (
@@ -103,12 +132,14 @@ impl StdConfig for ScsiConfig {
     }
 
     fn file_name(&self) -> String {
-        CONFIG_FILE.to_owned()
+        self.config_name.to_owned()
     }
 
     fn config_dir() -> std::path::PathBuf {
-        std::path::PathBuf::from(crate::CONFIG_PATH_BASE)
+        crate::config_dir()
     }
 }
 
-impl StdConfigLoad for ScsiConfig {}
+impl StdConfigVersioned for ScsiConfig {
+    const VERSION: u32 = CONFIG_VERSION;
+}
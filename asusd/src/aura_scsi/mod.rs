@@ -2,43 +2,98 @@ use std::sync::Arc;
 
 use config::ScsiConfig;
 use futures_util::lock::{Mutex, MutexGuard};
-use rog_scsi::{AuraEffect, Device, Task};
+use log::{debug, info};
+use rog_scsi::{
+    read_direct_colours, read_direct_mode, write_direct_colours, AuraEffect, Colour, EneController,
+};
 
 use crate::error::RogError;
 
 pub mod config;
 pub mod trait_impls;
 
+/// Any ENE controller, whatever the transport
+pub type EneDevice = Box<dyn EneController + Send>;
+
 #[derive(Clone)]
 pub struct ScsiAura {
-    device: Arc<Mutex<Device>>,
+    device: Arc<Mutex<EneDevice>>,
     config: Arc<Mutex<ScsiConfig>>,
+    led_count: usize,
 }
 
 impl ScsiAura {
-    pub fn new(device: Arc<Mutex<Device>>, config: Arc<Mutex<ScsiConfig>>) -> Self {
-        Self { device, config }
+    pub fn new(
+        device: Arc<Mutex<EneDevice>>,
+        config: Arc<Mutex<ScsiConfig>>,
+        led_count: usize,
+    ) -> Self {
+        Self {
+            device,
+            config,
+            led_count,
+        }
     }
 
     pub async fn lock_config(&self) -> MutexGuard<ScsiConfig> {
         self.config.lock().await
     }
 
-    pub async fn write_effect(&self, effect: &AuraEffect) -> Result<(), RogError> {
-        let tasks: Vec<Task> = effect.into();
-        for task in &tasks {
-            self.device.lock().await.perform(task).ok();
+    /// Write the effect, and if `save` is true store it on the device
+    pub async fn write_effect(&self, effect: &AuraEffect, save: bool) -> Result<(), RogError> {
+        let mut device = self.device.lock().await;
+        effect.write_to(device.as_mut())?;
+        if save {
+            device.save()?;
         }
         Ok(())
     }
 
+    /// Set each LED to a colour. These are not saved unless `save()` is used.
+    pub async fn write_direct(&self, colours: &[Colour]) -> Result<(), RogError> {
+        let mut device = self.device.lock().await;
+        write_direct_colours(device.as_mut(), colours, self.led_count)?;
+        Ok(())
+    }
+
+    /// Store the current state on the device
+    pub async fn save(&self) -> Result<(), RogError> {
+        self.device.lock().await.save()?;
+        Ok(())
+    }
+
+    /// Read the effect the device is showing
+    pub async fn read_effect(&self) -> Result<AuraEffect, RogError> {
+        Ok(AuraEffect::read_from_device(
+            self.device.lock().await.as_mut(),
+        )?)
+    }
+
+    /// Read the direct colours, `None` if the device is showing an effect
+    pub async fn read_direct(&self) -> Result<Option<Vec<Colour>>, RogError> {
+        let mut device = self.device.lock().await;
+        if !read_direct_mode(device.as_mut())? {
+            return Ok(None);
+        }
+        Ok(Some(read_direct_colours(device.as_mut(), self.led_count)?))
+    }
+
     /// Initialise the device if required. Locks the internal config so be wary
     /// of deadlocks.
     pub async fn do_initialization(&self) -> Result<(), RogError> {
         let config = self.config.lock().await;
         let mode = config.current_mode;
         if let Some(effect) = config.modes.get(&mode) {
-            self.write_effect(effect).await?;
+            // Skip the write (and flash wear) if the drive already shows it
+            match self.read_effect().await {
+                Ok(current) if effect.shows_same(&current) => {
+                    info!("ENE device is already showing {mode}");
+                    return Ok(());
+                }
+                Ok(current) => debug!("ENE device is showing {current}"),
+                Err(e) => debug!("Could not read ENE device state: {e}"),
+            }
+            self.write_effect(effect, config.save_to_device).await?;
         }
         Ok(())
     }
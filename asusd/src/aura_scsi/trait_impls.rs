@@ -3,7 +3,7 @@ use std::collections::BTreeMap;
 use config_traits::StdConfig;
 use log::error;
 use rog_aura::AuraDeviceType;
-use rog_scsi::{AuraEffect, AuraMode};
+use rog_scsi::{AuraEffect, AuraMode, Colour};
 use zbus::fdo::Error as ZbErr;
 use zbus::zvariant::OwnedObjectPath;
 use zbus::{interface, Connection};
@@ -66,9 +66,10 @@ impl ScsiZbus {
     #[zbus(property)]
     async fn set_led_mode(&self, mode: AuraMode) -> Result<(), zbus::Error> {
         let mut config = self.0.lock_config().await;
+        let save = config.save_to_device;
         if let Some(effect) = config.get_effect(mode) {
             self.0
-                .write_effect(effect)
+                .write_effect(effect, save)
                 .await
                 .map_err(|e| zbus::Error::Failure(format!("{e:?}")))?;
         } else {
@@ -100,9 +101,9 @@ impl ScsiZbus {
     /// the effect is stored and config written to disk.
     #[zbus(property)]
     async fn set_led_mode_data(&mut self, effect: AuraEffect) -> Result<(), ZbErr> {
-        self.0.write_effect(&effect).await?;
-
         let mut config = self.0.config.lock().await;
+        self.0.write_effect(&effect, config.save_to_device).await?;
+
         config.save_effect(effect);
         config.write();
         Ok(())
@@ -113,4 +114,42 @@ impl ScsiZbus {
         let config = self.0.config.lock().await;
         config.modes.clone()
     }
+
+    /// Read the effect the device is currently showing. This may differ from
+    /// `LedModeData` if it was changed by other software.
+    async fn device_effect(&self) -> Result<AuraEffect, ZbErr> {
+        Ok(self.0.read_effect().await?)
+    }
+
+    /// Read the colour of each LED if the device is in direct colour mode,
+    /// empty if it is showing an effect
+    async fn device_direct_colours(&self) -> Result<Vec<Colour>, ZbErr> {
+        Ok(self.0.read_direct().await?.unwrap_or_default())
+    }
+
+    /// Set the colour of each LED directly, bypassing the effect engine. These
+    /// are not saved to the device unless `Save` is called. Setting a mode
+    /// returns to the effect engine.
+    async fn set_direct_colours(&self, colours: Vec<Colour>) -> Result<(), ZbErr> {
+        Ok(self.0.write_direct(&colours).await?)
+    }
+
+    /// Store the state the device is showing so that it is restored on power
+    /// up
+    async fn save(&self) -> Result<(), ZbErr> {
+        Ok(self.0.save().await?)
+    }
+
+    /// Store each effect on the device as it is set
+    #[zbus(property)]
+    async fn save_to_device(&self) -> bool {
+        self.0.lock_config().await.save_to_device
+    }
+
+    #[zbus(property)]
+    async fn set_save_to_device(&self, save: bool) {
+        let mut config = self.0.lock_config().await;
+        config.save_to_device = save;
+        config.write();
+    }
 }
@@ -1,12 +1,66 @@
-use config_traits::{StdConfig, StdConfigLoad};
+use config_traits::{ConfigVersion, StdConfig, StdConfigVersioned};
+use rog_platform::platform::PlatformProfile;
 use rog_slash::{DeviceState, SlashMode, SlashType};
 use serde::{Deserialize, Serialize};
 
 const CONFIG_FILE: &str = "slash.ron";
+const CONFIG_VERSION: u32 = 1;
+
+/// The system state as seen by the slash controller, updated from the power,
+/// lid, and platform watches
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SystemState {
+    pub power_plugged: bool,
+    /// Battery charge in percent
+    pub battery: u8,
+    pub profile: PlatformProfile,
+    pub lid_closed: bool,
+    pub fan_curve_active: bool,
+}
+
+/// A system state that a `SlashRule` can match
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlashState {
+    /// On external power
+    Charging,
+    /// On battery
+    Discharging,
+    /// On battery and the charge is below this percentage
+    BatteryBelow(u8),
+    /// The active platform profile
+    Profile(PlatformProfile),
+    LidClosed,
+    /// A custom fan curve is enabled for the active profile
+    FanCurveActive,
+}
+
+impl SlashState {
+    pub fn matches(&self, state: &SystemState) -> bool {
+        match *self {
+            SlashState::Charging => state.power_plugged,
+            SlashState::Discharging => !state.power_plugged,
+            SlashState::BatteryBelow(level) => !state.power_plugged && state.battery < level,
+            SlashState::Profile(profile) => state.profile == profile,
+            SlashState::LidClosed => state.lid_closed,
+            SlashState::FanCurveActive => state.fan_curve_active,
+        }
+    }
+}
+
+/// Show `mode` with `brightness` and `interval` while `state` matches
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlashRule {
+    pub state: SlashState,
+    pub mode: SlashMode,
+    pub brightness: u8,
+    pub interval: u8,
+}
 
 /// Config for base system actions for the anime display
 #[derive(Deserialize, Serialize, Debug)]
 pub struct SlashConfig {
+    #[serde(default)]
+    pub version: ConfigVersion<CONFIG_VERSION>,
     #[serde(skip)]
     pub slash_type: SlashType,
     pub enabled: bool,
@@ -19,11 +73,28 @@ pub struct SlashConfig {
     pub show_on_battery: bool,
     pub show_battery_warning: bool,
     pub show_on_lid_closed: bool,
+    /// Checked in order each time the system state changes, the first
+    /// matching rule sets the mode. If none match the above mode, brightness,
+    /// and interval are used.
+    #[serde(default)]
+    pub state_rules: Vec<SlashRule>,
+}
+
+impl SlashConfig {
+    /// The mode, brightness, and interval to show for this system state
+    pub fn mode_for_state(&self, state: &SystemState) -> (SlashMode, u8, u8) {
+        self.state_rules
+            .iter()
+            .find(|rule| rule.state.matches(state))
+            .map(|rule| (rule.mode, rule.brightness, rule.interval))
+            .unwrap_or((self.display_mode, self.brightness, self.display_interval))
+    }
 }
 
 impl Default for SlashConfig {
     fn default() -> Self {
         SlashConfig {
+            version: ConfigVersion,
             enabled: true,
             brightness: 255,
             display_interval: 0,
@@ -35,6 +106,7 @@ impl Default for SlashConfig {
             show_on_battery: true,
             show_battery_warning: true,
             show_on_lid_closed: true,
+            state_rules: Vec::new(),
         }
     }
 }
@@ -48,11 +120,13 @@ impl StdConfig for SlashConfig {
     }
 
     fn config_dir() -> std::path::PathBuf {
-        std::path::PathBuf::from(crate::CONFIG_PATH_BASE)
+        crate::config_dir()
     }
 }
 
-impl StdConfigLoad for SlashConfig {}
+impl StdConfigVersioned for SlashConfig {
+    const VERSION: u32 = CONFIG_VERSION;
+}
 
 impl From<&SlashConfig> for DeviceState {
     fn from(config: &SlashConfig) -> Self {
@@ -64,3 +138,51 @@ impl From<&SlashConfig> for DeviceState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rog_platform::platform::PlatformProfile;
+    use rog_slash::SlashMode;
+
+    use super::{SlashConfig, SlashRule, SlashState, SystemState};
+
+    #[test]
+    fn state_rules_first_match() {
+        let config = SlashConfig {
+            state_rules: vec![
+                SlashRule {
+                    state: SlashState::BatteryBelow(20),
+                    mode: SlashMode::Hazard,
+                    brightness: 255,
+                    interval: 0,
+                },
+                SlashRule {
+                    state: SlashState::Profile(PlatformProfile::Performance),
+                    mode: SlashMode::Ramp,
+                    brightness: 128,
+                    interval: 2,
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut state = SystemState {
+            power_plugged: true,
+            battery: 10,
+            ..Default::default()
+        };
+        let base = (
+            config.display_mode,
+            config.brightness,
+            config.display_interval,
+        );
+        assert_eq!(config.mode_for_state(&state), base);
+
+        state.power_plugged = false;
+        assert_eq!(config.mode_for_state(&state), (SlashMode::Hazard, 255, 0));
+
+        state.battery = 50;
+        state.profile = PlatformProfile::Performance;
+        assert_eq!(config.mode_for_state(&state), (SlashMode::Ramp, 128, 2));
+    }
+}
@@ -1,7 +1,8 @@
 use std::sync::Arc;
 
-use config::SlashConfig;
+use config::{SlashConfig, SystemState};
 use futures_util::lock::{Mutex, MutexGuard};
+use log::debug;
 use rog_platform::hid_raw::HidRaw;
 use rog_platform::usb_raw::USBRaw;
 use rog_slash::usb::{slash_pkt_enable, slash_pkt_init, slash_pkt_options, slash_pkt_set_mode};
@@ -16,6 +17,8 @@ pub struct Slash {
     hid: Option<Arc<Mutex<HidRaw>>>,
     usb: Option<Arc<Mutex<USBRaw>>>,
     config: Arc<Mutex<SlashConfig>>,
+    /// `None` until the first update, the device then shows the base config
+    state: Arc<Mutex<Option<SystemState>>>,
 }
 
 impl Slash {
@@ -24,7 +27,12 @@ impl Slash {
         usb: Option<Arc<Mutex<USBRaw>>>,
         config: Arc<Mutex<SlashConfig>>,
     ) -> Self {
-        Self { hid, usb, config }
+        Self {
+            hid,
+            usb,
+            config,
+            state: Arc::new(Mutex::new(None)),
+        }
     }
 
     pub async fn lock_config(&self) -> MutexGuard<SlashConfig> {
@@ -40,6 +48,42 @@ impl Slash {
         Ok(())
     }
 
+    /// Update the tracked system state and, if this changes which of the
+    /// `state_rules` applies, switch the lightbar to the new mode.
+    pub async fn update_state(
+        &self,
+        update: impl FnOnce(&mut SystemState),
+    ) -> Result<(), RogError> {
+        let mut lock = self.state.lock().await;
+        let old = *lock;
+        let state = lock.get_or_insert_with(SystemState::default);
+        update(state);
+        let config = self.config.lock().await;
+        if !config.enabled || config.state_rules.is_empty() {
+            return Ok(());
+        }
+        let next = config.mode_for_state(state);
+        let current = old.map_or(
+            (
+                config.display_mode,
+                config.brightness,
+                config.display_interval,
+            ),
+            |old| config.mode_for_state(&old),
+        );
+        if next == current {
+            return Ok(());
+        }
+        debug!("Slash state changed to {state:?}, setting {next:?}");
+        let (mode, brightness, interval) = next;
+        self.write_bytes(&slash_pkt_options(
+            config.slash_type, config.enabled, brightness, interval,
+        ))
+        .await?;
+        self.write_bytes(&slash_pkt_set_mode(config.slash_type, mode)[1])
+            .await
+    }
+
     /// Initialise the device if required. Locks the internal config so be wary
     /// of deadlocks.
     pub async fn do_initialization(&self) -> Result<(), RogError> {
@@ -1,17 +1,29 @@
+use std::time::Duration;
+
 use config_traits::StdConfig;
+use futures_util::StreamExt;
 use log::{debug, error, warn};
+use logind_zbus::manager::ManagerProxy;
+use rog_platform::platform::{PlatformProfile, RogPlatform};
+use rog_platform::power::AsusPower;
+use rog_profiles::fan_curves_active;
 use rog_slash::usb::{
     slash_pkt_battery_saver, slash_pkt_boot, slash_pkt_enable, slash_pkt_lid_closed,
     slash_pkt_low_battery, slash_pkt_options, slash_pkt_save, slash_pkt_set_mode,
     slash_pkt_shutdown, slash_pkt_sleep,
 };
 use rog_slash::{DeviceState, SlashMode};
+use zbus::object_server::SignalEmitter;
+use zbus::proxy::CacheProperties;
 use zbus::zvariant::OwnedObjectPath;
 use zbus::{interface, Connection};
 
 use super::Slash;
 use crate::error::RogError;
-use crate::Reloadable;
+use crate::{CtrlTask, Reloadable};
+
+/// How often battery level and fan curve state are checked for `state_rules`
+const STATE_POLL_INTERVAL: Duration = Duration::from_secs(30);
 
 #[derive(Clone)]
 pub struct SlashZbus(Slash);
@@ -30,13 +42,15 @@ impl SlashZbus {
         self.reload()
             .await
             .unwrap_or_else(|err| warn!("Controller error: {}", err));
+        let signal = SignalEmitter::new(connection, path.clone())?;
+        let task = self.clone();
         connection
             .object_server()
             .at(path.clone(), self)
             .await
             .map_err(|e| error!("Couldn't add server at path: {path}, {e:?}"))
             .ok();
-        Ok(())
+        task.create_tasks(signal).await
     }
 }
 
@@ -315,3 +329,109 @@ impl Reloadable for SlashZbus {
         Ok(())
     }
 }
+
+impl CtrlTask for SlashZbus {
+    fn zbus_path() -> &'static str {
+        "SLASH_ZBUS_PATH"
+    }
+
+    /// Track the system state for `SlashConfig::state_rules`
+    async fn create_tasks(&self, _: SignalEmitter<'static>) -> Result<(), RogError> {
+        let connection = Connection::system().await?;
+        let manager = ManagerProxy::builder(&connection)
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?;
+        let power = AsusPower::new().ok();
+        let platform = RogPlatform::new().ok();
+
+        let power_plugged = manager.on_external_power().await.unwrap_or_default();
+        let lid_closed = manager.lid_closed().await.unwrap_or_default();
+        let battery = power.as_ref().and_then(|p| p.get_capacity().ok());
+        let profile = platform
+            .as_ref()
+            .and_then(|p| p.get_platform_profile().ok());
+        self.0
+            .update_state(|state| {
+                state.power_plugged = power_plugged;
+                state.lid_closed = lid_closed;
+                state.battery = battery.unwrap_or(100);
+                state.profile = profile.map(PlatformProfile::from).unwrap_or_default();
+                state.fan_curve_active = fan_curves_active().unwrap_or_default();
+            })
+            .await
+            .unwrap_or_else(|e| warn!("Slash state_rules: {e}"));
+
+        let inner1 = self.0.clone();
+        let inner2 = self.0.clone();
+        self.create_sys_event_tasks(
+            |_| async {},
+            |_| async {},
+            move |lid_closed| {
+                let inner = inner1.clone();
+                async move {
+                    inner
+                        .update_state(|state| state.lid_closed = lid_closed)
+                        .await
+                        .unwrap_or_else(|e| warn!("Slash state_rules: {e}"));
+                }
+            },
+            move |power_plugged| {
+                let inner = inner2.clone();
+                async move {
+                    inner
+                        .update_state(|state| state.power_plugged = power_plugged)
+                        .await
+                        .unwrap_or_else(|e| warn!("Slash state_rules: {e}"));
+                }
+            },
+        )
+        .await;
+
+        if let Some(platform) = platform {
+            match platform.monitor_platform_profile() {
+                Ok(watch) => {
+                    let inner = self.0.clone();
+                    tokio::spawn(async move {
+                        let mut buffer = [0; 32];
+                        if let Ok(mut stream) = watch.into_event_stream(&mut buffer) {
+                            while (stream.next().await).is_some() {
+                                if let Ok(profile) = platform.get_platform_profile() {
+                                    inner
+                                        .update_state(|state| {
+                                            state.profile = profile.into();
+                                            state.fan_curve_active =
+                                                fan_curves_active().unwrap_or_default();
+                                        })
+                                        .await
+                                        .unwrap_or_else(|e| warn!("Slash state_rules: {e}"));
+                                }
+                            }
+                        }
+                    });
+                }
+                Err(e) => debug!("Slash: platform_profile watch failed: {e}"),
+            }
+        }
+
+        // Neither of these emit change events
+        let inner = self.0.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(STATE_POLL_INTERVAL).await;
+                let battery = power.as_ref().and_then(|p| p.get_capacity().ok());
+                inner
+                    .update_state(|state| {
+                        if let Some(battery) = battery {
+                            state.battery = battery;
+                        }
+                        state.fan_curve_active = fan_curves_active().unwrap_or_default();
+                    })
+                    .await
+                    .unwrap_or_else(|e| warn!("Slash state_rules: {e}"));
+            }
+        });
+
+        Ok(())
+    }
+}
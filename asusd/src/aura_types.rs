@@ -1,16 +1,18 @@
 use std::sync::Arc;
 
-use config_traits::{StdConfig, StdConfigLoad};
+use config_traits::{StdConfig, StdConfigVersioned};
 use futures_util::lock::Mutex;
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use rog_anime::error::AnimeError;
 use rog_anime::usb::get_anime_type;
 use rog_anime::AnimeType;
 use rog_aura::AuraDeviceType;
+use rog_ene::i2c::{find_adapter, I2cEne};
+use rog_ene::{EneController, EneDeviceData, EneTransport};
 use rog_platform::hid_raw::HidRaw;
 use rog_platform::keyboard_led::KeyboardBacklight;
 use rog_platform::usb_raw::USBRaw;
-use rog_scsi::{open_device, ScsiType};
+use rog_scsi::open_device;
 use rog_slash::error::SlashError;
 use rog_slash::SlashType;
 
@@ -19,7 +21,7 @@ use crate::aura_anime::AniMe;
 use crate::aura_laptop::config::AuraConfig;
 use crate::aura_laptop::Aura;
 use crate::aura_scsi::config::ScsiConfig;
-use crate::aura_scsi::ScsiAura;
+use crate::aura_scsi::{EneDevice, ScsiAura};
 use crate::aura_slash::config::SlashConfig;
 use crate::aura_slash::Slash;
 use crate::error::RogError;
@@ -157,21 +159,59 @@ impl DeviceHandle {
 
     pub async fn maybe_scsi(dev_node: &str, prod_id: &str) -> Result<Self, RogError> {
         debug!("Testing for SCSI");
-        let prod_id = ScsiType::from(prod_id);
-        if prod_id == ScsiType::Unsupported {
+        let Some(data) = EneDeviceData::find_scsi(prod_id) else {
             log::info!("Unknown or invalid SCSI: {prod_id:?}, skipping");
             return Err(RogError::NotFound("No SCSI device".to_string()));
-        }
-        info!("Found SCSI device {prod_id:?} on {dev_node}");
+        };
+        info!("Found SCSI device {} on {dev_node}", data.name);
 
         let mut config = ScsiConfig::new().load();
         config.dev_type = AuraDeviceType::ScsiExtDisk;
-        let dev = Arc::new(Mutex::new(open_device(dev_node)?));
-        let scsi = ScsiAura::new(dev, Arc::new(Mutex::new(config)));
+        let dev: EneDevice = Box::new(open_device(dev_node, data)?);
+        let scsi = ScsiAura::new(
+            Arc::new(Mutex::new(dev)),
+            Arc::new(Mutex::new(config)),
+            data.led_count as usize,
+        );
         scsi.do_initialization().await?;
         Ok(Self::Scsi(scsi))
     }
 
+    /// Try an ENE controller on an SMBus. Reading the controller name register
+    /// takes a word write to select it, so only addresses in
+    /// `ENE_SMBUS_ADDRESSES` are tried. Nothing else is written unless the name
+    /// matches `EneDeviceData::device_name`.
+    pub async fn maybe_ene_i2c(data: &EneDeviceData) -> Result<Self, RogError> {
+        let EneTransport::I2c { adapter, address } = &data.transport else {
+            return Err(RogError::NotFound("Not an i2c ENE device".to_string()));
+        };
+        let Some(expected) = &data.device_name else {
+            warn!("ENE device {} has no device_name, skipping", data.name);
+            return Err(RogError::NotFound("No i2c ENE device".to_string()));
+        };
+        debug!("Testing for i2c ENE {} at {address:#x}", data.name);
+        let path = find_adapter(adapter)
+            .ok_or_else(|| RogError::NotFound(format!("No i2c adapter matching {adapter}")))?;
+        let mut dev = I2cEne::open(&path, *address, data.version)?;
+        let name = dev.device_name()?;
+        if name != *expected {
+            debug!("ENE device name at {path} {address:#x} is {name:?}, expected {expected:?}");
+            return Err(RogError::NotFound("No i2c ENE device".to_string()));
+        }
+        info!("Found ENE device {} on {path} at {address:#x}", data.name);
+
+        let mut config = ScsiConfig::for_ene_device(&data.name).load();
+        config.dev_type = AuraDeviceType::Unknown;
+        let dev: EneDevice = Box::new(dev);
+        let ene = ScsiAura::new(
+            Arc::new(Mutex::new(dev)),
+            Arc::new(Mutex::new(config)),
+            data.led_count as usize,
+        );
+        ene.do_initialization().await?;
+        Ok(Self::Scsi(ene))
+    }
+
     pub async fn maybe_laptop_aura(
         device: Option<Arc<Mutex<HidRaw>>>,
         prod_id: &str,
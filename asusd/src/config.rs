@@ -1,12 +1,13 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use config_traits::{StdConfig, StdConfigLoad2};
+use config_traits::{ron, ConfigVersion, Migration, StdConfig, StdConfigVersioned};
 use rog_platform::asus_armoury::FirmwareAttribute;
 use rog_platform::cpu::CPUEPP;
 use rog_platform::platform::PlatformProfile;
 use serde::{Deserialize, Serialize};
 
 const CONFIG_FILE: &str = "asusd.ron";
+const CONFIG_VERSION: u32 = 1;
 
 #[derive(Default, Clone, Deserialize, Serialize, PartialEq)]
 pub struct Tuning {
@@ -17,6 +18,8 @@ type Tunings = HashMap<PlatformProfile, Tuning>;
 
 #[derive(Deserialize, Serialize, PartialEq)]
 pub struct Config {
+    #[serde(default)]
+    pub version: ConfigVersion<CONFIG_VERSION>,
     // The current charge limit applied
     pub charge_control_end_threshold: u8,
     /// Save charge limit for restoring
@@ -53,6 +56,8 @@ pub struct Config {
     pub screenpad_gamma: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub screenpad_sync_primary: Option<bool>,
+    #[serde(default)]
+    pub screenpad: ScreenpadConfig,
     /// Temporary state for AC/Batt
     #[serde(skip)]
     pub last_power_plugged: u8,
@@ -69,9 +74,48 @@ impl Config {
     }
 }
 
+/// Automatic control of the screenpad, applied on top of the gamma and sync
+/// settings
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct ScreenpadConfig {
+    /// Seconds without keyboard or touch input before the screenpad is
+    /// turned off, `0` to keep it on
+    pub idle_timeout: u32,
+    /// Follow the ambient light sensor with `ambient_curve` instead of the
+    /// primary display
+    pub ambient_brightness: bool,
+    /// Points of ambient lux to screenpad brightness percent, interpolated
+    /// between
+    pub ambient_curve: Vec<(u32, i32)>,
+    /// Screenpad brightness percent while a process of this name is running.
+    /// This matches any process, whether or not it has a fullscreen window.
+    pub app_brightness: BTreeMap<String, i32>,
+    /// Length of the fade when the screenpad is turned on or off
+    pub fade_ms: u32,
+}
+
+impl Default for ScreenpadConfig {
+    fn default() -> Self {
+        Self {
+            idle_timeout: 0,
+            ambient_brightness: false,
+            ambient_curve: vec![
+                (0, 15),
+                (50, 35),
+                (300, 65),
+                (1000, 100),
+            ],
+            app_brightness: BTreeMap::new(),
+            fade_ms: 300,
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
+            version: ConfigVersion,
             charge_control_end_threshold: 100,
             base_charge_control_end_threshold: 100,
             disable_nvidia_powerd_on_battery: true,
@@ -92,6 +136,7 @@ impl Default for Config {
             last_power_plugged: Default::default(),
             screenpad_gamma: Default::default(),
             screenpad_sync_primary: Default::default(),
+            screenpad: Default::default(),
         }
     }
 }
@@ -114,11 +159,32 @@ impl StdConfig for Config {
     }
 
     fn config_dir() -> std::path::PathBuf {
-        std::path::PathBuf::from(crate::CONFIG_PATH_BASE)
+        crate::config_dir()
+    }
+}
+
+impl StdConfigVersioned for Config {
+    const VERSION: u32 = CONFIG_VERSION;
+
+    fn migrations() -> Vec<Migration> {
+        vec![
+            Migration {
+                from: 0,
+                migrate: migrate_unversioned,
+            },
+        ]
     }
 }
 
-impl StdConfigLoad2<Config611, Config601> for Config {}
+/// Files from before the version field are in the current layout or that of
+/// 6.1.1 or 6.0.1
+fn migrate_unversioned(data: &str) -> Result<String, ron::Error> {
+    let config: Config = ron::from_str(data)
+        .or_else(|_| ron::from_str::<Config611>(data).map(Config::from))
+        .or_else(|_| ron::from_str::<Config601>(data).map(Config::from))
+        .map_err(|e| e.code)?;
+    ron::to_string(&config)
+}
 
 #[derive(Deserialize, Serialize)]
 pub struct Config611 {
@@ -147,6 +213,7 @@ pub struct Config611 {
 impl From<Config611> for Config {
     fn from(c: Config611) -> Self {
         Self {
+            version: ConfigVersion,
             // Restore the base charge limit
             charge_control_end_threshold: c.charge_control_end_threshold,
             base_charge_control_end_threshold: c.charge_control_end_threshold,
@@ -168,6 +235,7 @@ impl From<Config611> for Config {
             armoury_settings: HashMap::default(),
             screenpad_gamma: None,
             screenpad_sync_primary: Default::default(),
+            screenpad: Default::default(),
         }
     }
 }
@@ -214,6 +282,7 @@ pub struct Config601 {
 impl From<Config601> for Config {
     fn from(c: Config601) -> Self {
         Self {
+            version: ConfigVersion,
             // Restore the base charge limit
             charge_control_end_threshold: c.charge_control_end_threshold,
             base_charge_control_end_threshold: c.charge_control_end_threshold,
@@ -235,6 +304,7 @@ impl From<Config601> for Config {
             armoury_settings: HashMap::default(),
             screenpad_gamma: None,
             screenpad_sync_primary: Default::default(),
+            screenpad: Default::default(),
         }
     }
 }
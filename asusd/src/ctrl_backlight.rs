@@ -1,22 +1,52 @@
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use config_traits::StdConfig;
 use futures_util::lock::Mutex;
 use log::{info, warn};
+use rog_platform::ambient_light::AmbientLight;
 use rog_platform::backlight::{Backlight, BacklightType};
+use tokio::runtime::Handle;
 use zbus::fdo::Error as FdoErr;
 use zbus::object_server::SignalEmitter;
 use zbus::{interface, Connection};
 
 use crate::config::Config;
 use crate::error::RogError;
+use crate::screenpad::{self, ambient_level, running_app};
 use crate::ASUS_ZBUS_PATH;
 
+/// How often the idle timeout setting is checked while it is disabled
+const IDLE_RECHECK: Duration = Duration::from_secs(5);
+/// How often running apps and the ambient light are checked
+const AUTO_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Default)]
+struct ScreenpadState {
+    /// Turned off by the idle timeout rather than the user, so input turns
+    /// it back on
+    idle_off: bool,
+    /// Brightness is set by a running app or the ambient light, and is not
+    /// synced with the primary display
+    automatic: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct CtrlBacklight {
     backlights: Vec<Backlight>,
     config: Arc<Mutex<Config>>,
+    screenpad: Arc<Mutex<ScreenpadState>>,
+    /// Fades are run on the tokio runtime, dbus methods may be called from
+    /// the zbus executor thread where the tokio timers can't be used
+    runtime: Handle,
+}
+
+/// Scale a brightness percent to the raw screenpad value, the screenpad uses
+/// the configured gamma rather than linear scaling
+fn screenpad_raw(level: i32, max: i32, gamma: f32) -> i32 {
+    let normalized_level = level as f32 / 100.0;
+    let gamma_corrected = normalized_level.powf(gamma);
+    (gamma_corrected * max as f32) as i32
 }
 
 impl CtrlBacklight {
@@ -37,7 +67,12 @@ impl CtrlBacklight {
             return Err(RogError::MissingFunction("No backlights found".into()));
         }
 
-        Ok(Self { backlights, config })
+        Ok(Self {
+            backlights,
+            config,
+            screenpad: Default::default(),
+            runtime: Handle::current(),
+        })
     }
 
     fn get_backlight(&self, device_type: &BacklightType) -> Option<&Backlight> {
@@ -77,9 +112,7 @@ impl CtrlBacklight {
             let gamma = self.config.lock().await.screenpad_gamma.unwrap_or(1.0);
             let scaled = if *device_type == BacklightType::Screenpad {
                 // Apply non-linear scaling with the configurable gamma value only for Screenpad
-                let normalized_level = level as f32 / 100.0;
-                let gamma_corrected = normalized_level.powf(gamma);
-                (gamma_corrected * max as f32) as i32
+                screenpad_raw(level, max, gamma)
             } else {
                 // Linear scaling for other devices
                 level * max / 100
@@ -91,19 +124,21 @@ impl CtrlBacklight {
             })?;
 
             // If sync is enabled and we're setting primary brightness, set screenpad
-            // afterward
+            // afterward, unless it is following an app or the ambient light
+            let automatic = self.screenpad.lock().await.automatic;
             if sync && *device_type == BacklightType::Primary {
                 for other in self
                     .backlights
                     .iter()
                     .filter(|b| b.device_type() != device_type)
                 {
+                    if automatic && other.device_type() == &BacklightType::Screenpad {
+                        continue;
+                    }
                     if let Ok(other_max) = other.get_max_brightness() {
                         let other_scaled = if other.device_type() == &BacklightType::Screenpad {
                             // Apply gamma only to Screenpad
-                            let normalized_level = level as f32 / 100.0;
-                            let gamma_corrected = normalized_level.powf(gamma);
-                            (gamma_corrected * other_max as f32) as i32
+                            screenpad_raw(level, other_max, gamma)
                         } else {
                             // Linear scaling for other devices
                             level * other_max / 100
@@ -150,6 +185,167 @@ impl CtrlBacklight {
         }
     }
 
+    /// Set the screenpad alone to a brightness percent, without syncing the
+    /// primary display
+    async fn set_screenpad_level(&self, level: i32) -> Result<(), RogError> {
+        let screenpad = self
+            .get_backlight(&BacklightType::Screenpad)
+            .ok_or_else(|| RogError::MissingFunction("Screenpad backlight not found".into()))?;
+        let gamma = self.config.lock().await.screenpad_gamma.unwrap_or(1.0);
+        let max = screenpad.get_max_brightness()?;
+        screenpad.set_brightness(screenpad_raw(level, max, gamma))?;
+        Ok(())
+    }
+
+    /// Turn the screenpad on or off with a fade. The brightness it had is
+    /// kept while off, and faded back up to when turned on.
+    pub async fn fade_screenpad_power(&self, on: bool) -> Result<(), RogError> {
+        let screenpad = self
+            .get_backlight(&BacklightType::Screenpad)
+            .ok_or_else(|| RogError::MissingFunction("Screenpad backlight not found".into()))?;
+        let fade = Duration::from_millis(self.config.lock().await.screenpad.fade_ms as u64);
+        let is_on = screenpad.get_bl_power()? == 0;
+        if on == is_on {
+            return Ok(());
+        }
+
+        if on {
+            let mut level = screenpad.get_brightness()?;
+            if level == 0 {
+                level = screenpad.get_max_brightness()?;
+            }
+            screenpad.set_brightness(0)?;
+            screenpad.set_bl_power(0)?;
+            self.runtime
+                .spawn(screenpad::fade(screenpad.clone(), level, fade))
+                .await
+                .ok();
+        } else {
+            let level = screenpad.get_brightness()?;
+            self.runtime
+                .spawn(screenpad::fade(screenpad.clone(), 0, fade))
+                .await
+                .ok();
+            screenpad.set_bl_power(1)?;
+            screenpad.set_brightness(level)?;
+        }
+        Ok(())
+    }
+
+    fn screenpad_is_on(&self) -> bool {
+        self.get_backlight(&BacklightType::Screenpad)
+            .and_then(|b| b.get_bl_power().ok())
+            .is_some_and(|power| power == 0)
+    }
+
+    /// Turn the screenpad off after `idle_timeout` seconds without keyboard
+    /// or touch input, and back on at the next input
+    fn start_idle_watch(&self, signal_ctxt: SignalEmitter<'static>) {
+        let ctrl = self.clone();
+        let activity = screenpad::watch_input_activity();
+        tokio::spawn(async move {
+            let mut last_input = Instant::now();
+            // The timeout has passed and been acted on, wait for input
+            let mut idle_handled = false;
+            loop {
+                let timeout =
+                    Duration::from_secs(ctrl.config.lock().await.screenpad.idle_timeout as u64);
+                let wait = if timeout.is_zero() || idle_handled {
+                    IDLE_RECHECK
+                } else {
+                    timeout
+                        .saturating_sub(last_input.elapsed())
+                        .min(IDLE_RECHECK)
+                };
+
+                tokio::select! {
+                    _ = activity.notified() => {
+                        last_input = Instant::now();
+                        idle_handled = false;
+                        let idle_off = std::mem::take(&mut ctrl.screenpad.lock().await.idle_off);
+                        if idle_off {
+                            info!("Screenpad: input after idle, turning on");
+                            ctrl.fade_screenpad_power(true)
+                                .await
+                                .unwrap_or_else(|e| warn!("Screenpad: {e}"));
+                            ctrl.screenpad_power_changed(&signal_ctxt).await.ok();
+                        }
+                    }
+                    _ = tokio::time::sleep(wait) => {
+                        if timeout.is_zero() || idle_handled || last_input.elapsed() < timeout {
+                            continue;
+                        }
+                        idle_handled = true;
+                        if ctrl.screenpad_is_on() {
+                            info!("Screenpad: idle for {timeout:?}, turning off");
+                            ctrl.fade_screenpad_power(false)
+                                .await
+                                .unwrap_or_else(|e| warn!("Screenpad: {e}"));
+                            ctrl.screenpad.lock().await.idle_off = true;
+                            ctrl.screenpad_power_changed(&signal_ctxt).await.ok();
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Set the screenpad brightness from `app_brightness` while one of the
+    /// apps runs, otherwise from the ambient light if enabled. The brightness
+    /// from before is restored when neither applies.
+    fn start_auto_brightness(&self) {
+        let ctrl = self.clone();
+        let sensor = AmbientLight::new().ok();
+        tokio::spawn(async move {
+            let mut restore = None;
+            let mut last = None;
+            loop {
+                tokio::time::sleep(AUTO_INTERVAL).await;
+                let config = ctrl.config.lock().await.screenpad.clone();
+
+                let app = if config.app_brightness.is_empty() {
+                    None
+                } else {
+                    running_app(config.app_brightness.keys())
+                };
+                let level = if let Some(app) = &app {
+                    config.app_brightness.get(app).copied()
+                } else if config.ambient_brightness {
+                    sensor
+                        .as_ref()
+                        .and_then(|s| s.get_illuminance().ok())
+                        .and_then(|lux| ambient_level(&config.ambient_curve, lux))
+                } else {
+                    None
+                };
+                ctrl.screenpad.lock().await.automatic = level.is_some();
+
+                if let Some(level) = level {
+                    if restore.is_none() {
+                        restore = ctrl
+                            .get_brightness_percent(&BacklightType::Screenpad)
+                            .await
+                            .ok();
+                    }
+                    if last != Some(level) {
+                        if let Some(app) = &app {
+                            info!("Screenpad: {app} is running, brightness {level}");
+                        }
+                        ctrl.set_screenpad_level(level)
+                            .await
+                            .unwrap_or_else(|e| warn!("Screenpad: {e}"));
+                        last = Some(level);
+                    }
+                } else if let Some(level) = restore.take() {
+                    ctrl.set_screenpad_level(level)
+                        .await
+                        .unwrap_or_else(|e| warn!("Screenpad: {e}"));
+                    last = None;
+                }
+            }
+        });
+    }
+
     pub async fn start_watch_primary(&self) -> Result<(), RogError> {
         if self.get_backlight(&BacklightType::Screenpad).is_none() {
             return Ok(());
@@ -188,6 +384,10 @@ impl CtrlBacklight {
                             continue;
                         }
 
+                        if backlights.screenpad.lock().await.automatic {
+                            continue;
+                        }
+
                         let level = backlights
                             .get_brightness_percent(&BacklightType::Primary)
                             .await
@@ -278,6 +478,10 @@ impl CtrlBacklight {
         Ok(())
     }
 
+    /// Screenpad brightness percent. While a process named in the
+    /// `app_brightness` config is running the brightness is set from there.
+    /// Any running process of that name matches, not only a fullscreen app,
+    /// as asusd can't see windows.
     #[zbus(property)]
     async fn screenpad_brightness(&self) -> Result<i32, FdoErr> {
         self.get_brightness_percent(&BacklightType::Screenpad).await
@@ -326,12 +530,65 @@ impl CtrlBacklight {
                     warn!("Failed to set backlight power: {}", e);
                     FdoErr::Failed(format!("Failed to set backlight power: {}", e))
                 })?;
+            self.screenpad.lock().await.idle_off = false;
             self.screenpad_power_changed(&ctxt).await?;
             Ok(())
         } else {
             Err(FdoErr::NotSupported("Screenpad backlight not found".into()).into())
         }
     }
+
+    /// Turn the screenpad off if on, or on if off, fading over the configured
+    /// `fade_ms`
+    async fn toggle_screenpad(
+        &self,
+        #[zbus(signal_context)] ctxt: SignalEmitter<'_>,
+    ) -> Result<(), FdoErr> {
+        if self.get_backlight(&BacklightType::Screenpad).is_none() {
+            return Err(FdoErr::NotSupported("Screenpad backlight not found".into()));
+        }
+        self.screenpad.lock().await.idle_off = false;
+        self.fade_screenpad_power(!self.screenpad_is_on())
+            .await
+            .map_err(|e| {
+                warn!("Failed to toggle screenpad: {}", e);
+                FdoErr::Failed(format!("Failed to toggle screenpad: {}", e))
+            })?;
+        self.screenpad_power_changed(&ctxt).await.ok();
+        Ok(())
+    }
+
+    /// Seconds without keyboard or touch input before the screenpad turns
+    /// off, `0` to never turn off
+    #[zbus(property)]
+    async fn screenpad_idle_timeout(&self) -> u32 {
+        self.config.lock().await.screenpad.idle_timeout
+    }
+
+    #[zbus(property)]
+    async fn set_screenpad_idle_timeout(&self, seconds: u32) -> Result<(), zbus::Error> {
+        let mut config = self.config.lock().await;
+        config.screenpad.idle_timeout = seconds;
+        config.write();
+        Ok(())
+    }
+
+    /// Set the screenpad brightness from the ambient light sensor
+    #[zbus(property)]
+    async fn screenpad_ambient_brightness(&self) -> bool {
+        self.config.lock().await.screenpad.ambient_brightness
+    }
+
+    #[zbus(property)]
+    async fn set_screenpad_ambient_brightness(&self, enabled: bool) -> Result<(), zbus::Error> {
+        if enabled && AmbientLight::new().is_err() {
+            return Err(FdoErr::NotSupported("No ambient light sensor found".into()).into());
+        }
+        let mut config = self.config.lock().await;
+        config.screenpad.ambient_brightness = enabled;
+        config.write();
+        Ok(())
+    }
 }
 
 impl crate::ZbusRun for CtrlBacklight {
@@ -340,6 +597,21 @@ impl crate::ZbusRun for CtrlBacklight {
     }
 }
 
+impl crate::CtrlTask for CtrlBacklight {
+    fn zbus_path() -> &'static str {
+        ASUS_ZBUS_PATH
+    }
+
+    async fn create_tasks(&self, signal_ctxt: SignalEmitter<'static>) -> Result<(), RogError> {
+        self.start_watch_primary().await?;
+        if self.get_backlight(&BacklightType::Screenpad).is_some() {
+            self.start_idle_watch(signal_ctxt);
+            self.start_auto_brightness();
+        }
+        Ok(())
+    }
+}
+
 impl crate::Reloadable for CtrlBacklight {
     async fn reload(&mut self) -> Result<(), RogError> {
         info!("Reloading backlight settings");
@@ -1,7 +1,6 @@
-use std::path::PathBuf;
 use std::sync::Arc;
 
-use config_traits::{StdConfig, StdConfigLoad};
+use config_traits::{ConfigVersion, StdConfig, StdConfigVersioned};
 use futures_lite::StreamExt;
 use futures_util::lock::Mutex;
 use log::{debug, error, info, warn};
@@ -14,13 +13,16 @@ use zbus::object_server::SignalEmitter;
 use zbus::{interface, Connection};
 
 use crate::error::RogError;
-use crate::{CtrlTask, CONFIG_PATH_BASE};
+use crate::CtrlTask;
 
 pub const FAN_CURVE_ZBUS_NAME: &str = "FanCurves";
 pub const FAN_CURVE_ZBUS_PATH: &str = "/xyz/ljones";
+const CONFIG_VERSION: u32 = 1;
 
 #[derive(Deserialize, Serialize, Debug, Default)]
 pub struct FanCurveConfig {
+    #[serde(default)]
+    pub version: ConfigVersion<CONFIG_VERSION>,
     pub profiles: FanCurveProfiles,
     #[serde(skip)]
     pub current: PlatformProfile,
@@ -38,11 +40,13 @@ impl StdConfig for FanCurveConfig {
     }
 
     fn config_dir() -> std::path::PathBuf {
-        PathBuf::from(CONFIG_PATH_BASE)
+        crate::config_dir()
     }
 }
 
-impl StdConfigLoad for FanCurveConfig {}
+impl StdConfigVersioned for FanCurveConfig {
+    const VERSION: u32 = CONFIG_VERSION;
+}
 
 #[derive(Debug, Clone)]
 pub struct CtrlFanCurveZbus {
@@ -72,8 +76,8 @@ impl CtrlFanCurveZbus {
                     // can read the existing values from hardware. The ACPI method used
                     // for this is what limits us.
                     platform.set_platform_profile(this.into())?;
-                    let mut dev = find_fan_curve_node()?;
-                    fan_curves.set_active_curve_to_defaults(this, &mut dev)?;
+                    let dev = find_fan_curve_node()?;
+                    fan_curves.set_active_curve_to_defaults(this, &dev)?;
 
                     info!("{this:?}:");
                     for curve in fan_curves.get_fan_curves_for(this) {
@@ -96,6 +100,32 @@ impl CtrlFanCurveZbus {
 
         Err(ProfileError::NotSupported.into())
     }
+
+    /// If any fan of the profile uses a custom curve
+    async fn profile_curves_enabled(&self, profile: PlatformProfile) -> bool {
+        self.config
+            .lock()
+            .await
+            .profiles
+            .get_fan_curves_for(profile)
+            .iter()
+            .any(|curve| curve.enabled)
+    }
+
+    async fn notify_curves_toggled(
+        &self,
+        signal_ctxt: &SignalEmitter<'_>,
+        profile: PlatformProfile,
+        was_enabled: bool,
+    ) {
+        let enabled = self.profile_curves_enabled(profile).await;
+        if enabled != was_enabled {
+            Self::fan_curves_toggled(signal_ctxt, profile, enabled)
+                .await
+                .map_err(|e| warn!("FanCurvesToggled: {e}"))
+                .ok();
+        }
+    }
 }
 
 #[interface(name = "xyz.ljones.FanCurves")]
@@ -104,9 +134,11 @@ impl CtrlFanCurveZbus {
     /// fan curve if in the same profile mode
     async fn set_fan_curves_enabled(
         &mut self,
+        #[zbus(signal_context)] ctxt: SignalEmitter<'_>,
         profile: PlatformProfile,
         enabled: bool,
     ) -> zbus::fdo::Result<()> {
+        let was_enabled = self.profile_curves_enabled(profile).await;
         self.config
             .lock()
             .await
@@ -116,8 +148,10 @@ impl CtrlFanCurveZbus {
             .lock()
             .await
             .profiles
-            .write_profile_curve_to_platform(profile, &mut find_fan_curve_node()?)?;
+            .write_profile_curve_to_platform(profile, &find_fan_curve_node()?)?;
         self.config.lock().await.write();
+        self.notify_curves_toggled(&ctxt, profile, was_enabled)
+            .await;
         Ok(())
     }
 
@@ -125,10 +159,12 @@ impl CtrlFanCurveZbus {
     /// activate a fan curve if in the same profile mode
     async fn set_profile_fan_curve_enabled(
         &mut self,
+        #[zbus(signal_context)] ctxt: SignalEmitter<'_>,
         profile: PlatformProfile,
         fan: FanCurvePU,
         enabled: bool,
     ) -> zbus::fdo::Result<()> {
+        let was_enabled = self.profile_curves_enabled(profile).await;
         self.config
             .lock()
             .await
@@ -138,11 +174,22 @@ impl CtrlFanCurveZbus {
             .lock()
             .await
             .profiles
-            .write_profile_curve_to_platform(profile, &mut find_fan_curve_node()?)?;
+            .write_profile_curve_to_platform(profile, &find_fan_curve_node()?)?;
         self.config.lock().await.write();
+        self.notify_curves_toggled(&ctxt, profile, was_enabled)
+            .await;
         Ok(())
     }
 
+    /// Emitted when a profile goes from using no custom fan curves to using at
+    /// least one, or back
+    #[zbus(signal)]
+    async fn fan_curves_toggled(
+        signal_ctxt: &SignalEmitter<'_>,
+        profile: PlatformProfile,
+        enabled: bool,
+    ) -> zbus::Result<()>;
+
     /// Get the fan-curve data for the currently active ThrottlePolicy
     async fn fan_curve_data(
         &mut self,
@@ -176,7 +223,7 @@ impl CtrlFanCurveZbus {
                 .lock()
                 .await
                 .profiles
-                .write_profile_curve_to_platform(profile, &mut find_fan_curve_node()?)?;
+                .write_profile_curve_to_platform(profile, &find_fan_curve_node()?)?;
         }
         self.config.lock().await.write();
         Ok(())
@@ -194,7 +241,7 @@ impl CtrlFanCurveZbus {
             .lock()
             .await
             .profiles
-            .set_active_curve_to_defaults(profile, &mut find_fan_curve_node()?)?;
+            .set_active_curve_to_defaults(profile, &find_fan_curve_node()?)?;
         self.platform.set_platform_profile(active.as_str())?;
         self.config.lock().await.write();
         Ok(())
@@ -213,7 +260,7 @@ impl CtrlFanCurveZbus {
             .lock()
             .await
             .profiles
-            .set_active_curve_to_defaults(active.as_str().into(), &mut find_fan_curve_node()?)?;
+            .set_active_curve_to_defaults(active.as_str().into(), &find_fan_curve_node()?)?;
         self.platform.set_platform_profile(active.as_str())?;
 
         self.config.lock().await.write();
@@ -258,7 +305,7 @@ impl CtrlTask for CtrlFanCurveZbus {
                                 .profiles
                                 .write_profile_curve_to_platform(
                                     profile,
-                                    &mut find_fan_curve_node().unwrap(),
+                                    &find_fan_curve_node().unwrap(),
                                 )
                                 .map_err(|e| warn!("write_profile_curve_to_platform, {}", e))
                                 .ok();
@@ -278,10 +325,10 @@ impl crate::Reloadable for CtrlFanCurveZbus {
     async fn reload(&mut self) -> Result<(), RogError> {
         let active = self.platform.get_platform_profile()?.into();
         let mut config = self.config.lock().await;
-        if let Ok(mut device) = find_fan_curve_node() {
+        if let Ok(device) = find_fan_curve_node() {
             config
                 .profiles
-                .write_profile_curve_to_platform(active, &mut device)?;
+                .write_profile_curve_to_platform(active, &device)?;
         }
 
         Ok(())
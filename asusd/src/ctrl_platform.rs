@@ -1,6 +1,7 @@
 use std::path::Path;
 use std::process::Command;
 use std::sync::Arc;
+use std::time::Duration;
 
 use config_traits::StdConfig;
 use futures_util::lock::Mutex;
@@ -16,9 +17,11 @@ use zbus::{interface, Connection};
 use crate::asus_armoury::set_config_or_default;
 use crate::config::Config;
 use crate::error::RogError;
-use crate::{task_watch_item, CtrlTask, ReloadAndNotify};
+use crate::{task_watch_item, task_watch_item_notify, CtrlTask, ReloadAndNotify};
 
 const PLATFORM_ZBUS_PATH: &str = "/xyz/ljones";
+/// The battery capacity doesn't emit change events
+const CHARGE_POLL_INTERVAL: Duration = Duration::from_secs(30);
 
 macro_rules! platform_get_value {
     ($self:ident, $property:tt, $prop_name:literal) => {
@@ -46,6 +49,9 @@ pub struct CtrlPlatform {
     attributes: FirmwareAttributes,
     cpu_control: Option<CPUControl>,
     config: Arc<Mutex<Config>>,
+    /// The charge limit `ChargeLimitReached` was last emitted for, cleared
+    /// when unplugged or the battery drops below it
+    charge_limit_reached: Arc<Mutex<Option<u8>>>,
 }
 
 impl CtrlPlatform {
@@ -68,6 +74,7 @@ impl CtrlPlatform {
             cpu_control: CPUControl::new()
                 .map_err(|e| error!("Couldn't get CPU control sysfs: {e}"))
                 .ok(),
+            charge_limit_reached: Arc::new(Mutex::new(None)),
         };
         let mut inotify_self = ret_self.clone();
 
@@ -141,6 +148,37 @@ impl CtrlPlatform {
         }
     }
 
+    /// The charge limit if on AC and the battery has charged up to it
+    fn charge_limit_state(&self) -> Option<u8> {
+        if !self.power.has_capacity() || !self.power.has_charge_control_end_threshold() {
+            return None;
+        }
+        let online = self.power.get_online().ok()?;
+        let capacity = self.power.get_capacity().ok()?;
+        let limit = self.power.get_charge_control_end_threshold().ok()?;
+        (online > 0 && capacity >= limit).then_some(limit)
+    }
+
+    /// Emit `ChargeLimitReached` if the battery has charged up to the limit
+    /// since the last check
+    async fn check_charge_limit(&self, signal_ctxt: &SignalEmitter<'_>) {
+        let state = self.charge_limit_state();
+        let mut reached = self.charge_limit_reached.lock().await;
+        if *reached == state {
+            return;
+        }
+        *reached = state;
+        if let Some(limit) = state {
+            let base_limit = self.config.lock().await.base_charge_control_end_threshold;
+            let one_shot = limit == 100 && base_limit > 0 && base_limit != 100;
+            debug!("Battery charged to the limit of {limit}%, one-shot: {one_shot}");
+            Self::charge_limit_reached(signal_ctxt, limit, one_shot)
+                .await
+                .map_err(|e| warn!("ChargeLimitReached: {e}"))
+                .ok();
+        }
+    }
+
     async fn run_ac_or_bat_cmd(&self, power_plugged: bool) {
         let prog: Vec<String> = if power_plugged {
             // AC ONLINE
@@ -293,30 +331,49 @@ impl CtrlPlatform {
     }
 
     #[zbus(property)]
-    async fn set_charge_control_end_threshold(&mut self, limit: u8) -> Result<(), FdoErr> {
+    async fn set_charge_control_end_threshold(
+        &mut self,
+        #[zbus(signal_context)] ctxt: SignalEmitter<'_>,
+        limit: u8,
+    ) -> Result<(), FdoErr> {
         if !(20..=100).contains(&limit) {
             return Err(RogError::ChargeLimit(limit))?;
         }
         self.power.set_charge_control_end_threshold(limit)?;
-        self.config.lock().await.charge_control_end_threshold = limit;
-        self.config.lock().await.base_charge_control_end_threshold = limit;
-        self.config.lock().await.write();
+        let mut config = self.config.lock().await;
+        config.charge_control_end_threshold = limit;
+        config.base_charge_control_end_threshold = limit;
+        config.write();
+        drop(config);
+        self.check_charge_limit(&ctxt).await;
         Ok(())
     }
 
-    async fn one_shot_full_charge(&self) -> Result<(), FdoErr> {
-        let base_limit = std::mem::replace(
-            &mut self.config.lock().await.charge_control_end_threshold,
-            100,
-        );
+    async fn one_shot_full_charge(
+        &self,
+        #[zbus(signal_context)] ctxt: SignalEmitter<'_>,
+    ) -> Result<(), FdoErr> {
+        let mut config = self.config.lock().await;
+        let base_limit = std::mem::replace(&mut config.charge_control_end_threshold, 100);
         if base_limit != 100 {
             self.power.set_charge_control_end_threshold(100)?;
-            self.config.lock().await.base_charge_control_end_threshold = base_limit;
-            self.config.lock().await.write();
+            config.base_charge_control_end_threshold = base_limit;
+            config.write();
         }
+        drop(config);
+        self.check_charge_limit(&ctxt).await;
         Ok(())
     }
 
+    /// Emitted once each time the battery charges up to the charge limit while
+    /// on AC. `one_shot` is true if it was the 100% of a one-shot full charge.
+    #[zbus(signal)]
+    async fn charge_limit_reached(
+        signal_ctxt: &SignalEmitter<'_>,
+        limit: u8,
+        one_shot: bool,
+    ) -> zbus::Result<()>;
+
     /// Toggle to next platform_profile. Names provided by `Profiles`.
     /// If fan-curves are supported will also activate a fan curve for profile.
     async fn next_platform_profile(
@@ -340,6 +397,7 @@ impl CtrlPlatform {
                     FdoErr::Failed(format!("RogPlatform: platform_profile: {err}"))
                 })?;
             self.enable_ppt_group_changed(&ctxt).await?;
+            self.cpu_epp_changed(&ctxt).await.ok();
             Ok(self.platform_profile_changed(&ctxt).await?)
         } else {
             Err(FdoErr::NotSupported(
@@ -388,6 +446,7 @@ impl CtrlPlatform {
                     FdoErr::Failed(format!("RogPlatform: platform_profile: {err}"))
                 })?;
             self.enable_ppt_group_changed(&ctxt).await?;
+            self.cpu_epp_changed(&ctxt).await.ok();
             Ok(())
         } else {
             Err(FdoErr::NotSupported(
@@ -408,6 +467,28 @@ impl CtrlPlatform {
         Ok(())
     }
 
+    /// The energy_performance_preference currently set on the CPUs
+    #[zbus(property)]
+    fn cpu_epp(&self) -> Result<CPUEPP, FdoErr> {
+        let cpu = self.cpu_control.as_ref().ok_or_else(|| {
+            FdoErr::NotSupported("RogPlatform: energy_performance_preference not supported".into())
+        })?;
+        Ok(cpu.get_epp()?)
+    }
+
+    /// If the laptop is on AC power
+    #[zbus(property)]
+    fn power_plugged(&self) -> Result<bool, FdoErr> {
+        Ok(self.power.get_online()? > 0)
+    }
+
+    /// If an armoury attribute was changed that only applies after a reboot,
+    /// always false without the `asus-armoury` driver
+    #[zbus(property)]
+    fn pending_reboot(&self) -> bool {
+        self.attributes.pending_reboot().unwrap_or_default()
+    }
+
     #[zbus(property)]
     async fn platform_profile_on_battery(&self) -> Result<PlatformProfile, FdoErr> {
         Ok(self.config.lock().await.platform_profile_on_battery)
@@ -611,9 +692,11 @@ impl ReloadAndNotify for CtrlPlatform {
     async fn reload_and_notify(
         &mut self,
         signal_context: &SignalEmitter<'static>,
-        data: Self::Data,
+        mut data: Self::Data,
     ) -> Result<(), RogError> {
         let mut config = self.config.lock().await;
+        // Not saved in the file, so would otherwise make every write look external
+        data.base_charge_control_end_threshold = config.base_charge_control_end_threshold;
         if *config != data {
             info!("asusd.ron updated externally, reloading and updating internal copy");
 
@@ -650,8 +733,9 @@ impl ReloadAndNotify for CtrlPlatform {
             // reload_and_notify!(platform_profile, "platform_profile");
 
             *config = data;
-            config.base_charge_control_end_threshold =
-                base_charge_control_end_threshold.unwrap_or_default();
+            if let Some(limit) = base_charge_control_end_threshold {
+                config.base_charge_control_end_threshold = limit;
+            }
         }
         Ok(())
     }
@@ -685,6 +769,8 @@ impl crate::Reloadable for CtrlPlatform {
 
 impl CtrlPlatform {
     task_watch_item!(charge_control_end_threshold "charge_control_end_threshold" power);
+
+    task_watch_item_notify!(pending_reboot attributes);
 }
 
 impl CtrlTask for CtrlPlatform {
@@ -774,6 +860,12 @@ impl CtrlTask for CtrlPlatform {
                             .await;
                     }
                     platform3.run_ac_or_bat_cmd(power_plugged).await;
+                    platform3
+                        .power_plugged_changed(&signal_ctxt_copy)
+                        .await
+                        .ok();
+                    platform3.cpu_epp_changed(&signal_ctxt_copy).await.ok();
+                    platform3.check_charge_limit(&signal_ctxt_copy).await;
                     // In case one-shot charge was used, restore the old charge limit
                     if platform3.power.has_charge_control_end_threshold() && !power_plugged {
                         platform3.restore_charge_limit().await;
@@ -810,6 +902,19 @@ impl CtrlTask for CtrlPlatform {
         // TODO: find a better way to manage this
         self.watch_charge_control_end_threshold(signal_ctxt_copy.clone())
             .await?;
+        self.watch_pending_reboot(signal_ctxt_copy.clone()).await?;
+
+        // Only a change from here on is signalled, not a battery that is
+        // already at the limit when asusd starts
+        *self.charge_limit_reached.lock().await = self.charge_limit_state();
+        let ctrl = self.clone();
+        let signal_ctxt_charge = signal_ctxt_copy.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(CHARGE_POLL_INTERVAL).await;
+                ctrl.check_charge_limit(&signal_ctxt_charge).await;
+            }
+        });
 
         let watch_platform_profile = self.platform.monitor_platform_profile()?;
         let ctrl = self.clone();
@@ -837,6 +942,7 @@ impl CtrlTask for CtrlPlatform {
                         ctrl.check_and_set_epp(epp, change_epp);
                         ctrl.platform_profile_changed(&signal_ctxt_copy).await.ok();
                         ctrl.enable_ppt_group_changed(&signal_ctxt_copy).await.ok();
+                        ctrl.cpu_epp_changed(&signal_ctxt_copy).await.ok();
                         let power_plugged = ctrl
                             .power
                             .get_online()
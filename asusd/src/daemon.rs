@@ -9,8 +9,8 @@ use asusd::config::Config;
 use asusd::ctrl_backlight::CtrlBacklight;
 use asusd::ctrl_fancurves::CtrlFanCurveZbus;
 use asusd::ctrl_platform::CtrlPlatform;
-use asusd::{print_board_info, start_tasks, CtrlTask, ZbusRun, DBUS_NAME};
-use config_traits::{StdConfig, StdConfigLoad2};
+use asusd::{print_board_info, start_tasks, CtrlTask, DBUS_NAME};
+use config_traits::{StdConfig, StdConfigVersioned};
 use futures_util::lock::Mutex;
 use log::{error, info};
 use rog_platform::asus_armoury::FirmwareAttributes;
@@ -93,8 +93,8 @@ async fn start_daemon() -> Result<(), Box<dyn Error>> {
 
     match CtrlBacklight::new(config.clone()) {
         Ok(backlight) => {
-            backlight.start_watch_primary().await?;
-            backlight.add_to_server(&mut server).await;
+            let sig_ctx = CtrlBacklight::signal_context(&server)?;
+            start_tasks(backlight, &mut server, sig_ctx).await?;
         }
         Err(err) => {
             error!("Backlight: {}", err);
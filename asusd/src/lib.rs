@@ -15,8 +15,11 @@ pub mod aura_scsi;
 pub mod aura_slash;
 pub mod aura_types;
 pub mod error;
+/// Idle, ambient light and per app control of the screenpad
+pub mod screenpad;
 
 use std::future::Future;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use dmi_id::DMIID;
@@ -32,6 +35,9 @@ use zbus::Connection;
 use crate::error::RogError;
 
 const CONFIG_PATH_BASE: &str = "/etc/asusd/";
+/// Overrides [`CONFIG_PATH_BASE`], used with a fake sysfs tree to run asusd
+/// without touching the system config
+pub const CONFIG_DIR_ENV: &str = "ASUSD_CONFIG_DIR";
 pub const ASUS_ZBUS_PATH: &str = "/xyz/ljones";
 
 pub static DBUS_NAME: &str = "xyz.ljones.Asusd";
@@ -134,6 +140,14 @@ macro_rules! task_watch_item_notify {
 
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// The directory all asusd configs are stored in
+pub fn config_dir() -> PathBuf {
+    std::env::var_os(CONFIG_DIR_ENV)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(CONFIG_PATH_BASE))
+}
+
 pub fn print_board_info() {
     let dmi = DMIID::new().unwrap_or_default();
     info!("Product family: {}", dmi.product_family);
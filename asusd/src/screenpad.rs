@@ -0,0 +1,153 @@
+//! Helpers for the automatic screenpad controls in
+//! [`crate::ctrl_backlight::CtrlBacklight`]: idle detection, the ambient
+//! brightness curve, matching running apps, and fading.
+
+use std::collections::HashSet;
+use std::fs::{read_dir, read_to_string, File};
+use std::io::Read;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::{info, warn};
+use rog_platform::backlight::Backlight;
+use rog_platform::input::keyboard_and_touch_devices;
+use rog_platform::sysfs;
+use tokio::sync::Notify;
+
+/// Time between brightness steps of a fade
+pub const FADE_STEP: Duration = Duration::from_millis(20);
+
+/// Brightness percent for `lux` from a curve of `(lux, percent)` points,
+/// interpolated linearly between points and flat past either end
+pub fn ambient_level(curve: &[(u32, i32)], lux: f32) -> Option<i32> {
+    let mut curve = curve.to_vec();
+    curve.sort_by_key(|(lux, _)| *lux);
+    let (first_lux, first_level) = *curve.first()?;
+    if lux <= first_lux as f32 {
+        return Some(first_level.clamp(0, 100));
+    }
+    for pair in curve.windows(2) {
+        let ((lux0, level0), (lux1, level1)) = (pair[0], pair[1]);
+        if lux <= lux1 as f32 {
+            let t = (lux - lux0 as f32) / (lux1 - lux0).max(1) as f32;
+            let level = level0 as f32 + t * (level1 - level0) as f32;
+            return Some((level.round() as i32).clamp(0, 100));
+        }
+    }
+    curve.last().map(|(_, level)| (*level).clamp(0, 100))
+}
+
+/// The raw brightness values to step through to get from `from` to `to` in
+/// `duration`, ending on `to`
+pub fn fade_steps(from: i32, to: i32, duration: Duration) -> Vec<i32> {
+    let steps = (duration.as_millis() / FADE_STEP.as_millis()).max(1) as i32;
+    (1..=steps)
+        .map(|i| from + (to - from) * i / steps)
+        .collect()
+}
+
+/// Fade the raw brightness of `backlight` to `to`
+pub async fn fade(backlight: Backlight, to: i32, duration: Duration) {
+    let from = backlight.get_brightness().unwrap_or(to);
+    for level in fade_steps(from, to, duration) {
+        if let Err(e) = backlight.set_brightness(level) {
+            warn!("Screenpad: fade failed: {e}");
+            return;
+        }
+        tokio::time::sleep(FADE_STEP).await;
+    }
+}
+
+/// The first of `apps` that has a running process, matched against the
+/// process name or the file name of the executable. Whether a window is
+/// fullscreen is not visible to a system service, so the process is used.
+pub fn running_app<'a>(apps: impl IntoIterator<Item = &'a String>) -> Option<String> {
+    let mut names = HashSet::new();
+    for entry in read_dir("/proc").ok()?.flatten() {
+        let pid = entry.path();
+        if !entry
+            .file_name()
+            .to_string_lossy()
+            .chars()
+            .all(|c| c.is_ascii_digit())
+        {
+            continue;
+        }
+        if let Ok(comm) = read_to_string(pid.join("comm")) {
+            names.insert(comm.trim_end().to_owned());
+        }
+        if let Ok(cmdline) = read_to_string(pid.join("cmdline")) {
+            if let Some(exe) = cmdline.split('\0').next() {
+                if let Some(name) = Path::new(exe).file_name() {
+                    names.insert(name.to_string_lossy().to_string());
+                }
+            }
+        }
+    }
+    apps.into_iter().find(|app| names.contains(*app)).cloned()
+}
+
+/// Start watching keyboards and touch surfaces, notifying on any input. The
+/// event nodes block on read so each is read on its own thread. Devices
+/// plugged in later are not watched.
+pub fn watch_input_activity() -> Arc<Notify> {
+    let activity = Arc::new(Notify::new());
+    let devices = keyboard_and_touch_devices(sysfs::root()).unwrap_or_else(|e| {
+        warn!("Screenpad: could not list input devices: {e}");
+        Vec::new()
+    });
+    for device in devices {
+        let mut file = match File::open(&device.devnode) {
+            Ok(file) => file,
+            Err(e) => {
+                warn!("Screenpad: could not open {:?}: {e}", device.devnode);
+                continue;
+            }
+        };
+        info!("Screenpad: watching {} for input", device.name);
+        let activity = activity.clone();
+        std::thread::spawn(move || {
+            // Room for a number of `struct input_event`, only the wakeup matters
+            let mut buffer = [0u8; 24 * 32];
+            while let Ok(n) = file.read(&mut buffer) {
+                if n == 0 {
+                    break;
+                }
+                activity.notify_one();
+            }
+        });
+    }
+    activity
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{ambient_level, fade_steps};
+
+    #[test]
+    fn ambient_curve() {
+        let curve = [
+            (300, 60),
+            (0, 10),
+            (1000, 100),
+        ];
+        assert_eq!(ambient_level(&curve, 0.0), Some(10));
+        assert_eq!(ambient_level(&curve, 150.0), Some(35));
+        assert_eq!(ambient_level(&curve, 300.0), Some(60));
+        assert_eq!(ambient_level(&curve, 650.0), Some(80));
+        assert_eq!(ambient_level(&curve, 20000.0), Some(100));
+        assert_eq!(ambient_level(&[], 100.0), None);
+        assert_eq!(ambient_level(&[(10, 150)], 0.0), Some(100));
+    }
+
+    #[test]
+    fn fade() {
+        assert_eq!(fade_steps(100, 0, Duration::from_millis(100)), [
+            80, 60, 40, 20, 0
+        ]);
+        assert_eq!(fade_steps(0, 255, Duration::ZERO), [255]);
+    }
+}
@@ -0,0 +1,413 @@
+//! End to end scenarios driving asusd over a private bus with the `rog-dbus`
+//! proxies, see `harness/mod.rs`
+
+mod harness;
+
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use harness::{Harness, GA402};
+use rog_aura::LedBrightness;
+use rog_dbus::zbus_aura::AuraProxy;
+use rog_dbus::zbus_backlight::BacklightProxy;
+use rog_dbus::zbus_fan_curves::FanCurvesProxy;
+use rog_dbus::zbus_platform::PlatformProxy;
+use rog_platform::cpu::CPUEPP;
+use rog_platform::fixture::SysfsFixture;
+use rog_platform::platform::PlatformProfile;
+use rog_profiles::FanCurvePU;
+use zbus::proxy::PropertyStream;
+
+const PLATFORM_PROFILE: &str = "firmware/acpi/platform_profile";
+const CHARGE_LIMIT: &str = "class/power_supply/BAT0/charge_control_end_threshold";
+const CPU0_EPP: &str = "bus/cpu/devices/cpu0/cpufreq/energy_performance_preference";
+const FAN_CURVE: &str = "class/hwmon/hwmon7";
+
+fn ga402() -> SysfsFixture {
+    SysfsFixture::from_ron(GA402).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn set_platform_profile() {
+    let Some(asusd) = Harness::start("profile", &ga402()).await else {
+        return;
+    };
+    let connection = asusd.connection().await;
+    let platform = PlatformProxy::new(&connection).await.unwrap();
+
+    // On AC at startup, so the default `platform_profile_on_ac` is applied
+    asusd.expect_sysfs(PLATFORM_PROFILE, "performance").await;
+
+    platform
+        .set_platform_profile(PlatformProfile::Quiet)
+        .await
+        .unwrap();
+    asusd.expect_sysfs(PLATFORM_PROFILE, "quiet").await;
+    // `platform_profile_linked_epp` is on by default
+    asusd
+        .expect_sysfs(CPU0_EPP, String::from(CPUEPP::Power).as_str())
+        .await;
+    assert_eq!(
+        platform.platform_profile().await.unwrap(),
+        PlatformProfile::Quiet
+    );
+    assert_eq!(platform.cpu_epp().await.unwrap(), CPUEPP::Power);
+    assert!(platform.power_plugged().await.unwrap());
+
+    platform.set_charge_control_end_threshold(60).await.unwrap();
+    asusd.expect_sysfs(CHARGE_LIMIT, "60").await;
+    assert!(platform.set_charge_control_end_threshold(10).await.is_err());
+    assert_eq!(asusd.read_sysfs(CHARGE_LIMIT), "60");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn platform_profile_changed_signal() {
+    let Some(asusd) = Harness::start("signal", &ga402()).await else {
+        return;
+    };
+    asusd.expect_sysfs(PLATFORM_PROFILE, "performance").await;
+
+    let listener = asusd.connection().await;
+    let listener = PlatformProxy::new(&listener).await.unwrap();
+    let mut changes = listener.receive_platform_profile_changed().await;
+
+    let connection = asusd.connection().await;
+    let platform = PlatformProxy::new(&connection).await.unwrap();
+    platform
+        .set_platform_profile(PlatformProfile::Balanced)
+        .await
+        .unwrap();
+
+    wait_for_profile(&mut changes, PlatformProfile::Balanced).await;
+
+    // A change made outside of asusd, such as by the Fn+F5 key, is picked up by
+    // the sysfs watch
+    std::fs::write(asusd.sysfs(PLATFORM_PROFILE), "quiet\n").unwrap();
+    wait_for_profile(&mut changes, PlatformProfile::Quiet).await;
+}
+
+async fn wait_for_profile(
+    changes: &mut PropertyStream<'_, PlatformProfile>,
+    profile: PlatformProfile,
+) {
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(change) = changes.next().await {
+            if change.get().await.ok() == Some(profile) {
+                return;
+            }
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("no change to {profile:?} received"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reload_config() {
+    let Some(asusd) = Harness::start("reload", &ga402()).await else {
+        return;
+    };
+    asusd.expect_sysfs(CHARGE_LIMIT, "100").await;
+
+    let path = asusd.config("asusd.ron");
+    let config = std::fs::read_to_string(&path).unwrap();
+    assert!(config.contains("charge_control_end_threshold: 100"));
+    std::fs::write(
+        &path,
+        config.replace(
+            "charge_control_end_threshold: 100",
+            "charge_control_end_threshold: 70",
+        ),
+    )
+    .unwrap();
+    asusd.expect_sysfs(CHARGE_LIMIT, "70").await;
+
+    let connection = asusd.connection().await;
+    let platform = PlatformProxy::new(&connection).await.unwrap();
+    assert_eq!(platform.charge_control_end_threshold().await.unwrap(), 70);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn apply_state_file() {
+    let Some(asusd) = Harness::start("apply", &ga402()).await else {
+        return;
+    };
+    asusd.expect_sysfs(PLATFORM_PROFILE, "performance").await;
+    asusd.expect_sysfs(CHARGE_LIMIT, "100").await;
+
+    let state = asusd.config("state.ron");
+    std::fs::write(&state, "(platform: (profile: Quiet, charge_limit: 80))").unwrap();
+    let state = state.to_string_lossy();
+    let run = |args: &[&str]| {
+        let output = asusd.asusctl(args)?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "{stdout}");
+        // Compact the pretty printed JSON, no values have spaces
+        Some(stdout.split_whitespace().collect::<String>())
+    };
+
+    let Some(applied) = run(&[
+        "--json", "apply", &state,
+    ]) else {
+        return;
+    };
+    assert!(
+        applied.contains(r#""setting":"platform.profile""#),
+        "{applied}"
+    );
+    assert!(
+        applied.contains(r#""setting":"platform.charge_limit""#),
+        "{applied}"
+    );
+    assert!(applied.contains(r#""dry_run":false"#), "{applied}");
+    asusd.expect_sysfs(PLATFORM_PROFILE, "quiet").await;
+    asusd.expect_sysfs(CHARGE_LIMIT, "80").await;
+
+    // The laptop now matches, so there is nothing left to do
+    let again = run(&[
+        "--json", "apply", "--dry-run", &state,
+    ])
+    .unwrap();
+    assert_eq!(again, r#"{"apply":{"changes":[],"dry_run":true}}"#);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn status_without_screenpad() {
+    let Some(asusd) = Harness::start("status", &ga402()).await else {
+        return;
+    };
+    asusd.expect_sysfs(PLATFORM_PROFILE, "performance").await;
+
+    // The ga402 has a primary backlight and no screenpad, asusd gives
+    // `NotSupported` for the screenpad brightness
+    let Some(text) = asusd.asusctl(&["status"]) else {
+        return;
+    };
+    let stdout = String::from_utf8_lossy(&text.stdout);
+    assert!(text.status.success(), "{stdout}");
+    assert!(stdout.contains("Profile"), "{stdout}");
+    assert!(stdout.contains("Backlight"), "{stdout}");
+    assert!(!stdout.contains("Screenpad"), "{stdout}");
+
+    let json = asusd
+        .asusctl(&[
+            "--json", "status",
+        ])
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&json.stdout);
+    assert!(json.status.success(), "{stdout}");
+    let status = stdout.split_whitespace().collect::<String>();
+    assert!(status.contains(r#""profile":"Performance""#), "{status}");
+    assert!(
+        status.contains(r#""screenpad_brightness":null"#),
+        "{status}"
+    );
+}
+
+/// An `asusd.ron` from 6.0.1, before configs carried a version
+const ASUSD_RON_601: &str = r#"(
+    charge_control_end_threshold: 70,
+    panel_od: false,
+    boot_sound: false,
+    mini_led_mode: false,
+    disable_nvidia_powerd_on_battery: true,
+    ac_command: "",
+    bat_command: "",
+    platform_profile_linked_epp: true,
+    platform_profile_on_battery: Quiet,
+    change_platform_profile_on_battery: true,
+    platform_profile_on_ac: Performance,
+    change_platform_profile_on_ac: true,
+    profile_quiet_epp: Power,
+    profile_balanced_epp: BalancePower,
+    profile_performance_epp: Performance,
+)"#;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_unversioned_config() {
+    let Some(asusd) =
+        Harness::start_with_config("migrate", &ga402(), &[("asusd.ron", ASUSD_RON_601)]).await
+    else {
+        return;
+    };
+    asusd.expect_sysfs(CHARGE_LIMIT, "70").await;
+
+    let backup = std::fs::read_to_string(asusd.config("asusd.ron.v0.bak")).unwrap();
+    assert_eq!(backup, ASUSD_RON_601);
+    let config = std::fs::read_to_string(asusd.config("asusd.ron")).unwrap();
+    assert!(config.contains("version: 1"));
+    assert!(config.contains("charge_control_end_threshold: 70"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn fan_curves_and_backlight() {
+    let Some(asusd) = Harness::start("fans", &ga402()).await else {
+        return;
+    };
+    let connection = asusd.connection().await;
+
+    let fans = FanCurvesProxy::new(&connection).await.unwrap();
+    let curves = fans
+        .fan_curve_data(PlatformProfile::Balanced)
+        .await
+        .unwrap();
+    assert_eq!(curves.len(), 2);
+    assert_eq!(curves[0].temp, [30, 40, 50, 60, 70, 80, 90, 100]);
+    fans.set_fan_curves_enabled(PlatformProfile::Balanced, true)
+        .await
+        .unwrap();
+    asusd
+        .expect_sysfs(&format!("{FAN_CURVE}/pwm1_enable"), "1")
+        .await;
+    asusd
+        .expect_sysfs(&format!("{FAN_CURVE}/pwm2_enable"), "1")
+        .await;
+
+    let backlight = BacklightProxy::new(&connection).await.unwrap();
+    backlight.set_primary_brightness(25).await.unwrap();
+    asusd
+        .expect_sysfs("class/backlight/intel_backlight/brightness", "128")
+        .await;
+    assert_eq!(backlight.primary_brightness().await.unwrap(), 25);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn charge_limit_and_fan_curve_signals() {
+    const CAPACITY: &str = "class/power_supply/BAT0/capacity";
+    let Some(asusd) = Harness::start("notify", &ga402()).await else {
+        return;
+    };
+    let connection = asusd.connection().await;
+    let platform = PlatformProxy::new(&connection).await.unwrap();
+    let fans = FanCurvesProxy::new(&connection).await.unwrap();
+    let mut reached = platform.receive_charge_limit_reached().await.unwrap();
+    let mut toggled = fans.receive_fan_curves_toggled().await.unwrap();
+
+    // Charging at 76%, so lowering the limit below that reaches it
+    platform.set_charge_control_end_threshold(70).await.unwrap();
+    let args = next_signal(&mut reached).await;
+    let args = args.args().unwrap();
+    assert_eq!((*args.limit(), *args.one_shot()), (70, false));
+
+    std::fs::write(asusd.sysfs(CAPACITY), "100\n").unwrap();
+    platform.one_shot_full_charge().await.unwrap();
+    let args = next_signal(&mut reached).await;
+    let args = args.args().unwrap();
+    assert_eq!((*args.limit(), *args.one_shot()), (100, true));
+
+    fans.set_fan_curves_enabled(PlatformProfile::Balanced, true)
+        .await
+        .unwrap();
+    let args = next_signal(&mut toggled).await;
+    let args = args.args().unwrap();
+    assert_eq!(
+        (*args.profile(), *args.enabled()),
+        (PlatformProfile::Balanced, true)
+    );
+    // Only signalled once no fan of the profile has a curve on
+    fans.set_profile_fan_curve_enabled(PlatformProfile::Balanced, FanCurvePU::CPU, false)
+        .await
+        .unwrap();
+    fans.set_profile_fan_curve_enabled(PlatformProfile::Balanced, FanCurvePU::GPU, false)
+        .await
+        .unwrap();
+    let args = next_signal(&mut toggled).await;
+    let args = args.args().unwrap();
+    assert_eq!(
+        (*args.profile(), *args.enabled()),
+        (PlatformProfile::Balanced, false)
+    );
+}
+
+async fn next_signal<S: futures_util::Stream + Unpin>(signals: &mut S) -> S::Item {
+    tokio::time::timeout(Duration::from_secs(10), signals.next())
+        .await
+        .expect("no signal received")
+        .expect("signal stream ended")
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn tuf_keyboard_aura() {
+    // Only TUF models fall back to the sysfs keyboard controls
+    let mut fixture = ga402();
+    fixture.dmi.insert(
+        "product_name".into(),
+        "ASUS TUF Gaming F15 FX507ZE_FX507ZE".into(),
+    );
+    fixture
+        .dmi
+        .insert("product_family".into(), "ASUS TUF Gaming F15".into());
+    let Some(asusd) = Harness::start("aura", &fixture).await else {
+        return;
+    };
+    let connection = asusd.connection().await;
+
+    let aura = AuraProxy::builder(&connection)
+        .path("/xyz/ljones/aura/tuf")
+        .unwrap()
+        .build()
+        .await
+        .unwrap();
+    aura.set_brightness(LedBrightness::High).await.unwrap();
+    asusd
+        .expect_sysfs("class/leds/asus::kbd_backlight/brightness", "3")
+        .await;
+    assert_eq!(aura.brightness().await.unwrap(), LedBrightness::High);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn screenpad_controls() {
+    const SCREENPAD: &str = "class/backlight/asus_screenpad";
+    let mut fixture = ga402();
+    fixture.backlight.insert(
+        "asus_screenpad".into(),
+        [
+            ("brightness".into(), "100".into()),
+            ("max_brightness".into(), "255".into()),
+            ("bl_power".into(), "0".into()),
+        ]
+        .into(),
+    );
+    // 100 lux
+    fixture.iio.insert(
+        "iio:device0".into(),
+        [
+            ("in_illuminance_raw".into(), "200".into()),
+            ("in_illuminance_scale".into(), "0.5".into()),
+        ]
+        .into(),
+    );
+    let Some(asusd) = Harness::start("screenpad", &fixture).await else {
+        return;
+    };
+    let connection = asusd.connection().await;
+    let backlight = BacklightProxy::new(&connection).await.unwrap();
+
+    // 41% from the default curve between 50 and 300 lux
+    backlight
+        .set_screenpad_ambient_brightness(true)
+        .await
+        .unwrap();
+    asusd
+        .expect_sysfs(&format!("{SCREENPAD}/brightness"), "104")
+        .await;
+
+    // Off fades to 0 then keeps the level for turning back on
+    backlight.toggle_screenpad().await.unwrap();
+    asusd
+        .expect_sysfs(&format!("{SCREENPAD}/bl_power"), "1")
+        .await;
+    assert_eq!(asusd.read_sysfs(&format!("{SCREENPAD}/brightness")), "104");
+    assert!(!backlight.screenpad_power().await.unwrap());
+    backlight.toggle_screenpad().await.unwrap();
+    asusd
+        .expect_sysfs(&format!("{SCREENPAD}/bl_power"), "0")
+        .await;
+    assert_eq!(asusd.read_sysfs(&format!("{SCREENPAD}/brightness")), "104");
+
+    // There are no input devices in the fixture, so it is always idle
+    backlight.set_screenpad_idle_timeout(1).await.unwrap();
+    asusd
+        .expect_sysfs(&format!("{SCREENPAD}/bl_power"), "1")
+        .await;
+    assert_eq!(backlight.screenpad_idle_timeout().await.unwrap(), 1);
+}
@@ -0,0 +1,218 @@
+//! Runs the `asusd` binary against a fake sysfs tree, with a private
+//! `dbus-daemon` standing in for the system bus. Tests talk to it with the
+//! same `rog-dbus` proxies `asusctl` uses, and check what it wrote to sysfs.
+
+use std::fs::{create_dir_all, read_to_string, remove_dir_all, write, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Output, Stdio};
+use std::time::Duration;
+
+use rog_platform::fixture::SysfsFixture;
+use zbus::fdo::DBusProxy;
+use zbus::names::BusName;
+use zbus::Connection;
+
+pub const GA402: &str = include_str!("../../../rog-platform/data/sysfs/ga402.ron");
+
+/// Set to skip the tests when `dbus-daemon` or `asusctl` is missing, by
+/// default they fail
+const SKIP_ENV: &str = "ASUSD_HARNESS_SKIP";
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+const POLL: Duration = Duration::from_millis(50);
+
+const BUS_CONFIG: &str = r#"<!DOCTYPE busconfig PUBLIC "-//freedesktop//DTD D-Bus Bus Configuration 1.0//EN"
+ "http://www.freedesktop.org/standards/dbus/1.0/busconfig.dtd">
+<busconfig>
+  <type>session</type>
+  <listen>unix:path=@SOCKET@</listen>
+  <auth>EXTERNAL</auth>
+  <policy context="default">
+    <allow user="*"/>
+    <allow own="*"/>
+    <allow send_destination="*"/>
+    <allow receive_sender="*"/>
+  </policy>
+</busconfig>
+"#;
+
+pub struct Harness {
+    dir: PathBuf,
+    address: String,
+    dbus: Child,
+    asusd: Child,
+}
+
+impl Harness {
+    /// Build the fixture, start the bus and asusd, and wait until asusd owns
+    /// its name. `None` if `dbus-daemon` is not installed and `SKIP_ENV` is
+    /// set.
+    pub async fn start(name: &str, fixture: &SysfsFixture) -> Option<Self> {
+        Self::start_with_config(name, fixture, &[]).await
+    }
+
+    /// As [`Harness::start`], with config files written to the config dir
+    /// before asusd starts
+    pub async fn start_with_config(
+        name: &str,
+        fixture: &SysfsFixture,
+        configs: &[(&str, &str)],
+    ) -> Option<Self> {
+        let dir = std::env::temp_dir().join(format!("asusd-harness-{name}-{}", std::process::id()));
+        remove_dir_all(&dir).ok();
+        create_dir_all(dir.join("config")).unwrap();
+        for (file, content) in configs {
+            write(dir.join("config").join(file), content).unwrap();
+        }
+        fixture.create(&dir.join("sysfs")).unwrap();
+
+        let bus_config = dir.join("bus.conf");
+        let socket = dir.join("bus");
+        write(
+            &bus_config,
+            BUS_CONFIG.replace("@SOCKET@", &socket.to_string_lossy()),
+        )
+        .unwrap();
+
+        let mut dbus = match Command::new("dbus-daemon")
+            .arg(format!("--config-file={}", bus_config.display()))
+            .args([
+                "--nofork", "--print-address",
+            ])
+            .stdout(Stdio::piped())
+            .spawn()
+        {
+            Ok(dbus) => dbus,
+            Err(e) => {
+                remove_dir_all(&dir).ok();
+                return skip(&format!("dbus-daemon not available: {e}"));
+            }
+        };
+        let mut address = String::new();
+        BufReader::new(dbus.stdout.take().unwrap())
+            .read_line(&mut address)
+            .unwrap();
+        let address = address.trim().to_owned();
+
+        let asusd = Command::new(env!("CARGO_BIN_EXE_asusd"))
+            .env("IS_SERVICE", "1")
+            .env("DBUS_SYSTEM_BUS_ADDRESS", &address)
+            .env(rog_platform::sysfs::SYSFS_ROOT_ENV, dir.join("sysfs"))
+            .env(asusd::CONFIG_DIR_ENV, dir.join("config"))
+            .stdout(File::create(dir.join("asusd.log")).unwrap())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let harness = Self {
+            dir,
+            address,
+            dbus,
+            asusd,
+        };
+        harness.wait_for_asusd().await;
+        Some(harness)
+    }
+
+    async fn wait_for_asusd(&self) {
+        let connection = self.connection().await;
+        let dbus = DBusProxy::new(&connection).await.unwrap();
+        let name: BusName = asusd::DBUS_NAME.try_into().unwrap();
+        let started = tokio::time::timeout(TIMEOUT, async {
+            while !dbus.name_has_owner(name.clone()).await.unwrap_or(false) {
+                tokio::time::sleep(POLL).await;
+            }
+        })
+        .await;
+        if started.is_err() {
+            panic!("asusd did not start, log:\n{}", self.log());
+        }
+    }
+
+    /// A new connection to the private bus
+    pub async fn connection(&self) -> Connection {
+        zbus::connection::Builder::address(self.address.as_str())
+            .unwrap()
+            .build()
+            .await
+            .unwrap()
+    }
+
+    pub fn sysfs(&self, path: &str) -> PathBuf {
+        self.dir.join("sysfs").join(path)
+    }
+
+    pub fn config(&self, file: &str) -> PathBuf {
+        self.dir.join("config").join(file)
+    }
+
+    /// Read a file from the fake sysfs tree without the trailing newline
+    pub fn read_sysfs(&self, path: &str) -> String {
+        read_to_string(self.sysfs(path))
+            .unwrap()
+            .trim_end()
+            .to_owned()
+    }
+
+    /// Wait until a sysfs file holds `expected`, panics with the asusd log if
+    /// it never does
+    pub async fn expect_sysfs(&self, path: &str, expected: &str) {
+        let res = tokio::time::timeout(TIMEOUT, async {
+            while self.read_sysfs(path) != expected {
+                tokio::time::sleep(POLL).await;
+            }
+        })
+        .await;
+        if res.is_err() {
+            panic!(
+                "{path} is {:?}, expected {expected:?}, log:\n{}",
+                self.read_sysfs(path),
+                self.log()
+            );
+        }
+    }
+
+    /// Run the `asusctl` built next to asusd against the private bus. It is not
+    /// built by `cargo test --package asusd`, so build it first. `None` if it
+    /// is missing and `SKIP_ENV` is set.
+    pub fn asusctl(&self, args: &[&str]) -> Option<Output> {
+        let bin = Path::new(env!("CARGO_BIN_EXE_asusd")).with_file_name("asusctl");
+        if !bin.exists() {
+            return skip(&format!(
+                "{} not built, run `cargo build --package asusctl`",
+                bin.display()
+            ));
+        }
+        let output = Command::new(bin)
+            .args(args)
+            .env("DBUS_SYSTEM_BUS_ADDRESS", &self.address)
+            .env(rog_platform::sysfs::SYSFS_ROOT_ENV, self.dir.join("sysfs"))
+            .output()
+            .unwrap();
+        Some(output)
+    }
+
+    pub fn log(&self) -> String {
+        read_to_string(self.dir.join("asusd.log")).unwrap_or_default()
+    }
+}
+
+impl Drop for Harness {
+    fn drop(&mut self) {
+        self.asusd.kill().ok();
+        self.asusd.wait().ok();
+        self.dbus.kill().ok();
+        self.dbus.wait().ok();
+        remove_dir_all(&self.dir).ok();
+    }
+}
+
+/// `None` if `SKIP_ENV` is set, otherwise fail the test
+fn skip<T>(reason: &str) -> Option<T> {
+    if std::env::var_os(SKIP_ENV).is_some() {
+        eprintln!("{reason}, skipping");
+        return None;
+    }
+    panic!("{reason}, set {SKIP_ENV}=1 to skip the dbus tests instead");
+}
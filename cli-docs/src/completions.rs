@@ -0,0 +1,423 @@
+//! Completion scripts for bash, zsh and fish. Each script finds the
+//! subcommand being completed by following the subcommand names on the
+//! command line, then suggests the value of the option before the cursor, or
+//! else the options and subcommands of that subcommand.
+
+use std::fmt::Write;
+use std::str::FromStr;
+
+use crate::{Arg, Command, Values};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    pub const ALL: [Self; 3] = [
+        Self::Bash,
+        Self::Zsh,
+        Self::Fish,
+    ];
+
+    /// Name of the completion file the shell looks for
+    pub fn file_name(self, program: &str) -> String {
+        match self {
+            Self::Bash => program.to_owned(),
+            Self::Zsh => format!("_{program}"),
+            Self::Fish => format!("{program}.fish"),
+        }
+    }
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "bash" => Ok(Self::Bash),
+            "zsh" => Ok(Self::Zsh),
+            "fish" => Ok(Self::Fish),
+            _ => Err(format!("unknown shell {s}, must be one of bash, zsh, fish")),
+        }
+    }
+}
+
+impl Command {
+    /// The completion script for `shell`, with `self` as the program
+    pub fn completions(&self, shell: Shell) -> String {
+        match shell {
+            Shell::Bash => bash(self),
+            Shell::Zsh => zsh(self),
+            Shell::Fish => fish(self),
+        }
+    }
+}
+
+/// Quote for bash or zsh
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// Quote for fish, which allows escapes inside single quotes
+fn fish_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
+fn function_name(program: &str) -> String {
+    format!("_{}", program.replace('-', "_"))
+}
+
+/// The shell command printing the values of `key`
+fn query(program: &str, key: &str) -> String {
+    format!("{program} completions --values {key} 2>/dev/null")
+}
+
+/// `/aura/static` for the path `["aura", "static"]`, the top level is empty
+fn path_key(path: &[&str]) -> String {
+    path.iter().map(|name| format!("/{name}")).collect()
+}
+
+/// Every subcommand path, for the pattern that follows them
+fn subcommand_paths(cli: &Command) -> Vec<String> {
+    cli.all()
+        .into_iter()
+        .skip(1)
+        .map(|(path, _)| path_key(&path))
+        .collect()
+}
+
+/// Options that take a value, as the pattern of the path and each way of
+/// writing the option, such as `/profile:--profile-set`
+fn value_patterns(cli: &Command) -> Vec<(Vec<String>, &Arg)> {
+    cli.all()
+        .into_iter()
+        .flat_map(|(path, command)| {
+            let key = path_key(&path);
+            command
+                .options
+                .iter()
+                .filter(|arg| arg.meta.is_some())
+                .map(move |arg| {
+                    let patterns = arg
+                        .flags()
+                        .iter()
+                        .map(|flag| format!("{key}:{flag}"))
+                        .collect();
+                    (patterns, arg)
+                })
+        })
+        .collect()
+}
+
+fn bash(cli: &Command) -> String {
+    let program = &cli.name;
+    let mut s = String::new();
+    writeln!(s, "# bash completion for {program}").ok();
+    writeln!(s).ok();
+    writeln!(s, "{}() {{", function_name(program)).ok();
+    s.push_str(
+        r#"    local cur prev cmd_path i
+    cur="${COMP_WORDS[COMP_CWORD]}"
+    prev="${COMP_WORDS[COMP_CWORD-1]}"
+    cmd_path=""
+"#,
+    );
+
+    let paths = subcommand_paths(cli);
+    if !paths.is_empty() {
+        let patterns: Vec<String> = paths.iter().map(|p| quote(p)).collect();
+        writeln!(s, "    for ((i = 1; i < COMP_CWORD; i++)); do").ok();
+        writeln!(s, r#"        case "${{cmd_path}}/${{COMP_WORDS[i]}}" in"#).ok();
+        writeln!(s, "            {})", patterns.join("|")).ok();
+        writeln!(
+            s,
+            r#"                cmd_path="${{cmd_path}}/${{COMP_WORDS[i]}}""#
+        )
+        .ok();
+        writeln!(s, "                ;;").ok();
+        writeln!(s, "        esac").ok();
+        writeln!(s, "    done").ok();
+    }
+
+    writeln!(s).ok();
+    writeln!(s, r#"    case "${{cmd_path}}:${{prev}}" in"#).ok();
+    for (patterns, arg) in value_patterns(cli) {
+        let patterns: Vec<String> = patterns.iter().map(|p| quote(p)).collect();
+        writeln!(s, "        {})", patterns.join("|")).ok();
+        match &arg.values {
+            Values::Any => {}
+            Values::List(list) => {
+                writeln!(
+                    s,
+                    r#"            COMPREPLY=($(compgen -W {} -- "${{cur}}"))"#,
+                    quote(&list.join(" "))
+                )
+                .ok();
+            }
+            Values::Path => {
+                writeln!(s, r#"            COMPREPLY=($(compgen -f -- "${{cur}}"))"#).ok();
+            }
+            Values::Query(key) => {
+                writeln!(
+                    s,
+                    r#"            COMPREPLY=($(compgen -W "$({})" -- "${{cur}}"))"#,
+                    query(program, key)
+                )
+                .ok();
+            }
+        }
+        writeln!(s, "            return").ok();
+        writeln!(s, "            ;;").ok();
+    }
+    writeln!(s, "    esac").ok();
+
+    writeln!(s).ok();
+    writeln!(s, r#"    case "${{cmd_path}}" in"#).ok();
+    for (path, command) in cli.all() {
+        let mut words: Vec<String> = command.options.iter().flat_map(Arg::flags).collect();
+        if command.command_query.is_none() {
+            words.extend(command.commands.iter().map(|c| c.name.clone()));
+        }
+        for arg in &command.free {
+            if let Values::List(list) = &arg.values {
+                words.extend(list.iter().cloned());
+            }
+        }
+        let mut words = format!("\"{}", words.join(" "));
+        if let Some(key) = &command.command_query {
+            write!(words, " $({})", query(program, key)).ok();
+        }
+        for arg in &command.free {
+            if let Values::Query(key) = &arg.values {
+                write!(words, " $({})", query(program, key)).ok();
+            }
+        }
+        words.push('"');
+
+        writeln!(s, "        {})", quote(&path_key(&path))).ok();
+        writeln!(
+            s,
+            r#"            COMPREPLY=($(compgen -W {words} -- "${{cur}}"))"#
+        )
+        .ok();
+        if command.free.iter().any(|arg| arg.values == Values::Path) {
+            writeln!(s, r#"            COMPREPLY+=($(compgen -f -- "${{cur}}"))"#).ok();
+        }
+        writeln!(s, "            ;;").ok();
+    }
+    writeln!(s, "    esac").ok();
+    writeln!(s, "}}").ok();
+    writeln!(s).ok();
+    writeln!(s, "complete -F {} {program}", function_name(program)).ok();
+    s
+}
+
+fn zsh(cli: &Command) -> String {
+    let program = &cli.name;
+    let function = function_name(program);
+    let mut s = String::new();
+    writeln!(s, "#compdef {program}").ok();
+    writeln!(s).ok();
+    writeln!(s, "{function}() {{").ok();
+    // `path` is the zsh array of `PATH` so is not used as a name
+    s.push_str(
+        r#"    local cmd_path="" prev="${words[CURRENT-1]}" i
+    local -a items
+"#,
+    );
+
+    let paths = subcommand_paths(cli);
+    if !paths.is_empty() {
+        let patterns: Vec<String> = paths.iter().map(|p| quote(p)).collect();
+        writeln!(s, "    for ((i = 2; i < CURRENT; i++)); do").ok();
+        writeln!(s, r#"        case "${{cmd_path}}/${{words[i]}}" in"#).ok();
+        writeln!(s, "            ({})", patterns.join("|")).ok();
+        writeln!(
+            s,
+            r#"                cmd_path="${{cmd_path}}/${{words[i]}}""#
+        )
+        .ok();
+        writeln!(s, "                ;;").ok();
+        writeln!(s, "        esac").ok();
+        writeln!(s, "    done").ok();
+    }
+
+    writeln!(s).ok();
+    writeln!(s, r#"    case "${{cmd_path}}:${{prev}}" in"#).ok();
+    for (patterns, arg) in value_patterns(cli) {
+        let patterns: Vec<String> = patterns.iter().map(|p| quote(p)).collect();
+        writeln!(s, "        ({})", patterns.join("|")).ok();
+        match &arg.values {
+            Values::Any => {}
+            Values::List(list) => {
+                let list: Vec<String> = list.iter().map(|v| quote(v)).collect();
+                writeln!(s, "            compadd -- {}", list.join(" ")).ok();
+            }
+            Values::Path => {
+                writeln!(s, "            _files").ok();
+            }
+            Values::Query(key) => {
+                writeln!(
+                    s,
+                    r#"            compadd -- ${{(f)"$({})"}}"#,
+                    query(program, key)
+                )
+                .ok();
+            }
+        }
+        writeln!(s, "            return").ok();
+        writeln!(s, "            ;;").ok();
+    }
+    writeln!(s, "    esac").ok();
+
+    writeln!(s).ok();
+    writeln!(s, r#"    case "${{cmd_path}}" in"#).ok();
+    for (path, command) in cli.all() {
+        // `_describe` takes `name:description`, a colon in the name is escaped
+        let item = |name: &str, help: &str| quote(&format!("{}:{help}", name.replace(':', r"\:")));
+        let mut items: Vec<String> = command
+            .options
+            .iter()
+            .flat_map(|arg| arg.flags().into_iter().map(|flag| item(&flag, &arg.help)))
+            .collect();
+        if command.command_query.is_none() {
+            items.extend(command.commands.iter().map(|c| item(&c.name, &c.help)));
+        }
+        for arg in &command.free {
+            if let Values::List(list) = &arg.values {
+                items.extend(list.iter().map(|v| item(v, &arg.help)));
+            }
+        }
+
+        writeln!(s, "        ({})", quote(&path_key(&path))).ok();
+        writeln!(s, "            items=(").ok();
+        for item in items {
+            writeln!(s, "                {item}").ok();
+        }
+        writeln!(s, "            )").ok();
+        let queries = command
+            .command_query
+            .iter()
+            .chain(command.free.iter().filter_map(|arg| match &arg.values {
+                Values::Query(key) => Some(key),
+                _ => None,
+            }));
+        for key in queries {
+            writeln!(
+                s,
+                r#"            items+=(${{(f)"$({})"}})"#,
+                query(program, key)
+            )
+            .ok();
+        }
+        if command.free.iter().any(|arg| arg.values == Values::Path) {
+            writeln!(s, "            _files").ok();
+        }
+        writeln!(s, "            ;;").ok();
+    }
+    writeln!(s, "    esac").ok();
+    writeln!(s, "    _describe -t commands {} items", quote(program)).ok();
+    writeln!(s, "}}").ok();
+    writeln!(s).ok();
+    writeln!(s, r#"{function} "$@""#).ok();
+    s
+}
+
+fn fish(cli: &Command) -> String {
+    let program = &cli.name;
+    let function = format!("_{}_path", function_name(program));
+    let mut s = String::new();
+    writeln!(s, "# fish completion for {program}").ok();
+    writeln!(s).ok();
+    writeln!(
+        s,
+        "# True if the subcommand being completed is the one at the path given"
+    )
+    .ok();
+    writeln!(s, "function {function}").ok();
+    writeln!(s, r#"    set -l cmd_path """#).ok();
+    let paths = subcommand_paths(cli);
+    if !paths.is_empty() {
+        writeln!(s, "    for word in (commandline -opc)[2..-1]").ok();
+        writeln!(s, r#"        switch "$cmd_path/$word""#).ok();
+        writeln!(s, "            case {}", paths.join(" ")).ok();
+        writeln!(s, r#"                set cmd_path "$cmd_path/$word""#).ok();
+        writeln!(s, "        end").ok();
+        writeln!(s, "    end").ok();
+    }
+    writeln!(s, r#"    test "$cmd_path" = "$argv[1]""#).ok();
+    writeln!(s, "end").ok();
+    writeln!(s).ok();
+    writeln!(s, "complete -c {program} -f").ok();
+
+    for (path, command) in cli.all() {
+        let condition = format!(
+            "-n {}",
+            fish_quote(&format!("{function} '{}'", path_key(&path)))
+        );
+        let complete = format!("complete -c {program} {condition}");
+        for arg in &command.options {
+            let mut line = complete.clone();
+            if let Some(short) = arg.short {
+                write!(line, " -s {short}").ok();
+            }
+            if let Some(long) = &arg.long {
+                write!(line, " -l {long}").ok();
+            }
+            if arg.meta.is_some() {
+                match &arg.values {
+                    Values::Any => line.push_str(" -x"),
+                    Values::List(list) => {
+                        write!(line, " -x -a {}", fish_quote(&list.join(" "))).ok();
+                    }
+                    Values::Path => line.push_str(" -r -F"),
+                    Values::Query(key) => {
+                        write!(
+                            line,
+                            " -x -a {}",
+                            fish_quote(&format!("({})", query(program, key)))
+                        )
+                        .ok();
+                    }
+                }
+            }
+            if !arg.help.is_empty() {
+                write!(line, " -d {}", fish_quote(&arg.help)).ok();
+            }
+            writeln!(s, "{line}").ok();
+        }
+
+        if let Some(key) = &command.command_query {
+            writeln!(
+                s,
+                "{complete} -a {}",
+                fish_quote(&format!("({})", query(program, key)))
+            )
+            .ok();
+        } else {
+            for sub in &command.commands {
+                let mut line = format!("{complete} -a {}", fish_quote(&sub.name));
+                if !sub.help.is_empty() {
+                    write!(line, " -d {}", fish_quote(&sub.help)).ok();
+                }
+                writeln!(s, "{line}").ok();
+            }
+        }
+
+        for arg in &command.free {
+            let values = match &arg.values {
+                Values::Any => continue,
+                Values::List(list) => format!("-a {}", fish_quote(&list.join(" "))),
+                Values::Path => "-F".to_owned(),
+                Values::Query(key) => {
+                    format!("-a {}", fish_quote(&format!("({})", query(program, key))))
+                }
+            };
+            writeln!(s, "{complete} {values} -d {}", fish_quote(&arg.help)).ok();
+        }
+    }
+    s
+}
@@ -0,0 +1,361 @@
+//! Shell completions and man pages for the `gumdrop` command lines of
+//! `asusctl` and `rog-control-center`.
+//!
+//! `gumdrop` describes options only through the usage text, so a [`Command`]
+//! tree is read from that text, reaching each subcommand by parsing its name.
+//! The values an option takes come from lists in its help, such as
+//! `<off, low, med, high>`. Values only the running laptop knows, such as
+//! the aura modes it supports, are a [`Values::Query`] that the completion
+//! scripts ask the program for.
+
+mod completions;
+mod man;
+
+use gumdrop::{Opt, Options, ParsingStyle};
+
+pub use crate::completions::Shell;
+
+/// What to suggest for the value of an option or a positional argument
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Values {
+    /// Nothing is suggested
+    Any,
+    /// One of a fixed list
+    List(Vec<String>),
+    /// A file or directory
+    Path,
+    /// Asked of the program while completing with
+    /// `<program> completions --values <key>`, which prints one value a line
+    Query(String),
+}
+
+/// An option, or a positional argument when neither `short` nor `long` is
+/// set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arg {
+    pub short: Option<char>,
+    pub long: Option<String>,
+    /// Name of the value taken, `None` for a flag
+    pub meta: Option<String>,
+    pub help: String,
+    pub values: Values,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Command {
+    pub name: String,
+    pub help: String,
+    pub options: Vec<Arg>,
+    /// Positional arguments
+    pub free: Vec<Arg>,
+    pub commands: Vec<Command>,
+    /// Set if the running laptop supports only some of `commands`, the key
+    /// to query the names of those it does
+    pub command_query: Option<String>,
+}
+
+impl Command {
+    /// The command line of `T` with all of its subcommands, `name` being the
+    /// program name
+    pub fn from_options<T: Options>(name: &str, help: &str) -> Self {
+        Self::read::<T>(name, help, &[]).unwrap_or_else(|| Self {
+            name: name.to_owned(),
+            help: help.to_owned(),
+            options: Vec::new(),
+            free: Vec::new(),
+            commands: Vec::new(),
+            command_query: None,
+        })
+    }
+
+    /// Read the command at `path` by parsing the path as arguments, which
+    /// gives the usage of the last subcommand in it
+    fn read<T: Options>(name: &str, help: &str, path: &[&str]) -> Option<Self> {
+        let parsed = T::parse_args(path, ParsingStyle::AllOptions).ok()?;
+        let (free, mut options) = read_usage(parsed.self_usage());
+        for arg in &mut options {
+            if arg.meta.is_none() && takes_value::<T>(path, arg) {
+                arg.meta = Some("VALUE".to_owned());
+            }
+            if arg.meta.is_none() {
+                arg.values = Values::Any;
+            }
+        }
+
+        let commands = parsed
+            .self_command_list()
+            .map(read_entries)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(command, help)| {
+                let mut path = path.to_vec();
+                path.push(&command);
+                Self::read::<T>(&command, &help, &path)
+            })
+            .collect();
+
+        Some(Self {
+            name: name.to_owned(),
+            help: help.to_owned(),
+            options,
+            free,
+            commands,
+            command_query: None,
+        })
+    }
+
+    /// The subcommand at `path`, such as `["anime", "image"]`
+    pub fn find_mut(&mut self, path: &[&str]) -> Option<&mut Command> {
+        let Some((first, rest)) = path.split_first() else {
+            return Some(self);
+        };
+        self.commands
+            .iter_mut()
+            .find(|c| c.name == *first)?
+            .find_mut(rest)
+    }
+
+    /// The option with the long, or short, name `name`, or the positional
+    /// argument of that name
+    pub fn arg_mut(&mut self, name: &str) -> Option<&mut Arg> {
+        let short = |arg: &Arg| {
+            arg.short
+                .is_some_and(|s| name.len() == 1 && name.starts_with(s))
+        };
+        self.options
+            .iter_mut()
+            .chain(self.free.iter_mut())
+            .find(|arg| {
+                arg.long.as_deref() == Some(name)
+                    || short(arg)
+                    || (arg.long.is_none()
+                        && arg.short.is_none()
+                        && arg.meta.as_deref() == Some(name))
+            })
+    }
+
+    /// Set what to suggest for option `name` of the subcommand at `path`
+    pub fn set_values(&mut self, path: &[&str], name: &str, values: Values) {
+        if let Some(arg) = self.find_mut(path).and_then(|c| c.arg_mut(name)) {
+            arg.values = values;
+        }
+    }
+
+    /// This command and every subcommand under it, with the names leading to
+    /// each. The path of this command is empty.
+    fn all(&self) -> Vec<(Vec<&str>, &Command)> {
+        let mut all = vec![(Vec::new(), self)];
+        for command in &self.commands {
+            for (mut path, sub) in command.all() {
+                path.insert(0, command.name.as_str());
+                all.push((path, sub));
+            }
+        }
+        all
+    }
+}
+
+impl Arg {
+    /// The ways of writing the option, such as `-k` and `--kbd-bright`
+    fn flags(&self) -> Vec<String> {
+        self.short
+            .map(|s| format!("-{s}"))
+            .into_iter()
+            .chain(self.long.as_ref().map(|l| format!("--{l}")))
+            .collect()
+    }
+}
+
+/// Whether option `arg` of the subcommand at `path` takes a value. An empty
+/// `meta` hides the value in the usage, so ask the parser instead.
+fn takes_value<T: Options>(path: &[&str], arg: &Arg) -> bool {
+    let (flag, opt) = match (&arg.long, arg.short) {
+        (Some(long), _) => (format!("--{long}"), Opt::Long(long)),
+        (None, Some(short)) => (format!("-{short}"), Opt::Short(short)),
+        (None, None) => return false,
+    };
+    let mut args = path.to_vec();
+    args.push(&flag);
+    let missing = gumdrop::Error::missing_argument(opt).to_string();
+    matches!(T::parse_args(&args, ParsingStyle::AllOptions), Err(e) if e.to_string() == missing)
+}
+
+/// The entries of a usage section as the first column and the help. Help too
+/// long for the first column is on the following line.
+fn read_entries(text: &str) -> Vec<(String, String)> {
+    let mut entries: Vec<(String, String)> = Vec::new();
+    for line in text.lines() {
+        let Some(entry) = line.strip_prefix("  ") else {
+            continue;
+        };
+        if entry.starts_with(' ') {
+            if let Some((_, help)) = entries.last_mut() {
+                help.push_str(entry.trim());
+            }
+        } else {
+            let (first, help) = entry.split_once("  ").unwrap_or((entry, ""));
+            entries.push((first.trim().to_owned(), help.trim().to_owned()));
+        }
+    }
+    entries
+}
+
+/// Split a usage in to the positional arguments and the options
+fn read_usage(usage: &str) -> (Vec<Arg>, Vec<Arg>) {
+    let (mut free, mut options) = (Vec::new(), Vec::new());
+    // Each section is its header followed by indented entries
+    for section in usage.split("\n\n") {
+        let Some((header, entries)) = section.split_once('\n') else {
+            continue;
+        };
+        for (first, help) in read_entries(entries) {
+            let values = values_in_help(&help);
+            let mut arg = Arg {
+                short: None,
+                long: None,
+                meta: None,
+                help,
+                values,
+            };
+            match header {
+                "Positional arguments:" => {
+                    arg.meta = Some(first);
+                    free.push(arg);
+                }
+                "Optional arguments:" => {
+                    for word in first.split_whitespace() {
+                        let word = word.trim_end_matches(',');
+                        if let Some(long) = word.strip_prefix("--") {
+                            arg.long = Some(long.to_owned());
+                        } else if let Some(short) = word.strip_prefix('-') {
+                            arg.short = short.chars().next();
+                        } else {
+                            arg.meta = Some(word.to_owned());
+                        }
+                    }
+                    options.push(arg);
+                }
+                _ => {}
+            }
+        }
+    }
+    (free, options)
+}
+
+/// A list of values in the help, either `<a, b, c>`, `<a/b/c>` or ending the
+/// help as `: a, b, c`. Ranges such as `<0-255>` are not a list.
+fn values_in_help(help: &str) -> Values {
+    let bracketed = help
+        .split_once('<')
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .map(|(list, _)| list);
+    let trailing = help.rsplit_once(": ").map(|(_, list)| list);
+
+    for list in bracketed.into_iter().chain(trailing) {
+        let values: Vec<String> = list
+            .split([',', '/'])
+            .map(|v| v.trim().trim_start_matches("default:").to_owned())
+            .collect();
+        let is_word = |v: &String| {
+            v.chars().any(|c| c.is_ascii_alphabetic())
+                && v.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        };
+        if values.len() > 1 && values.iter().all(is_word) {
+            return Values::List(values);
+        }
+    }
+    Values::Any
+}
+
+#[cfg(test)]
+mod tests {
+    use gumdrop::Options;
+
+    use super::{values_in_help, Command, Values};
+
+    #[derive(Debug, Default, Options)]
+    struct Cli {
+        #[options(help = "print help message")]
+        help: bool,
+        #[options(meta = "", help = "<off, low, med, high>")]
+        bright: Option<String>,
+        #[options(help = "a flag with help long enough to be put on the next line of the usage")]
+        next_brightness_level_of_the_keyboard: bool,
+        #[options(command)]
+        command: Option<Sub>,
+    }
+
+    #[derive(Debug, Options)]
+    enum Sub {
+        #[options(help = "set a mode")]
+        Mode(Mode),
+    }
+
+    #[derive(Debug, Default, Options)]
+    struct Mode {
+        #[options(no_long, meta = "WORD", help = "set the speed: low, med, high")]
+        speed: String,
+        #[options(free, help = "names of the modes")]
+        names: Vec<String>,
+    }
+
+    fn list(values: &[&str]) -> Values {
+        Values::List(values.iter().map(|v| v.to_string()).collect())
+    }
+
+    #[test]
+    fn help_values() {
+        assert_eq!(
+            values_in_help("<off, low, med, high>"),
+            list(&["off", "low", "med", "high"])
+        );
+        assert_eq!(
+            values_in_help("select fan <cpu/gpu/mid> to modify"),
+            list(&["cpu", "gpu", "mid"])
+        );
+        assert_eq!(
+            values_in_help("Default is used, <default:GlitchOut, SeeYa>"),
+            list(&["GlitchOut", "SeeYa"])
+        );
+        assert_eq!(
+            values_in_help("set the speed: low, med, high"),
+            list(&["low", "med", "high"])
+        );
+        assert_eq!(values_in_help("Set brightness value <0-255>"), Values::Any);
+        assert_eq!(values_in_help("Set LED colours <hex>"), Values::Any);
+        assert_eq!(
+            values_in_help("set the zone e.g, 0, 1, one, logo"),
+            Values::Any
+        );
+    }
+
+    #[test]
+    fn read_options() {
+        let mut cli = Command::from_options::<Cli>("cli", "testing");
+        assert_eq!(cli.options.len(), 3);
+        assert_eq!(cli.options[0].flags(), ["-h", "--help"]);
+        assert_eq!(cli.options[0].meta, None);
+        assert_eq!(cli.options[1].meta.as_deref(), Some("VALUE"));
+        assert_eq!(cli.options[1].values, list(&["off", "low", "med", "high"]));
+        assert_eq!(
+            cli.options[2].help,
+            "a flag with help long enough to be put on the next line of the usage"
+        );
+
+        assert_eq!(cli.commands.len(), 1);
+        let mode = cli.find_mut(&["mode"]).unwrap();
+        assert_eq!(mode.help, "set a mode");
+        assert_eq!(mode.options[0].flags(), ["-s"]);
+        assert_eq!(mode.options[0].meta.as_deref(), Some("WORD"));
+        assert_eq!(mode.options[0].values, list(&["low", "med", "high"]));
+        assert_eq!(mode.free[0].meta.as_deref(), Some("names"));
+
+        cli.set_values(&["mode"], "names", Values::Query("modes".into()));
+        assert_eq!(
+            cli.commands[0].free[0].values,
+            Values::Query("modes".into())
+        );
+        assert_eq!(cli.all().len(), 2);
+    }
+}
@@ -0,0 +1,157 @@
+//! Man pages in roff, one for the program and one for each subcommand named
+//! after its path as git does, such as `asusctl-aura-static(1)`.
+
+use std::fmt::Write;
+
+use crate::{Arg, Command, Values};
+
+/// Escape text for roff, `-` is escaped so that it is not a hyphen
+fn roff(text: &str) -> String {
+    let text = text.replace('\\', r"\e").replace('-', r"\-");
+    if text.starts_with([
+        '.', '\'',
+    ]) {
+        format!(r"\&{text}")
+    } else {
+        text
+    }
+}
+
+fn page_name(program: &str, path: &[&str]) -> String {
+    path_words(program, path).replace(' ', "-")
+}
+
+fn write_arg(s: &mut String, program: &str, arg: &Arg) {
+    writeln!(s, ".TP").ok();
+    let mut first: Vec<String> = arg
+        .flags()
+        .iter()
+        .map(|flag| format!(r"\fB{}\fR", roff(flag)))
+        .collect();
+    if let Some(meta) = &arg.meta {
+        if first.is_empty() {
+            first.push(format!(r"\fI{}\fR", roff(meta)));
+        } else if let Some(last) = first.last_mut() {
+            write!(last, r" \fI{}\fR", roff(meta)).ok();
+        }
+    }
+    writeln!(s, "{}", first.join(", ")).ok();
+    writeln!(s, "{}", roff(&arg.help)).ok();
+    match &arg.values {
+        Values::List(list) if arg.meta.is_some() => {
+            writeln!(s, ".br").ok();
+            writeln!(s, "Possible values: {}", roff(&list.join(", "))).ok();
+        }
+        Values::Query(key) => {
+            writeln!(s, ".br").ok();
+            writeln!(
+                s,
+                r"The values this laptop supports are listed by \fB{}\fR",
+                roff(&format!("{program} completions --values {key}"))
+            )
+            .ok();
+        }
+        _ => {}
+    }
+}
+
+impl Command {
+    /// A man page for this program and each subcommand as the file name and
+    /// the page
+    pub fn man_pages(&self, version: &str) -> Vec<(String, String)> {
+        let program = &self.name;
+        self.all()
+            .into_iter()
+            .map(|(path, command)| {
+                let name = page_name(program, &path);
+                let mut s = String::new();
+                writeln!(
+                    s,
+                    r#".TH {} 1 "" "{}" "User Commands""#,
+                    roff(&name.to_uppercase()),
+                    roff(&format!("{program} {version}"))
+                )
+                .ok();
+
+                writeln!(s, ".SH NAME").ok();
+                if command.help.is_empty() {
+                    writeln!(s, "{}", roff(&name)).ok();
+                } else {
+                    writeln!(s, r"{} \- {}", roff(&name), roff(&command.help)).ok();
+                }
+
+                writeln!(s, ".SH SYNOPSIS").ok();
+                let mut synopsis = format!(r"\fB{}\fR", roff(&path_words(program, &path)));
+                if !command.options.is_empty() {
+                    synopsis.push_str(r" [\fIOPTIONS\fR]");
+                }
+                for arg in &command.free {
+                    if let Some(meta) = &arg.meta {
+                        write!(synopsis, r" [\fI{}\fR...]", roff(&meta.to_uppercase())).ok();
+                    }
+                }
+                if !command.commands.is_empty() {
+                    synopsis.push_str(r" [\fICOMMAND\fR]");
+                }
+                writeln!(s, "{synopsis}").ok();
+
+                if !command.free.is_empty() {
+                    writeln!(s, ".SH ARGUMENTS").ok();
+                    for arg in &command.free {
+                        write_arg(&mut s, program, arg);
+                    }
+                }
+
+                if !command.options.is_empty() {
+                    writeln!(s, ".SH OPTIONS").ok();
+                    for arg in &command.options {
+                        write_arg(&mut s, program, arg);
+                    }
+                }
+
+                if !command.commands.is_empty() {
+                    writeln!(s, ".SH COMMANDS").ok();
+                    if let Some(key) = &command.command_query {
+                        writeln!(
+                            s,
+                            r"Not every laptop supports all of these, those this one does are listed by \fB{}\fR",
+                            roff(&format!("{program} completions --values {key}"))
+                        )
+                        .ok();
+                    }
+                    for sub in &command.commands {
+                        let mut sub_path = path.clone();
+                        sub_path.push(&sub.name);
+                        writeln!(s, ".TP").ok();
+                        writeln!(s, r"\fB{}\fR", roff(&sub.name)).ok();
+                        if !sub.help.is_empty() {
+                            writeln!(s, "{}", roff(&sub.help)).ok();
+                            writeln!(s, ".br").ok();
+                        }
+                        writeln!(
+                            s,
+                            r"See \fB{}\fR(1)",
+                            roff(&page_name(program, &sub_path))
+                        )
+                        .ok();
+                    }
+                }
+
+                if let Some((_, parent)) = path.split_last() {
+                    writeln!(s, ".SH SEE ALSO").ok();
+                    writeln!(s, r"\fB{}\fR(1)", roff(&page_name(program, parent))).ok();
+                }
+
+                (format!("{name}.1"), s)
+            })
+            .collect()
+    }
+}
+
+/// The command line leading to the subcommand, such as `asusctl aura static`
+fn path_words(program: &str, path: &[&str]) -> String {
+    std::iter::once(program)
+        .chain(path.iter().copied())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -3,7 +3,11 @@
 //! updating them from previous versions where fields or names are changed in
 //! some way.
 //!
-//! The end canonical file format is `.ron` as this supports rust types well
+//! The end canonical file format is `.ron` as this supports rust types well.
+//! Configs implementing [`StdConfigVersioned`] carry a layout version and are
+//! upgraded by explicit migration steps, see the [`versioned`] module.
+
+pub mod versioned;
 
 use std::fs::{self, create_dir, File, OpenOptions};
 use std::io::{Read, Write};
@@ -14,6 +18,7 @@ pub use ron;
 use ron::ser::PrettyConfig;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+pub use versioned::{ConfigReport, ConfigVersion, Migration, StdConfigVersioned};
 
 /// Config file helper traits. Only `new()` and `file_name()` are required to be
 /// implemented, the rest are intended to be free methods.
@@ -144,18 +149,31 @@ where
             .unwrap_or_else(|err| error!("Could not write config: {}", err));
     }
 
-    /// Renames the existing file to `<file>-old`
-    fn rename_file_old(&self) {
-        warn!("Renaming {} to {}-old", self.file_name(), self.file_name());
-        let mut cfg_old = self.file_path().to_string_lossy().to_string();
-        cfg_old.push_str("-old");
-        std::fs::rename(self.file_path(), cfg_old).unwrap_or_else(|err| {
-            error!(
-                "Could not rename. Please remove {} then restart service: Error {}",
-                self.file_name(),
-                err
-            );
-        });
+    /// Copy the config file to `<file>.<tag>.bak`, or `<file>.<tag>.<n>.bak`
+    /// if that exists so that no earlier backup is replaced. Returns the path
+    /// of the backup.
+    fn backup(&self, tag: &str) -> Option<PathBuf> {
+        let path = self.file_path();
+        let backup = (0..)
+            .map(|n| {
+                let name = if n == 0 {
+                    format!("{}.{tag}.bak", self.file_name())
+                } else {
+                    format!("{}.{tag}.{n}.bak", self.file_name())
+                };
+                path.with_file_name(name)
+            })
+            .find(|backup| !backup.exists())?;
+        match fs::copy(&path, &backup) {
+            Ok(_) => {
+                warn!("Backed up {path:?} to {backup:?}");
+                Some(backup)
+            }
+            Err(e) => {
+                error!("Could not back up {path:?} to {backup:?}: {e}");
+                None
+            }
+        }
     }
 }
 
@@ -199,8 +217,8 @@ macro_rules! std_config_load {
         /// impl StdConfigLoad2<FanCurveConfigOld, FanCurveConfigOlder> for FanCurveConfig {}
         /// ```
         ///
-        /// If all of the generics fails to parse, then the old config is renamed and a
-        /// new one created
+        /// If all of the generics fails to parse, then the old config is backed up and
+        /// a new one created. New configs should use [`StdConfigVersioned`] instead.
         pub trait $trait_name<$($generic),*>
         where
             Self: $crate::StdConfig + DeserializeOwned + Serialize,
@@ -218,7 +236,7 @@ macro_rules! std_config_load {
                             self = data.into();
                             log::info!("New version failed, trying previous: Parsed RON for {:?}", std::any::type_name::<$generic>());
                         })* else {
-                            self.rename_file_old();
+                            self.backup("invalid");
                             self = Self::new();
                         }
                     } else {
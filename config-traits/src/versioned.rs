@@ -0,0 +1,578 @@
+//! Versioned config files. Each config carries a `version` field, older files
+//! are backed up and then upgraded by ordered [`Migration`] steps, and files
+//! with unknown or invalid fields are reported on and salvaged rather than
+//! replaced by defaults.
+
+use std::{fmt, fs};
+
+use log::{info, warn};
+use ron::Value;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::StdConfig;
+
+/// The `version` field of a config. It is always written as `N`, the version
+/// of the layout the config type describes, and any number is accepted when
+/// reading as [`StdConfigVersioned::load`] checks the version of the file
+/// before it is parsed. Files from before versioning have no field and are
+/// version `0`, so the field should be `#[serde(default)]`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigVersion<const N: u32>;
+
+impl<const N: u32> Serialize for ConfigVersion<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(N)
+    }
+}
+
+impl<'de, const N: u32> Deserialize<'de> for ConfigVersion<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u32::deserialize(deserializer)?;
+        Ok(Self)
+    }
+}
+
+/// One step upgrading a config file from the layout of version `from` to
+/// that of `from + 1`
+pub struct Migration {
+    pub from: u32,
+    /// Rewrite the file contents in the next layout
+    pub migrate: fn(&str) -> Result<String, ron::Error>,
+}
+
+/// What [`StdConfigVersioned::validate`] found in a config file
+#[derive(Debug)]
+pub struct ConfigReport<T> {
+    /// The config with any invalid fields left at their defaults, `None` if
+    /// the file is not a RON struct at all
+    pub config: Option<T>,
+    /// Why the file could not be read at all
+    pub error: Option<String>,
+    /// Fields that are not part of the layout, such as `screenpad.fade`
+    pub unknown: Vec<String>,
+    /// Fields that failed to parse, and the parse error
+    pub invalid: Vec<(String, String)>,
+}
+
+impl<T> ConfigReport<T> {
+    /// True if every field of the file was read
+    pub fn is_clean(&self) -> bool {
+        self.config.is_some() && self.unknown.is_empty() && self.invalid.is_empty()
+    }
+}
+
+impl<T> fmt::Display for ConfigReport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut issues = Vec::new();
+        if let Some(error) = &self.error {
+            issues.push(format!("unreadable: {error}"));
+        }
+        for field in &self.unknown {
+            issues.push(format!("unknown field `{field}`"));
+        }
+        for (field, error) in &self.invalid {
+            issues.push(format!("invalid field `{field}`: {error}"));
+        }
+        if issues.is_empty() {
+            write!(f, "ok")
+        } else {
+            write!(f, "{}", issues.join(", "))
+        }
+    }
+}
+
+/// Loading of configs with a [`ConfigVersion`] field
+///
+/// # Example
+/// ```rust
+/// use std::path::PathBuf;
+///
+/// use config_traits::{ConfigVersion, Migration, StdConfig, StdConfigVersioned};
+/// use serde::{Deserialize, Serialize};
+///
+/// const CONFIG_VERSION: u32 = 2;
+///
+/// #[derive(Deserialize, Serialize, Debug)]
+/// struct FanCurveConfig {
+///     #[serde(default)]
+///     version: ConfigVersion<CONFIG_VERSION>,
+///     enabled: bool,
+/// }
+///
+/// impl StdConfig for FanCurveConfig {
+///     fn new() -> Self {
+///         Self {
+///             version: ConfigVersion,
+///             enabled: false,
+///         }
+///     }
+///
+///     fn file_name(&self) -> std::string::String {
+///         "test_name.ron".to_owned()
+///     }
+///
+///     fn config_dir() -> PathBuf {
+///         PathBuf::from("/tmp")
+///     }
+/// }
+///
+/// impl StdConfigVersioned for FanCurveConfig {
+///     const VERSION: u32 = CONFIG_VERSION;
+///
+///     fn migrations() -> Vec<Migration> {
+///         // Version 1 called the field `active`, version 0 has no changes
+///         vec![Migration {
+///             from: 1,
+///             migrate: |data| Ok(data.replace("active:", "enabled:")),
+///         }]
+///     }
+/// }
+/// ```
+pub trait StdConfigVersioned: StdConfig {
+    /// The current layout version, the `N` of the config's [`ConfigVersion`]
+    const VERSION: u32;
+
+    /// Steps upgrading older files, applied in order of [`Migration::from`].
+    /// A version without a step is read as the next version unchanged, as is
+    /// the case when only fields with a `#[serde(default)]` were added.
+    fn migrations() -> Vec<Migration> {
+        Vec::new()
+    }
+
+    /// Parse the contents of a config file of the current version. Fields
+    /// that fail to parse are reported and take their value from `self`.
+    fn validate(&self, data: &str) -> ConfigReport<Self> {
+        let mut report = ConfigReport {
+            config: None,
+            error: None,
+            unknown: Vec::new(),
+            invalid: Vec::new(),
+        };
+        match ron::from_str::<Self>(data) {
+            Ok(config) => report.config = Some(config),
+            Err(e) => match salvage(self, data, &mut report.invalid) {
+                Ok(config) => report.config = Some(config),
+                Err(salvage_error) => {
+                    report.error = Some(format!("{}, {salvage_error}", e.code));
+                    report.invalid.clear();
+                }
+            },
+        }
+        if let Some(config) = &report.config {
+            report.unknown = unknown_fields(data, config)
+                .into_iter()
+                .filter(|field| !report.invalid.iter().any(|(invalid, _)| invalid == field))
+                .collect();
+        }
+        report
+    }
+
+    /// Read the config file, upgrading and validating it, then write it back
+    /// in the current layout. `self` is used if the file does not exist, and
+    /// provides the defaults of fields that are invalid. The file is backed
+    /// up before it is migrated or if anything in it could not be read.
+    fn load(mut self) -> Self {
+        let mut data = fs::read_to_string(self.file_path()).unwrap_or_default();
+        if data.trim().is_empty() {
+            self.write();
+            return self;
+        }
+
+        let mut backed_up = false;
+        match file_version(&data) {
+            Some(version) if version < Self::VERSION => {
+                backed_up = self.backup(&format!("v{version}")).is_some();
+                match migrate::<Self>(&data, version) {
+                    Ok(migrated) => data = migrated,
+                    Err(e) => warn!(
+                        "Migrating {} from version {version} failed, reading it as version {}: {e}",
+                        self.file_name(),
+                        Self::VERSION
+                    ),
+                }
+            }
+            Some(version) if version > Self::VERSION => {
+                warn!(
+                    "{} is version {version}, newer than the supported version {}",
+                    self.file_name(),
+                    Self::VERSION
+                );
+                backed_up = self.backup(&format!("v{version}")).is_some();
+            }
+            _ => {}
+        }
+
+        let report = self.validate(&data);
+        if !report.is_clean() {
+            warn!("Config {}: {report}", self.file_name());
+            if !backed_up {
+                self.backup("invalid");
+            }
+        }
+        if let Some(config) = report.config {
+            self = config;
+        }
+        self.write();
+        self
+    }
+}
+
+#[derive(Deserialize)]
+struct FileVersion {
+    #[serde(default)]
+    version: u32,
+}
+
+/// The `version` field of a config file, `0` if it has none
+fn file_version(data: &str) -> Option<u32> {
+    ron::from_str::<FileVersion>(data).ok().map(|f| f.version)
+}
+
+fn migrate<T: StdConfigVersioned>(data: &str, from: u32) -> Result<String, ron::Error> {
+    let mut steps = T::migrations();
+    steps.sort_by_key(|step| step.from);
+    let mut data = data.to_owned();
+    for step in steps
+        .iter()
+        .filter(|step| step.from >= from && step.from < T::VERSION)
+    {
+        info!(
+            "Migrating {} from version {} to {}",
+            std::any::type_name::<T>(),
+            step.from,
+            step.from + 1
+        );
+        data = (step.migrate)(&data)?;
+    }
+    Ok(data)
+}
+
+/// Parse each field of the file on its own on top of the fields of
+/// `defaults`, so that one bad value only loses that field
+fn salvage<T: Serialize + DeserializeOwned>(
+    defaults: &T,
+    data: &str,
+    invalid: &mut Vec<(String, String)>,
+) -> Result<T, String> {
+    let (attributes, fields) =
+        split_struct(data).ok_or_else(|| "the file is not a RON struct".to_owned())?;
+    let defaults = ron::to_string(defaults).map_err(|e| e.to_string())?;
+    let (_, mut merged) =
+        split_struct(&defaults).ok_or_else(|| "the defaults are not a RON struct".to_owned())?;
+
+    let join = |fields: &[(String, String)]| {
+        let fields: Vec<String> = fields.iter().map(|(n, v)| format!("{n}:{v}")).collect();
+        format!("{attributes}({})", fields.join(","))
+    };
+    for (name, value) in fields {
+        let mut trial = merged.clone();
+        match trial.iter_mut().find(|(n, _)| *n == name) {
+            Some(field) => field.1 = value,
+            None => trial.push((name.clone(), value)),
+        }
+        match ron::from_str::<T>(&join(&trial)) {
+            Ok(_) => merged = trial,
+            Err(e) => invalid.push((name, e.code.to_string())),
+        }
+    }
+    ron::from_str(&join(&merged)).map_err(|e| e.code.to_string())
+}
+
+/// Split the top level struct of a RON file in to its `#![enable(..)]`
+/// attributes and its fields, as `(name, value)` source text
+fn split_struct(data: &str) -> Option<(String, Vec<(String, String)>)> {
+    let data = strip_comments(data);
+    let mut attributes = String::new();
+    let mut rest = data.trim_start();
+    while let Some(attribute) = rest.strip_prefix("#!") {
+        let end = attribute.find(']')? + 1;
+        attributes.push_str("#!");
+        attributes.push_str(&attribute[..end]);
+        attributes.push('\n');
+        rest = attribute[end..].trim_start();
+    }
+    // An optional struct name
+    let body = rest
+        .trim_start_matches(|c: char| c.is_alphanumeric() || c == '_')
+        .trim_start()
+        .strip_prefix('(')?;
+
+    let mut fields = Vec::new();
+    let mut push = |field: &str| -> Option<()> {
+        let field = field.trim();
+        if !field.is_empty() {
+            let (name, value) = field.split_once(':')?;
+            fields.push((name.trim().to_owned(), value.trim().to_owned()));
+        }
+        Some(())
+    };
+    let mut depth = 0;
+    let mut start = 0;
+    let mut chars = body.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' | '\'' => skip_literal(&mut chars, c),
+            '(' | '[' | '{' => depth += 1,
+            ')' if depth == 0 => {
+                push(&body[start..i])?;
+                return Some((attributes, fields));
+            }
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                push(&body[start..i])?;
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Advance past the end of a string or char literal opened by `quote`
+fn skip_literal(chars: &mut std::str::CharIndices<'_>, quote: char) {
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            c if c == quote => return,
+            _ => {}
+        }
+    }
+}
+
+/// Remove `//` and `/* */` comments outside of string and char literals
+fn strip_comments(data: &str) -> String {
+    let mut out = String::with_capacity(data.len());
+    let mut chars = data.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                out.push(c);
+                while let Some(l) = chars.next() {
+                    out.push(l);
+                    if l == '\\' {
+                        out.extend(chars.next());
+                    } else if l == c {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => while chars.next_if(|c| *c != '\n').is_some() {},
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                out.push(' ');
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Fields of the file that are missing once it is parsed and written again,
+/// which are those the layout does not have
+fn unknown_fields<T: Serialize>(data: &str, config: &T) -> Vec<String> {
+    let mut unknown = Vec::new();
+    let file = ron::from_str::<Value>(data).ok();
+    let known = ron::to_string(config)
+        .ok()
+        .and_then(|known| ron::from_str::<Value>(&known).ok());
+    if let (Some(file), Some(known)) = (file, known) {
+        diff_fields(&file, &known, "", &mut unknown);
+    }
+    unknown
+}
+
+fn diff_fields(file: &Value, known: &Value, path: &str, unknown: &mut Vec<String>) {
+    match (file, known) {
+        (Value::Map(file), Value::Map(known)) => {
+            for (key, value) in file.iter() {
+                let Value::String(name) = key else {
+                    continue;
+                };
+                let field = if path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{path}.{name}")
+                };
+                match known.iter().find(|(k, _)| *k == key) {
+                    Some((_, known)) => diff_fields(value, known, &field, unknown),
+                    // Skipped when serializing if `None`
+                    None if matches!(value, Value::Option(None)) => {}
+                    None => unknown.push(field),
+                }
+            }
+        }
+        (Value::Seq(file), Value::Seq(known)) => {
+            for (n, (file, known)) in file.iter().zip(known).enumerate() {
+                diff_fields(file, known, &format!("{path}[{n}]"), unknown);
+            }
+        }
+        (Value::Option(Some(file)), Value::Option(Some(known))) => {
+            diff_fields(file, known, path, unknown);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{read_to_string, remove_file, write};
+    use std::path::PathBuf;
+
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Deserialize, Serialize, Debug, PartialEq)]
+    enum Colour {
+        Red,
+        Green,
+    }
+
+    #[derive(Deserialize, Serialize, Debug, PartialEq)]
+    struct Test {
+        #[serde(default)]
+        version: ConfigVersion<2>,
+        name: String,
+        enabled: bool,
+        level: u8,
+        colour: Colour,
+    }
+
+    impl Test {
+        fn named(name: &str) -> Self {
+            Self {
+                version: ConfigVersion,
+                name: name.to_owned(),
+                enabled: false,
+                level: 1,
+                colour: Colour::Red,
+            }
+        }
+    }
+
+    impl StdConfig for Test {
+        fn new() -> Self {
+            Self::named("test.ron")
+        }
+
+        fn file_name(&self) -> String {
+            self.name.clone()
+        }
+
+        fn config_dir() -> PathBuf {
+            std::env::temp_dir().join(format!("config-traits-test-{}", std::process::id()))
+        }
+    }
+
+    impl StdConfigVersioned for Test {
+        const VERSION: u32 = 2;
+
+        fn migrations() -> Vec<Migration> {
+            vec![
+                Migration {
+                    from: 1,
+                    migrate: |data| Ok(data.replace("active:", "enabled:")),
+                },
+            ]
+        }
+    }
+
+    #[test]
+    fn migrate_and_back_up() {
+        let config = Test::named("migrate.ron");
+        let path = config.file_path();
+        let old = "// Written by 6.1\n(version: 1, name: \"migrate.ron\", active: true, level: 3, \
+                   colour: Green)";
+        write(&path, old).unwrap();
+
+        let config = config.load();
+        assert!(config.enabled);
+        assert_eq!(config.level, 3);
+        assert_eq!(config.colour, Colour::Green);
+        let backup = path.with_file_name("migrate.ron.v1.bak");
+        assert_eq!(read_to_string(&backup).unwrap(), old);
+        assert!(read_to_string(&path).unwrap().contains("version: 2"));
+
+        // The next migration must not replace the first backup
+        write(&path, old).unwrap();
+        config.load();
+        let second = path.with_file_name("migrate.ron.v1.1.bak");
+        assert_eq!(read_to_string(&second).unwrap(), old);
+
+        for file in [
+            path, backup, second,
+        ] {
+            remove_file(file).ok();
+        }
+    }
+
+    #[test]
+    fn unversioned_without_step() {
+        let config = Test::named("unversioned.ron");
+        let path = config.file_path();
+        write(
+            &path,
+            "(name: \"unversioned.ron\", enabled: true, level: 5, colour: Red)",
+        )
+        .unwrap();
+
+        let config = config.load();
+        assert!(config.enabled);
+        assert_eq!(config.level, 5);
+        let backup = path.with_file_name("unversioned.ron.v0.bak");
+        assert!(backup.exists());
+
+        remove_file(path).ok();
+        remove_file(backup).ok();
+    }
+
+    #[test]
+    fn report_invalid_and_unknown() {
+        let data = r#"#![enable(implicit_some)]
+(
+    version: 2,
+    name: "test.ron",
+    // A comment, with a comma
+    enabled: maybe,
+    level: 7,
+    colour: Blue,
+    brightness: (low: 1, high: "3,)"),
+)"#;
+        let report = Test::new().validate(data);
+        assert_eq!(
+            report.config,
+            Some(Test {
+                level: 7,
+                ..Test::new()
+            })
+        );
+        let invalid: Vec<&str> = report.invalid.iter().map(|(f, _)| f.as_str()).collect();
+        assert_eq!(invalid, ["enabled", "colour"]);
+        assert_eq!(report.unknown, ["brightness"]);
+        assert!(!report.is_clean());
+
+        let report = Test::new().validate("[1, 2]");
+        assert!(report.config.is_none());
+        assert!(report.error.is_some());
+    }
+
+    #[test]
+    fn read_file_version() {
+        assert_eq!(
+            file_version("(level: 1, colour: Green, version: 4, nested: (a: Some(1)))"),
+            Some(4)
+        );
+        assert_eq!(file_version("(level: 1)"), Some(0));
+        assert_eq!(file_version("not ron"), None);
+    }
+}
@@ -132,11 +132,20 @@ desktop-file-validate %{buildroot}/%{_datadir}/applications/rog-control-center.d
 %{_datadir}/icons/hicolor/scalable/status/notification-reboot.svg
 %{_docdir}/%{name}/
 %{_datadir}/asusd/
+%{_mandir}/man1/asusctl.1*
+%{_mandir}/man1/asusctl-*.1*
+%{_datadir}/bash-completion/completions/asusctl
+%{_datadir}/zsh/site-functions/_asusctl
+%{_datadir}/fish/vendor_completions.d/asusctl.fish
 
 %files rog-gui
 %{_bindir}/rog-control-center
 %{_datadir}/applications/rog-control-center.desktop
 %{_datadir}/icons/hicolor/512x512/apps/rog-control-center.png
 %{_datadir}/rog-gui
+%{_mandir}/man1/rog-control-center.1*
+%{_datadir}/bash-completion/completions/rog-control-center
+%{_datadir}/zsh/site-functions/_rog-control-center
+%{_datadir}/fish/vendor_completions.d/rog-control-center.fish
 
 %changelog
@@ -1,4 +1,7 @@
+use std::path::Path;
+
 use log::warn;
+use rog_platform::sysfs::SYSFS_ROOT_ENV;
 
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Clone)]
 pub struct DMIID {
@@ -16,7 +19,13 @@ pub struct DMIID {
 }
 
 impl DMIID {
+    /// If `SYSFS_ROOT_ENV` is set the attributes are read from
+    /// `<root>/class/dmi/id` instead of through udev
     pub fn new() -> Result<Self, String> {
+        if let Some(root) = std::env::var_os(SYSFS_ROOT_ENV).filter(|r| !r.is_empty()) {
+            return Self::from_sysfs(Path::new(&root));
+        }
+
         let mut enumerator = udev::Enumerator::new().map_err(|err| {
             warn!("{}", err);
             format!("dmi enumerator failed: {err}")
@@ -82,6 +91,34 @@ impl DMIID {
         }
         Err("dmi not found".into())
     }
+
+    /// Read the DMI attributes from a sysfs tree. The udev properties such as
+    /// `ID_MODEL` are not available this way and are taken from the matching
+    /// attributes instead.
+    pub fn from_sysfs(root: &Path) -> Result<Self, String> {
+        let path = root.join("class/dmi/id");
+        if !path.is_dir() {
+            return Err(format!("dmi not found in {path:?}"));
+        }
+        let attr = |name: &str| {
+            std::fs::read_to_string(path.join(name))
+                .map(|s| s.trim_end().to_string())
+                .unwrap_or("Unknown".to_string())
+        };
+        Ok(Self {
+            id_model: attr("product_name"),
+            dmi_family: attr("product_family"),
+            dmi_vendor: attr("sys_vendor"),
+            board_name: attr("board_name"),
+            board_vendor: attr("board_vendor"),
+            bios_date: attr("bios_date"),
+            bios_release: attr("bios_release"),
+            bios_vendor: attr("bios_vendor"),
+            bios_version: attr("bios_version"),
+            product_family: attr("product_family"),
+            product_name: attr("product_name"),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -1,17 +1,21 @@
+use std::cell::RefCell;
 use std::convert::TryFrom;
 use std::str::FromStr;
 use std::thread::sleep;
 use std::time::{Duration, Instant};
 
 use dmi_id::DMIID;
-use log::info;
+use log::{error, info};
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "dbus")]
 use zbus::zvariant::{OwnedValue, Type, Value};
 
 use crate::error::{AnimeError, Result};
 use crate::usb::{AnimAwake, AnimBooting, AnimShutdown, AnimSleeping, Brightness};
-use crate::{AnimTime, AnimeGif};
+use crate::{
+    anime_descriptors, AnimTime, AnimeDescriptor, AnimeFrame, AnimeGenerator, AnimeGif, AnimeVideo,
+    GENERATOR_LOOP_FRAMES,
+};
 
 /// The first 7 bytes of a USB packet are accounted for by `USB_PREFIX1` and
 /// `USB_PREFIX2`
@@ -34,6 +38,21 @@ pub const USB_PREFIX3: [u8; 7] = [
     0x5e, 0xc0, 0x02, 0xe7, 0x04, 0x73, 0x02,
 ];
 
+/// The prefix for the packet at `pane`. Bytes 3 and 4 are the start offset of
+/// the pane data, the same as `USB_PREFIX1`, `USB_PREFIX2`, `USB_PREFIX3`.
+const fn usb_prefix(pane: usize) -> [u8; 7] {
+    let start = (1 + pane * PANE_LEN) as u16;
+    [
+        0x5e,
+        0xc0,
+        0x02,
+        (start & 0xff) as u8,
+        (start >> 8) as u8,
+        0x73,
+        0x02,
+    ]
+}
+
 #[cfg_attr(feature = "dbus", derive(Type, Value, OwnedValue))]
 #[derive(Default, Deserialize, PartialEq, Eq, Clone, Copy, Serialize, Debug)]
 pub struct Animations {
@@ -57,67 +76,98 @@ pub struct DeviceState {
     pub brightness_on_battery: Brightness,
 }
 
+/// A handle to one of the `AnimeDescriptor` loaded from `anime_support.ron`.
+/// Serialised as the model name.
 #[cfg_attr(feature = "dbus", derive(Type), zvariant(signature = "s"))]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
-pub enum AnimeType {
-    GA401,
-    GA402,
-    GU604,
-    #[default]
-    Unsupported,
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AnimeType(usize);
+
+#[allow(non_upper_case_globals)]
+impl AnimeType {
+    // These index in to the builtin descriptors, which are always first
+    pub const GA401: AnimeType = AnimeType(0);
+    pub const GA402: AnimeType = AnimeType(1);
+    pub const GU604: AnimeType = AnimeType(2);
+    pub const Unsupported: AnimeType = AnimeType(usize::MAX);
+}
+
+impl Default for AnimeType {
+    fn default() -> Self {
+        Self::Unsupported
+    }
 }
 
 impl FromStr for AnimeType {
     type Err = AnimeError;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        Ok(match s {
-            "ga401" | "GA401" => Self::GA401,
-            "ga402" | "GA402" => Self::GA402,
-            "gu604" | "GU604" => Self::GU604,
-            _ => Self::Unsupported,
-        })
+        Ok(anime_descriptors()
+            .iter()
+            .position(|d| d.name.eq_ignore_ascii_case(s))
+            .map_or(Self::Unsupported, AnimeType))
+    }
+}
+
+impl Serialize for AnimeType {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for AnimeType {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let name = String::deserialize(deserializer)?;
+        Ok(Self::from_str(&name).unwrap_or_default())
     }
 }
 
 impl AnimeType {
     pub fn from_dmi() -> Self {
         let board_name = DMIID::new().unwrap_or_default().board_name.to_uppercase();
-        if board_name.contains("GA401I") || board_name.contains("GA401Q") {
-            AnimeType::GA401
-        } else if board_name.contains("GA402R") || board_name.contains("GA402X") {
-            AnimeType::GA402
-        } else if board_name.contains("GU604V") {
-            AnimeType::GU604
-        } else {
-            AnimeType::Unsupported
+        anime_descriptors()
+            .iter()
+            .position(|d| {
+                d.board_names
+                    .iter()
+                    .any(|b| board_name.contains(&b.to_uppercase()))
+            })
+            .map_or(Self::Unsupported, AnimeType)
+    }
+
+    /// The geometry of this model. `Unsupported` uses the GA402 geometry.
+    pub fn descriptor(&self) -> &'static AnimeDescriptor {
+        let descriptors = anime_descriptors();
+        descriptors
+            .get(self.0)
+            .unwrap_or(&descriptors[Self::GA402.0])
+    }
+
+    pub fn name(&self) -> &'static str {
+        if *self == Self::Unsupported {
+            return "Unsupported";
         }
+        &self.descriptor().name
     }
 
     /// The width of diagonal images
     pub fn width(&self) -> usize {
-        match self {
-            AnimeType::GU604 => 70,
-            _ => 74,
-        }
+        self.descriptor().diagonal_width
     }
 
     /// The height of diagonal images
     pub fn height(&self) -> usize {
-        match self {
-            AnimeType::GA401 => 36,
-            AnimeType::GU604 => 43,
-            _ => 39,
-        }
+        self.descriptor().diagonal_height
     }
 
     /// The length of usable data for this type
     pub fn data_length(&self) -> usize {
-        match self {
-            AnimeType::GA401 => PANE_LEN * 2,
-            AnimeType::GU604 => PANE_LEN * 3,
-            _ => PANE_LEN * 3,
-        }
+        PANE_LEN * self.descriptor().panes
     }
 }
 
@@ -165,6 +215,29 @@ impl AnimeDataBuffer {
 
         Ok(Self { data, anime })
     }
+
+    /// Linear blend from `self` to `other` where `t = 0.0` is all `self` and
+    /// `t = 1.0` is all `other`. Used to crossfade between frames.
+    ///
+    /// # Errors
+    /// Will error if the buffers are not the same length
+    #[inline]
+    pub fn blend(&self, other: &AnimeDataBuffer, t: f32) -> Result<Self> {
+        if self.data.len() != other.data.len() {
+            return Err(AnimeError::DataBufferLength);
+        }
+        let t = t.clamp(0.0, 1.0);
+        let data = self
+            .data
+            .iter()
+            .zip(other.data.iter())
+            .map(|(a, b)| (*a as f32 + (*b as f32 - *a as f32) * t).round() as u8)
+            .collect();
+        Ok(Self {
+            data,
+            anime: self.anime,
+        })
+    }
 }
 
 /// The packets to be written to USB
@@ -178,23 +251,12 @@ impl TryFrom<AnimeDataBuffer> for AnimePacketType {
             return Err(AnimeError::DataBufferLength);
         }
 
-        let mut buffers = match anime.anime {
-            AnimeType::GA401 => vec![[0; 640]; 2],
-            AnimeType::GA402 | AnimeType::GU604 | AnimeType::Unsupported => vec![[0; 640]; 3],
-        };
+        let mut buffers = vec![[0; 640]; anime.anime.descriptor().panes];
 
         for (idx, chunk) in anime.data.as_slice().chunks(PANE_LEN).enumerate() {
+            buffers[idx][..BLOCK_START].copy_from_slice(&usb_prefix(idx));
             buffers[idx][BLOCK_START..BLOCK_END].copy_from_slice(chunk);
         }
-        buffers[0][..7].copy_from_slice(&USB_PREFIX1);
-        buffers[1][..7].copy_from_slice(&USB_PREFIX2);
-
-        if matches!(
-            anime.anime,
-            AnimeType::GA402 | AnimeType::GU604 | AnimeType::Unsupported
-        ) {
-            buffers[2][..7].copy_from_slice(&USB_PREFIX3);
-        }
         Ok(buffers)
     }
 }
@@ -205,19 +267,97 @@ impl TryFrom<AnimeDataBuffer> for AnimePacketType {
 /// If `callback` is `Ok(true)` then `run_animation` will exit the animation
 /// loop early.
 pub fn run_animation(frames: &AnimeGif, callback: &dyn Fn(AnimeDataBuffer) -> Result<bool>) {
+    run_frames(
+        frames.duration(),
+        || frames.total_frame_time(),
+        || frames.frames().iter().cloned(),
+        callback,
+    );
+}
+
+/// This runs a video as a blocking loop by using the `callback` to write data.
+/// Frames are decoded as they are played so only one is held in memory at a
+/// time.
+///
+/// If `callback` is `Ok(true)` then `run_video` will exit the animation loop
+/// early.
+pub fn run_video(video: &AnimeVideo, callback: &dyn Fn(AnimeDataBuffer) -> Result<bool>) {
+    run_frames(
+        video.duration(),
+        || video.total_frame_time(),
+        || {
+            video
+                .frames()
+                .map_err(|e| error!("rog-anime: could not open video: {e}"))
+                .into_iter()
+                .flatten()
+                .map_while(|frame| {
+                    frame
+                        .map_err(|e| error!("rog-anime: could not decode video frame: {e}"))
+                        .ok()
+                })
+        },
+        callback,
+    );
+}
+
+/// This runs a generator as a blocking loop by using the `callback` to write
+/// data. The generator state carries on between loops.
+///
+/// If `callback` is `Ok(true)` then `run_generator` will exit the animation
+/// loop early.
+pub fn run_generator(
+    generator: &AnimeGenerator,
+    callback: &dyn Fn(AnimeDataBuffer) -> Result<bool>,
+) {
+    let frames = match generator.frames() {
+        Ok(frames) => RefCell::new(frames),
+        Err(e) => {
+            error!("rog-anime: could not start generator: {e}");
+            return;
+        }
+    };
+    run_frames(
+        generator.duration(),
+        || generator.total_frame_time(),
+        || {
+            let mut frames = frames.borrow_mut();
+            (0..GENERATOR_LOOP_FRAMES).map_while(move |_| {
+                frames.next().and_then(|frame| {
+                    frame
+                        .map_err(|e| error!("rog-anime: could not generate frame: {e}"))
+                        .ok()
+                })
+            })
+        },
+        callback,
+    );
+}
+
+/// The shared frame loop for `run_animation`, `run_video`, and
+/// `run_generator`. `frames` is
+/// called once per loop of the animation, and `total_frame_time` only if the
+/// length of a single loop is needed.
+fn run_frames<I: Iterator<Item = AnimeFrame>>(
+    duration: AnimTime,
+    total_frame_time: impl Fn() -> Duration,
+    frames: impl Fn() -> I,
+    callback: &dyn Fn(AnimeDataBuffer) -> Result<bool>,
+) {
     let mut count = 0;
     let start = Instant::now();
 
     let mut timed = false;
-    let mut run_time = frames.total_frame_time();
-    if let AnimTime::Fade(time) = frames.duration() {
-        if let Some(middle) = time.show_for() {
-            run_time = middle + time.total_fade_time();
-        }
+    let mut run_time = Duration::ZERO;
+    if let AnimTime::Fade(time) = duration {
+        run_time = match time.show_for() {
+            Some(middle) => middle + time.total_fade_time(),
+            None => total_frame_time(),
+        };
         // add a small buffer
         run_time += Duration::from_millis(250);
         timed = true;
-    } else if let AnimTime::Time(time) = frames.duration() {
+    } else if let AnimTime::Time(time) = duration {
         run_time = time;
         timed = true;
     }
@@ -229,7 +369,7 @@ pub fn run_animation(frames: &AnimeGif, callback: &dyn Fn(AnimeDataBuffer) -> Re
     let mut fade_in_accum = 0.0;
     let mut fade_out_step = 0.0;
     let mut fade_out_accum;
-    if let AnimTime::Fade(time) = frames.duration() {
+    if let AnimTime::Fade(time) = duration {
         fade_in = time.fade_in();
         fade_out = time.fade_out();
         fade_in_step = 1.0 / fade_in.as_secs_f32();
@@ -245,12 +385,15 @@ pub fn run_animation(frames: &AnimeGif, callback: &dyn Fn(AnimeDataBuffer) -> Re
         }
     }
 
+    let mut next_frame = Instant::now();
     'animation: loop {
-        for frame in frames.frames() {
+        let mut played = false;
+        for frame in frames() {
+            played = true;
             let frame_start = Instant::now();
             let mut output = frame.frame().clone();
 
-            if let AnimTime::Fade(_) = frames.duration() {
+            if let AnimTime::Fade(_) = duration {
                 if frame_start <= start + fade_in {
                     for pixel in output.data_mut() {
                         *pixel = (*pixel as f32 * fade_in_accum) as u8;
@@ -278,9 +421,15 @@ pub fn run_animation(frames: &AnimeGif, callback: &dyn Fn(AnimeDataBuffer) -> Re
             if timed && Instant::now().duration_since(start) > run_time {
                 break 'animation;
             }
-            sleep(frame.delay());
+            // Keep to the frame timing regardless of how long decoding and writing
+            // took, but don't rush to catch up if running behind
+            next_frame = (next_frame + frame.delay()).max(Instant::now());
+            sleep(next_frame.saturating_duration_since(Instant::now()));
+        }
+        if !played {
+            break 'animation;
         }
-        if let AnimTime::Count(times) = frames.duration() {
+        if let AnimTime::Count(times) = duration {
             count += 1;
             if count >= times {
                 break 'animation;
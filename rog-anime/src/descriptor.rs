@@ -0,0 +1,146 @@
+use std::sync::OnceLock;
+
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+
+pub const ANIME_SUPPORT_CONF: &str = "/usr/share/asusd/anime_support.ron";
+
+/// The descriptors for all models known at build time. Kept in sync with the
+/// installed `anime_support.ron` so that the `AnimeType` consts are always
+/// valid.
+const ANIME_SUPPORT_BUILTIN: &str = include_str!("../data/anime_support.ron");
+
+/// One physical row of LEDs, counted from the top of the display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AnimeRow {
+    /// The x position on a full square grid where the first LED of the row
+    /// sits. See `AnimeImage::first_x()`
+    pub first_x: u32,
+    /// How many LEDs are physically in the row
+    pub width: u32,
+    /// The width of the row data including any dead pixels
+    pub pitch: u32,
+}
+
+/// A run of a diagonal image (such as the ASUS gifs) copied in to the data
+/// buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DiagonalRow {
+    /// Start index in the data buffer
+    pub start: usize,
+    /// Start position in the diagonal image
+    pub x: usize,
+    pub y: usize,
+    /// How many pixels to copy
+    pub len: usize,
+}
+
+/// A row of LEDs as seen in the USB packets. Derived from wireshark captures
+/// and used by the simulator to draw the display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PacketRow {
+    /// The packet index byte (idx = 3), one of `0x01`, `0x74`, `0xe7`
+    pub index: u8,
+    /// Starting index in that packet
+    pub start: usize,
+    /// The length to read inclusive
+    pub len: usize,
+    /// Offset to the right by how many LEDs
+    pub offset: i32,
+}
+
+/// Everything that differs between AniMe Matrix models. Adding support for a
+/// new model should only require a new entry in `anime_support.ron`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AnimeDescriptor {
+    /// The name used for `AnimeType`, e.g `GA401`
+    pub name: String,
+    /// Partial matches against `/sys/class/dmi/id/board_name`, case
+    /// insensitive. e.g, `GA401I` matches `GA401IV`
+    pub board_names: Vec<String>,
+    /// The width of diagonal images
+    pub diagonal_width: usize,
+    /// The height of diagonal images
+    pub diagonal_height: usize,
+    /// How many USB packets a full frame is split over
+    pub panes: usize,
+    /// Count of unused bytes at the start of the data buffer
+    pub data_offset: usize,
+    /// Scale ratio in CM. See `AnimeImage::scale_x()`
+    pub scale_x: f32,
+    /// Scale ratio in CM. See `AnimeImage::scale_y()`
+    pub scale_y: f32,
+    /// Longest row LED count (physical) plus the half-pixel offset
+    pub phys_width: f32,
+    /// Longest column LED count (physical) plus any dead pixels
+    pub phys_height: f32,
+    /// The LED rows, top to bottom
+    pub rows: Vec<AnimeRow>,
+    /// How a diagonal image maps on to the data buffer
+    pub diagonal_rows: Vec<DiagonalRow>,
+    /// How the USB packets map on to the physical rows
+    pub packet_rows: Vec<PacketRow>,
+}
+
+/// Load the builtin descriptors, then replace or append with those from
+/// `/usr/share/asusd/anime_support.ron` if that file is available. The order
+/// of the builtin entries never changes as the `AnimeType` consts index in to
+/// it.
+pub fn anime_descriptors() -> &'static [AnimeDescriptor] {
+    static DESCRIPTORS: OnceLock<Vec<AnimeDescriptor>> = OnceLock::new();
+    DESCRIPTORS.get_or_init(|| {
+        let mut data: Vec<AnimeDescriptor> = ron::from_str(ANIME_SUPPORT_BUILTIN)
+            .unwrap_or_else(|e| panic!("Could not deserialise builtin anime_support.ron: {e}"));
+
+        if let Ok(file) = std::fs::read_to_string(ANIME_SUPPORT_CONF) {
+            match ron::from_str::<Vec<AnimeDescriptor>>(&file) {
+                Ok(tmp) => {
+                    for desc in tmp {
+                        if let Some(existing) = data.iter_mut().find(|d| d.name == desc.name) {
+                            *existing = desc;
+                        } else {
+                            data.push(desc);
+                        }
+                    }
+                    info!("Loaded AniMe support data from {ANIME_SUPPORT_CONF}");
+                }
+                Err(e) => error!("Could not deserialise {ANIME_SUPPORT_CONF}: {e}"),
+            }
+        } else {
+            warn!("{ANIME_SUPPORT_CONF} not found, using builtin AniMe support data");
+        }
+        data
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::AnimeType;
+
+    #[test]
+    fn builtin_consts_match_names() {
+        assert_eq!(AnimeType::GA401.name(), "GA401");
+        assert_eq!(AnimeType::GA402.name(), "GA402");
+        assert_eq!(AnimeType::GU604.name(), "GU604");
+        assert_eq!(AnimeType::Unsupported.name(), "Unsupported");
+        assert_eq!("gu604".parse::<AnimeType>().unwrap(), AnimeType::GU604);
+        assert_eq!("G000".parse::<AnimeType>().unwrap(), AnimeType::Unsupported);
+    }
+
+    #[test]
+    fn builtin_geometry_fits_buffer() {
+        for anime_type in [
+            AnimeType::GA401,
+            AnimeType::GA402,
+            AnimeType::GU604,
+        ] {
+            let desc = anime_type.descriptor();
+            let leds: u32 = desc.rows.iter().map(|r| r.pitch).sum();
+            assert!(desc.data_offset + leds as usize <= anime_type.data_length());
+            for row in &desc.diagonal_rows {
+                assert!(row.start + row.len <= anime_type.data_length());
+                assert!(row.x + row.len <= desc.diagonal_width);
+            }
+        }
+    }
+}
@@ -12,7 +12,9 @@ pub enum AnimeError {
     Io(std::io::Error),
     Png(PngError),
     Gif(DecodingError),
+    WebP(image_webp::DecodingError),
     Format,
+    VideoFormat,
     /// The input was incorrect size, expected size is `IncorrectSize(width,
     /// height)`
     IncorrectSize(u32, u32),
@@ -36,7 +38,12 @@ impl fmt::Display for AnimeError {
             AnimeError::Io(e) => write!(f, "Could not open: {}", e),
             AnimeError::Png(e) => write!(f, "PNG error: {}", e),
             AnimeError::Gif(e) => write!(f, "GIF error: {}", e),
+            AnimeError::WebP(e) => write!(f, "WebP error: {}", e),
             AnimeError::Format => write!(f, "PNG file is not 8bit greyscale"),
+            AnimeError::VideoFormat => write!(
+                f,
+                "Video must be an APNG, WebP, or a directory of PNG images"
+            ),
             AnimeError::IncorrectSize(width, height) => write!(
                 f,
                 "The input image size is incorrect, expected {}x{}",
@@ -89,6 +96,13 @@ impl From<DecodingError> for AnimeError {
     }
 }
 
+impl From<image_webp::DecodingError> for AnimeError {
+    #[inline]
+    fn from(err: image_webp::DecodingError) -> Self {
+        AnimeError::WebP(err)
+    }
+}
+
 impl From<AnimeError> for zbus::fdo::Error {
     #[inline]
     fn from(err: AnimeError) -> Self {
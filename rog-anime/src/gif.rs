@@ -20,6 +20,10 @@ pub struct AnimeFrame {
 }
 
 impl AnimeFrame {
+    pub(crate) fn new(data: AnimeDataBuffer, delay: Duration) -> Self {
+        Self { data, delay }
+    }
+
     /// Get the inner data buffer of the gif frame
     #[inline]
     pub fn frame(&self) -> &AnimeDataBuffer {
@@ -6,6 +6,7 @@ use glam::{Mat3, Vec3};
 use log::error;
 
 use crate::data::AnimeDataBuffer;
+use crate::descriptor::AnimeRow;
 use crate::error::{AnimeError, Result};
 use crate::AnimeType;
 
@@ -106,8 +107,6 @@ impl AnimeImage {
         })
     }
 
-    // TODO: Convert functions back to const after todo completed
-
     /// Scale ratio in CM
     ///
     /// This is worked out by measuring the physical width of the display from
@@ -116,13 +115,9 @@ impl AnimeImage {
     ///
     /// For GA401 this is `26.8 / (33 + 0.5) = 0.8`
     /// For GA402 this is `27.4 / (35 + 0.5) = 0.77`
-    /// For GA402 this is `30.9 / (39 + 0.5) = 0.77`
+    /// For GU604 this is `30.9 / (39 + 0.5) = 0.78`
     fn scale_x(anime_type: AnimeType) -> f32 {
-        match anime_type {
-            AnimeType::GA401 => 0.8,
-            AnimeType::GU604 => 0.78,
-            _ => 0.77,
-        }
+        anime_type.descriptor().scale_x
     }
 
     /// Scale ratio in CM
@@ -135,11 +130,7 @@ impl AnimeImage {
     /// For GA402 this is `17.3 / (61.0)       = 0.283`
     /// For GU604 this is `17.7 / (62.0 + 1)   = 0.28`
     fn scale_y(anime_type: AnimeType) -> f32 {
-        match anime_type {
-            AnimeType::GA401 => 0.3,
-            AnimeType::GU604 => 0.28,
-            _ => 0.283,
-        }
+        anime_type.descriptor().scale_y
     }
 
     /// Get the starting X position for the data we actually require when
@@ -163,31 +154,7 @@ impl AnimeImage {
     ///  first_x
     /// ```
     fn first_x(anime_type: AnimeType, y: u32) -> u32 {
-        match anime_type {
-            AnimeType::GA401 => {
-                if y < 5 {
-                    // first 5 rows for GA401 are always at X = 0
-                    return 0;
-                }
-                (y + 1) / 2 - 3
-            }
-            AnimeType::GU604 => {
-                // first 9 rows start at zero
-                if y <= 9 {
-                    return 0;
-                }
-                // and then their offset grows by one every two rows
-                (y - 9) / 2
-            }
-            _ => {
-                // first 11 rows start at zero
-                if y <= 11 {
-                    return 0;
-                }
-                // and then their offset grows by one every two rows
-                (y + 1) / 2 - 5
-            }
-        }
+        Self::row(anime_type, y).map_or(0, |r| r.first_x)
     }
 
     /// Width in LED count
@@ -205,80 +172,45 @@ impl AnimeImage {
     ///       \      |
     ///        ------+
     /// ```
-    // TODO: make this return only width, and move calcs to pitch
     fn width(anime_type: AnimeType, y: u32) -> u32 {
-        match anime_type {
-            AnimeType::GA401 => {
-                if y < 5 {
-                    // First 5 rows for GA401 are always 33 physical LEDs long
-                    return 33;
-                }
-                36 - (y + 1) / 2
-            }
-            AnimeType::GU604 => {
-                if y <= 9 {
-                    return 38 + y % 2;
-                }
-                38 - Self::first_x(anime_type, y) + y % 2
-            }
-            _ => {
-                if y <= 11 {
-                    return 34;
-                }
-                39 - y / 2
-            }
-        }
+        Self::row(anime_type, y).map_or(0, |r| r.width)
+    }
+
+    /// Layout of row `y`, `None` past the last row
+    fn row(anime_type: AnimeType, y: u32) -> Option<&'static AnimeRow> {
+        anime_type.descriptor().rows.get(y as usize)
     }
 
     /// Physical display width by count of LED
     fn phys_width(anime_type: AnimeType) -> f32 {
-        match anime_type {
-            // 33.0 = Longest row LED count (physical) plus half-pixel offset
-            AnimeType::GA401 => (33.0 + 0.5) * Self::scale_x(anime_type),
-
-            AnimeType::GU604 => (38.0 + 0.5) * Self::scale_x(anime_type),
-            _ => (35.0 + 0.5) * Self::scale_x(anime_type),
-        }
+        anime_type.descriptor().phys_width * Self::scale_x(anime_type)
     }
 
     /// Height in LED count of longest column (physical count)
     fn height(anime_type: AnimeType) -> u32 {
-        match anime_type {
-            AnimeType::GA401 => 55,
-            AnimeType::GU604 => 62,
-            _ => 61,
-        }
+        anime_type.descriptor().rows.len() as u32
     }
 
     /// Physical display height
     fn phys_height(anime_type: AnimeType) -> f32 {
-        match anime_type {
-            // 54.0 = End column LED count (physical) plus one dead pixel
-            AnimeType::GA401 => (54.0 + 1.0) * Self::scale_y(anime_type),
-            AnimeType::GU604 => 62.0 * Self::scale_y(anime_type),
-            // GA402 may not have dead pixels and require only the physical LED count
-            _ => 61.0 * Self::scale_y(anime_type),
-        }
+        anime_type.descriptor().phys_height * Self::scale_y(anime_type)
     }
 
     /// Find the actual width of the data including the dead pixels
     fn pitch(anime_type: AnimeType, y: u32) -> u32 {
-        match anime_type {
-            AnimeType::GA401 => match y {
-                0 | 2 | 4 => 33,
-                1 | 3 => 35, // Some rows are padded
-                _ => 36 - y / 2,
-            },
-            AnimeType::GU604 => AnimeImage::width(anime_type, y),
-            // GA402 does not have padding, equivalent to width
-            _ => AnimeImage::width(anime_type, y),
-        }
+        Self::row(anime_type, y).map_or(0, |r| r.pitch)
     }
 
     pub(crate) fn get_mut(&mut self) -> &mut [Pixel] {
         &mut self.img_pixels
     }
 
+    /// Replace the image being sampled. Call `update()` after this.
+    pub(crate) fn set_pixels(&mut self, pixels: Vec<Pixel>, width: u32) {
+        self.img_pixels = pixels;
+        self.width = width;
+    }
+
     /// Generate a list of LED positions. These are then used to sample the
     /// Image data, and will contain their resulting brightness.
     #[inline]
@@ -432,8 +364,27 @@ impl AnimeImage {
         let decoder = png_pong::Decoder::new(data)?.into_steps();
         let png_pong::Step { raster, delay: _ } = decoder.last().ok_or(AnimeError::NoFrames)??;
 
+        let (pixels, width) = Self::pixels_from_raster(&raster)?;
+
+        let mut matrix = AnimeImage::new(
+            Vec2::new(scale, scale),
+            angle,
+            translation,
+            bright,
+            pixels,
+            width,
+            anime_type,
+        )?;
+
+        matrix.update();
+        Ok(matrix)
+    }
+
+    /// Convert a decoded PNG (or APNG frame) to greyscale pixels, returning the
+    /// pixels and the image width
+    pub(crate) fn pixels_from_raster(raster: &png_pong::PngRaster) -> Result<(Vec<Pixel>, u32)> {
         let width;
-        let pixels = match &raster {
+        let pixels = match raster {
             png_pong::PngRaster::Gray8(ras) => {
                 width = ras.width();
                 Self::pixels_from_8bit(ras, true)
@@ -468,19 +419,7 @@ impl AnimeImage {
             }
             png_pong::PngRaster::Palette(..) => return Err(AnimeError::Format),
         };
-
-        let mut matrix = AnimeImage::new(
-            Vec2::new(scale, scale),
-            angle,
-            translation,
-            bright,
-            pixels,
-            width,
-            anime_type,
-        )?;
-
-        matrix.update();
-        Ok(matrix)
+        Ok((pixels, width))
     }
 
     fn pixels_from_8bit<P>(ras: &pix::Raster<P>, grey: bool) -> Vec<Pixel>
@@ -534,9 +473,7 @@ impl TryFrom<&AnimeImage> for AnimeDataBuffer {
             .map(|l| if let Some(l) = l { l.bright() } else { 0 })
             .collect();
         let mut v = Vec::with_capacity(leds.anime_type.data_length());
-        if leds.anime_type == AnimeType::GA401 {
-            v.push(0);
-        }
+        v.resize(leds.anime_type.descriptor().data_offset, 0);
         v.append(&mut l);
         v.append(&mut vec![0u8; leds.anime_type.data_length() - v.len()]);
         AnimeDataBuffer::from_vec(leds.anime_type, v)
@@ -640,6 +577,15 @@ mod tests {
         assert_eq!(AnimeImage::pitch(a, 14), 29);
     }
 
+    #[test]
+    fn rows_past_the_end() {
+        let a = AnimeType::GA401;
+        let y = AnimeImage::height(a);
+        assert_eq!(AnimeImage::first_x(a, y), 0);
+        assert_eq!(AnimeImage::width(a, y), 0);
+        assert_eq!(AnimeImage::pitch(a, y), 0);
+    }
+
     #[test]
     #[ignore = "Just to inspect image packet"]
     fn ga402_image_packet_check() {
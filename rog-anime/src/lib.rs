@@ -3,6 +3,10 @@
 mod data;
 pub use data::*;
 
+/// The per-model LED geometry, loaded from `anime_support.ron`
+mod descriptor;
+pub use descriptor::*;
+
 /// Useful for specialised effects that require a grid of data
 mod grid;
 pub use grid::*;
@@ -21,6 +25,14 @@ pub use diagonal::*;
 mod gif;
 pub use crate::gif::*;
 
+/// Video playback from APNG, WebP, or image sequences, decoded frame by frame
+mod video;
+pub use video::*;
+
+/// Procedural content such as the game of life, plasma, and fire
+mod generator;
+pub use generator::*;
+
 /// A container of images/grids/gifs/pauses which can be iterated over to
 /// generate cool effects
 mod sequencer;
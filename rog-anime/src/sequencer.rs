@@ -6,7 +6,10 @@ use glam::Vec2;
 use serde::{Deserialize, Serialize};
 
 use crate::error::Result;
-use crate::{AnimTime, AnimeDataBuffer, AnimeDiagonal, AnimeGif, AnimeImage, AnimeType};
+use crate::{
+    AnimTime, AnimeDataBuffer, AnimeDiagonal, AnimeGenerator, AnimeGif, AnimeImage, AnimeType,
+    AnimeVideo, GeneratorKind,
+};
 
 /// All the possible `AniMe` actions that can be used. This enum is intended to
 /// be a helper for loading up `ActionData`.
@@ -42,6 +45,29 @@ pub enum ActionLoader {
         time: AnimTime,
         brightness: f32,
     },
+    /// Video from an APNG, animated WebP, or a directory of PNG images. The
+    /// frames are decoded while playing. `frame_time` is used for image
+    /// sequences, or files without frame timing, and defaults to about 30fps
+    Video {
+        file: PathBuf,
+        scale: f32,
+        angle: f32,
+        translation: Vec2,
+        time: AnimTime,
+        brightness: f32,
+        #[serde(default)]
+        frame_time: Option<Duration>,
+    },
+    /// Procedurally generated frames. The same `seed` always gives the same
+    /// frames. `AnimTime::Count` counts loops of `GENERATOR_LOOP_FRAMES`
+    Generator {
+        kind: GeneratorKind,
+        seed: u64,
+        time: AnimTime,
+        brightness: f32,
+        #[serde(default)]
+        frame_time: Option<Duration>,
+    },
     /// A pause to be used between sequences
     Pause(Duration),
 }
@@ -55,6 +81,10 @@ pub enum ActionData {
     /// Basic image, can have properties changed and image updated via those
     /// properties
     Image(Box<AnimeDataBuffer>),
+    /// Video decoded frame by frame as it plays
+    Video(AnimeVideo),
+    /// Procedural frames generated as they play
+    Generator(AnimeGenerator),
     /// A pause to be used between sequences
     Pause(Duration),
     /// Placeholder
@@ -132,6 +162,26 @@ impl ActionData {
                     )?),
                 }
             }
+            ActionLoader::Video {
+                file,
+                scale,
+                angle,
+                translation,
+                time,
+                brightness,
+                frame_time,
+            } => ActionData::Video(AnimeVideo::new(
+                file, *scale, *angle, *translation, *time, *brightness, *frame_time, anime_type,
+            )?),
+            ActionLoader::Generator {
+                kind,
+                seed,
+                time,
+                brightness,
+                frame_time,
+            } => ActionData::Generator(AnimeGenerator::new(
+                *kind, *seed, *time, *brightness, *frame_time, anime_type,
+            )?),
             ActionLoader::Pause(duration) => ActionData::Pause(*duration),
         };
         Ok(a)
@@ -10,7 +10,6 @@
 
 use std::str::FromStr;
 
-use dmi_id::DMIID;
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "dbus")]
 use zbus::zvariant::{OwnedValue, Type, Value};
@@ -242,18 +241,7 @@ impl From<AnimShutdown> for i32 {
 /// The currently known USB device is `19b6`.
 #[inline]
 pub fn get_anime_type() -> AnimeType {
-    let dmi = DMIID::new().unwrap_or_default();
-    let board_name = dmi.board_name;
-
-    if board_name.contains("GA401I") || board_name.contains("GA401Q") {
-        AnimeType::GA401
-    } else if board_name.contains("GA402R") || board_name.contains("GA402X") {
-        AnimeType::GA402
-    } else if board_name.contains("GU604V") {
-        AnimeType::GU604
-    } else {
-        AnimeType::Unsupported
-    }
+    AnimeType::from_dmi()
 }
 
 /// Get the two device initialization packets. These are required for device
@@ -320,7 +308,8 @@ pub const fn pkt_set_enable_powersave_anim(status: bool) -> [u8; PACKET_SIZE] {
     pkt
 }
 
-/// Set which animations are shown for each stage
+/// Set which animations are shown for each stage. The firmware only stores
+/// this selection, there is no known packet to upload user frames for a stage.
 #[inline]
 pub const fn pkt_set_builtin_animations(
     boot: AnimBooting,
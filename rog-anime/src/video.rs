@@ -0,0 +1,342 @@
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use glam::Vec2;
+use image_webp::WebPDecoder;
+use log::error;
+use png_pong::decode::Error as PngError;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AnimeError, Result};
+use crate::{AnimTime, AnimeDataBuffer, AnimeFrame, AnimeImage, AnimeType, Pixel};
+
+/// The frame time used for image sequences and for formats that do not carry
+/// their own timing, about 30fps
+pub const VIDEO_FRAME_TIME: Duration = Duration::from_millis(33);
+
+/// A video for playing on the `AniMe`. Supported are APNG (or plain PNG),
+/// animated WebP, and directories of PNG images played in file name order.
+///
+/// Unlike `AnimeGif` nothing is precomputed, frames are decoded from the file
+/// as they are played by `frames()` so long clips use little memory.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AnimeVideo {
+    file: PathBuf,
+    scale: f32,
+    angle: f32,
+    translation: Vec2,
+    duration: AnimTime,
+    brightness: f32,
+    /// Used if the source has no frame timing
+    frame_time: Duration,
+    anime_type: AnimeType,
+}
+
+impl AnimeVideo {
+    /// Check that `file` can be decoded and create the video. No frames are
+    /// stored.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        file: &Path,
+        scale: f32,
+        angle: f32,
+        translation: Vec2,
+        duration: AnimTime,
+        brightness: f32,
+        frame_time: Option<Duration>,
+        anime_type: AnimeType,
+    ) -> Result<Self> {
+        if !(0.0..=1.0).contains(&brightness) {
+            return Err(AnimeError::InvalidBrightness(brightness));
+        }
+        let video = Self {
+            file: file.to_path_buf(),
+            scale,
+            angle,
+            translation,
+            duration,
+            brightness,
+            frame_time: frame_time.unwrap_or(VIDEO_FRAME_TIME),
+            anime_type,
+        };
+        // Fail early on unsupported or broken files
+        video.frames()?.next().ok_or(AnimeError::NoFrames)??;
+        Ok(video)
+    }
+
+    /// Get the time/count for this video
+    #[inline]
+    pub fn duration(&self) -> AnimTime {
+        self.duration
+    }
+
+    /// Start decoding the video from the first frame
+    pub fn frames(&self) -> Result<VideoFrames> {
+        let source = if self.file.is_dir() {
+            let mut files: Vec<PathBuf> = std::fs::read_dir(&self.file)?
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| {
+                    p.extension()
+                        .is_some_and(|e| e.to_string_lossy().to_lowercase() == "png")
+                })
+                .collect();
+            files.sort();
+            VideoSource::Sequence(files.into_iter())
+        } else {
+            let ext = self
+                .file
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase());
+            let file = File::open(&self.file).map_err(|e| {
+                error!("Could not open {:?}: {e:?}", self.file);
+                e
+            })?;
+            let reader = BufReader::new(file);
+            match ext.as_deref() {
+                Some("png") | Some("apng") => {
+                    VideoSource::Png(Box::new(png_pong::Decoder::new(reader)?.into_steps()))
+                }
+                Some("webp") => {
+                    let decoder = WebPDecoder::new(reader)?;
+                    let buffer = vec![
+                        0;
+                        decoder
+                            .output_buffer_size()
+                            .ok_or(AnimeError::VideoFormat)?
+                    ];
+                    VideoSource::WebP {
+                        decoder: Box::new(decoder),
+                        buffer,
+                        done: false,
+                    }
+                }
+                _ => return Err(AnimeError::VideoFormat),
+            }
+        };
+
+        Ok(VideoFrames {
+            source,
+            image: AnimeImage::new(
+                Vec2::new(self.scale, self.scale),
+                self.angle,
+                self.translation,
+                self.brightness,
+                Vec::new(),
+                0,
+                self.anime_type,
+            )?,
+            frame_time: self.frame_time,
+        })
+    }
+
+    /// Get total video time for one run. This requires decoding the whole video
+    /// so is only done when needed.
+    pub fn total_frame_time(&self) -> Duration {
+        self.frames()
+            .map(|frames| frames.map_while(|f| f.ok()).map(|f| f.delay()).sum())
+            .unwrap_or_default()
+    }
+}
+
+type PngSteps = Box<dyn Iterator<Item = std::result::Result<png_pong::Step, PngError>>>;
+
+enum VideoSource {
+    Png(PngSteps),
+    WebP {
+        decoder: Box<WebPDecoder<BufReader<File>>>,
+        buffer: Vec<u8>,
+        done: bool,
+    },
+    Sequence(std::vec::IntoIter<PathBuf>),
+}
+
+/// Iterator over the decoded frames of an `AnimeVideo`. Each frame is
+/// resampled on to the LED grid with `AnimeImage`.
+pub struct VideoFrames {
+    source: VideoSource,
+    image: AnimeImage,
+    frame_time: Duration,
+}
+
+impl VideoFrames {
+    /// Decode the next frame as greyscale pixels, width, and the frame delay
+    fn next_pixels(&mut self) -> Option<Result<(Vec<Pixel>, u32, Duration)>> {
+        match &mut self.source {
+            VideoSource::Png(decoder) => {
+                Some(decoder.next()?.map_err(AnimeError::from).and_then(|step| {
+                    let (pixels, width) = AnimeImage::pixels_from_raster(&step.raster)?;
+                    Ok((pixels, width, Duration::from_millis(step.delay as u64)))
+                }))
+            }
+            VideoSource::WebP {
+                decoder,
+                buffer,
+                done,
+            } => {
+                if *done {
+                    return None;
+                }
+                let delay = if decoder.is_animated() {
+                    match decoder.read_frame(buffer) {
+                        Ok(delay) => Duration::from_millis(delay as u64),
+                        Err(image_webp::DecodingError::NoMoreFrames) => return None,
+                        Err(e) => return Some(Err(e.into())),
+                    }
+                } else {
+                    *done = true;
+                    if let Err(e) = decoder.read_image(buffer) {
+                        return Some(Err(e.into()));
+                    }
+                    Duration::ZERO
+                };
+                let width = decoder.dimensions().0;
+                let channels = if decoder.has_alpha() { 4 } else { 3 };
+                let pixels = buffer
+                    .chunks(channels)
+                    .map(|px| Pixel {
+                        color: (px[0] as u32 + px[1] as u32 + px[2] as u32) / 3,
+                        alpha: if channels == 4 {
+                            px[3] as f32 / 255.0
+                        } else {
+                            1.0
+                        },
+                    })
+                    .collect();
+                Some(Ok((pixels, width, delay)))
+            }
+            VideoSource::Sequence(files) => {
+                let path = files.next()?;
+                Some(
+                    std::fs::read(&path)
+                        .map_err(|e| {
+                            error!("Could not open {path:?}: {e:?}");
+                            AnimeError::from(e)
+                        })
+                        .and_then(|data| {
+                            let step = png_pong::Decoder::new(std::io::Cursor::new(data))?
+                                .into_steps()
+                                .last()
+                                .ok_or(AnimeError::NoFrames)??;
+                            let (pixels, width) = AnimeImage::pixels_from_raster(&step.raster)?;
+                            Ok((pixels, width, Duration::ZERO))
+                        }),
+                )
+            }
+        }
+    }
+}
+
+impl Iterator for VideoFrames {
+    type Item = Result<AnimeFrame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (pixels, width, delay) = match self.next_pixels()? {
+            Ok(res) => res,
+            Err(e) => return Some(Err(e)),
+        };
+        self.image.set_pixels(pixels, width);
+        self.image.update();
+        let delay = if delay.is_zero() {
+            self.frame_time
+        } else {
+            delay
+        };
+        Some(AnimeDataBuffer::try_from(&self.image).map(|data| AnimeFrame::new(data, delay)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use super::{AnimeVideo, VIDEO_FRAME_TIME};
+    use crate::error::AnimeError;
+    use crate::{AnimTime, AnimeType, Vec2};
+
+    fn video(file: &str, frame_time: Option<Duration>) -> Result<AnimeVideo, AnimeError> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests/data");
+        path.push(file);
+        AnimeVideo::new(
+            &path,
+            1.0,
+            0.0,
+            Vec2::default(),
+            AnimTime::Infinite,
+            1.0,
+            frame_time,
+            AnimeType::GA401,
+        )
+    }
+
+    /// The LED data and delay of every frame
+    fn decode(video: &AnimeVideo) -> Vec<(Vec<u8>, Duration)> {
+        video
+            .frames()
+            .unwrap()
+            .map(|f| {
+                let f = f.unwrap();
+                (f.frame().data().to_vec(), f.delay())
+            })
+            .collect()
+    }
+
+    /// The fixtures are a 16x16 white, black, then grey frame. At scale 1.0 on
+    /// the GA401 the image covers 982 LEDs.
+    fn check_frames(frames: &[(Vec<u8>, Duration)], delays: [Duration; 3]) {
+        let got: Vec<Duration> = frames.iter().map(|f| f.1).collect();
+        assert_eq!(got, delays);
+        let white = &frames[0].0;
+        assert_eq!(white.iter().filter(|v| **v == 254).count(), 982);
+        assert_eq!(white.iter().filter(|v| **v == 0).count(), white.len() - 982);
+        assert!(frames[1].0.iter().all(|v| *v == 0));
+        for (white, grey) in white.iter().zip(&frames[2].0) {
+            assert_eq!(*grey, white / 2);
+        }
+    }
+
+    #[test]
+    fn video_rejects_gif() {
+        let res = video("ga401-diagonal.gif", None);
+        assert!(matches!(res, Err(AnimeError::VideoFormat)));
+    }
+
+    #[test]
+    fn video_apng() {
+        let video = video("video/clip.png", None).unwrap();
+        let frames = decode(&video);
+        check_frames(&frames, [
+            Duration::from_millis(100),
+            Duration::from_millis(200),
+            Duration::from_millis(300),
+        ]);
+        assert_eq!(video.total_frame_time(), Duration::from_millis(600));
+    }
+
+    #[test]
+    fn video_webp() {
+        let video = video("video/clip.webp", None).unwrap();
+        let frames = decode(&video);
+        check_frames(&frames, [
+            Duration::from_millis(100),
+            Duration::from_millis(200),
+            Duration::from_millis(300),
+        ]);
+        assert_eq!(video.total_frame_time(), Duration::from_millis(600));
+    }
+
+    #[test]
+    fn video_png_sequence() {
+        let frames = decode(&video("video/sequence", None).unwrap());
+        check_frames(&frames, [VIDEO_FRAME_TIME; 3]);
+
+        // Sequences have no timing of their own
+        let time = Duration::from_millis(50);
+        let frames = decode(&video("video/sequence", Some(time)).unwrap());
+        check_frames(&frames, [time; 3]);
+    }
+}
@@ -0,0 +1,41 @@
+use std::error::Error;
+
+use rog_aura::capture::DeviceCapture;
+use rog_aura::AuraDeviceType;
+
+const USAGE: &str = "usage:
+  aura-capture import <product id or tuf> <traffic log>   print an unannotated capture
+  aura-capture check <capture.ron>...                      rebuild and compare annotated steps";
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("import") if args.len() == 3 => {
+            let device = AuraDeviceType::from(args[1].as_str());
+            if device == AuraDeviceType::Unknown {
+                return Err(format!("{} is not a known aura device", args[1]).into());
+            }
+            let log = std::fs::read_to_string(&args[2])?;
+            let capture = DeviceCapture::from_traffic(device, &args[1], &log)?;
+            println!("{}", capture.to_ron()?);
+        }
+        Some("check") if args.len() > 1 => {
+            let mut failed = false;
+            for path in &args[1..] {
+                let capture = DeviceCapture::from_ron(&std::fs::read_to_string(path)?)?;
+                let mismatches = capture.mismatches();
+                if mismatches.is_empty() {
+                    println!("{path}: {} steps ok", capture.steps.len());
+                } else {
+                    failed = true;
+                    println!("{path}:\n{}", mismatches.join("\n"));
+                }
+            }
+            if failed {
+                return Err("captures differ from the packet builders".into());
+            }
+        }
+        _ => return Err(USAGE.into()),
+    }
+    Ok(())
+}
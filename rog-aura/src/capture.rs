@@ -0,0 +1,437 @@
+//! Device traffic for checking the packet builders against what real hardware
+//! was sent.
+//!
+//! A [`DeviceCapture`] is a list of steps, each holding the packets seen for
+//! one action (such as setting a mode in the vendor software) along with the
+//! [`AuraEffect`] or [`LaptopAuraPower`] that should produce them.
+//! [`DeviceCapture::from_traffic`] turns a hex dump or `usbmon` text log into
+//! an unannotated capture, after which each step is given a note and an input
+//! by hand.
+//!
+//! Captures recorded from hardware go in `data/captures`, there are none yet.
+//! `data/snapshots` uses the same layout to store the current output of the
+//! builders for each keyboard type. These were not recorded from hardware, so
+//! they only catch unintended changes to the builders and say nothing about
+//! whether the packets are right. Both are rebuilt with
+//! [`crate::usb::effect_packets`] and [`crate::usb::power_packets`] by the
+//! tests in this module.
+//!
+//! Packets are stored as space separated hex so they can be compared by eye
+//! with a capture tool or `per_key_raw_bytes.ods`.
+
+use std::fmt::Write;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::Error;
+use crate::keyboard::{AuraLaptopUsbPackets, LaptopAuraPower};
+use crate::usb::{effect_packets, power_packets};
+use crate::{AuraDeviceType, AuraEffect, AuraModeNum, AuraZone, Colour, Direction, Speed};
+
+/// A single write to the device
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packet(pub Vec<u8>);
+
+impl Packet {
+    /// Parse hex bytes. Spaces, `:` and `,` separators and `0x` prefixes are
+    /// allowed, otherwise digits are read in pairs.
+    pub fn from_hex(s: &str) -> Result<Self, Error> {
+        let digits: String = s
+            .replace("0x", "")
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '\t' | ':' | ','))
+            .collect();
+        if digits.is_empty() {
+            return Err(Error::ParseCapture("empty packet".into()));
+        }
+        (0..digits.len())
+            .step_by(2)
+            .map(|i| {
+                digits
+                    .get(i..i + 2)
+                    .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                    .ok_or_else(|| Error::ParseCapture(format!("not hex bytes: {s}")))
+            })
+            .collect::<Result<Vec<u8>, Error>>()
+            .map(Self)
+    }
+
+    pub fn to_hex(&self) -> String {
+        let mut s = String::with_capacity(self.0.len() * 3);
+        for (i, b) in self.0.iter().enumerate() {
+            if i > 0 {
+                s.push(' ');
+            }
+            write!(s, "{b:02x}").ok();
+        }
+        s
+    }
+}
+
+impl Serialize for Packet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hex())
+    }
+}
+
+impl<'de> Deserialize<'de> for Packet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::from_hex(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// What the packets of a step were sent for
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub enum CaptureInput {
+    /// Not yet annotated, or traffic the crate has no builder for. Never
+    /// checked.
+    #[default]
+    Unannotated,
+    /// Built with [`crate::usb::effect_packets`]
+    Effect(AuraEffect),
+    /// Built with [`crate::usb::power_packets`]
+    Power(LaptopAuraPower),
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CaptureStep {
+    /// What was done on the device or in the vendor software
+    pub note: String,
+    pub input: CaptureInput,
+    pub packets: Vec<Packet>,
+}
+
+impl CaptureStep {
+    /// The packets the crate builds for this step's input, `None` if it is
+    /// unannotated
+    pub fn rebuild(&self, device: AuraDeviceType) -> Option<AuraLaptopUsbPackets> {
+        match &self.input {
+            CaptureInput::Unannotated => None,
+            CaptureInput::Effect(effect) => Some(effect_packets(device, effect)),
+            CaptureInput::Power(power) => Some(power_packets(device, power)),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DeviceCapture {
+    pub device: AuraDeviceType,
+    /// USB product ID, or `tuf` for the sysfs keyboards. Empty for builder
+    /// snapshots.
+    #[serde(default)]
+    pub product_id: String,
+    /// Laptop model the capture was taken on. Empty for builder snapshots.
+    #[serde(default)]
+    pub model: String,
+    /// How the traffic was captured, e.g. `usbmon` while using Armoury Crate
+    /// in a VM
+    pub source: String,
+    pub steps: Vec<CaptureStep>,
+}
+
+impl DeviceCapture {
+    pub fn from_ron(ron: &str) -> Result<Self, Error> {
+        Ok(ron::from_str(ron)?)
+    }
+
+    pub fn to_ron(&self) -> Result<String, Error> {
+        Ok(ron::ser::to_string_pretty(
+            self,
+            ron::ser::PrettyConfig::new().depth_limit(4),
+        )?)
+    }
+
+    /// Build a capture from a traffic log, one packet per line:
+    /// - `# text` starts a new step with `text` as the note
+    /// - `usbmon` text lines have the data after the `=` taken, submissions and
+    ///   completions without data are skipped
+    /// - anything else is read as a hex packet, see [`Packet::from_hex`]
+    ///
+    /// Steps whose first packet is a mode packet are annotated with the
+    /// decoded [`AuraEffect`], all others are left as
+    /// [`CaptureInput::Unannotated`].
+    pub fn from_traffic(
+        device: AuraDeviceType,
+        product_id: &str,
+        log: &str,
+    ) -> Result<Self, Error> {
+        let mut steps: Vec<CaptureStep> = Vec::new();
+        for (n, line) in log.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(note) = line.strip_prefix('#') {
+                steps.push(CaptureStep {
+                    note: note.trim().to_owned(),
+                    ..Default::default()
+                });
+                continue;
+            }
+            let hex = if is_usbmon(line) {
+                match line.split_once('=') {
+                    Some((_, data)) => data,
+                    None => continue,
+                }
+            } else {
+                line
+            };
+            let packet = Packet::from_hex(hex)
+                .map_err(|e| Error::ParseCapture(format!("line {}: {e}", n + 1)))?;
+            if steps.is_empty() {
+                steps.push(CaptureStep::default());
+            }
+            if let Some(step) = steps.last_mut() {
+                step.packets.push(packet);
+            }
+        }
+
+        for step in steps.iter_mut() {
+            if let Some(effect) = step
+                .packets
+                .first()
+                .and_then(|p| decode_effect(device, &p.0))
+            {
+                step.input = CaptureInput::Effect(effect);
+            }
+        }
+
+        Ok(Self {
+            device,
+            product_id: product_id.to_owned(),
+            steps,
+            ..Default::default()
+        })
+    }
+
+    /// Rebuild every annotated step and describe each one that differs from
+    /// what was captured. Empty if all match.
+    pub fn mismatches(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        for (i, step) in self.steps.iter().enumerate() {
+            let Some(built) = step.rebuild(self.device) else {
+                continue;
+            };
+            let captured: Vec<&Vec<u8>> = step.packets.iter().map(|p| &p.0).collect();
+            let built_ref: Vec<&Vec<u8>> = built.iter().collect();
+            if captured != built_ref {
+                let mut msg = format!("step {i} \"{}\":", step.note);
+                for n in 0..captured.len().max(built.len()) {
+                    let c = step.packets.get(n).map(Packet::to_hex);
+                    let b = built.get(n).map(|p| Packet(p.clone()).to_hex());
+                    if c != b {
+                        write!(
+                            msg,
+                            "\n  packet {n}\n    captured {}\n    built    {}",
+                            c.unwrap_or_else(|| "-".into()),
+                            b.unwrap_or_else(|| "-".into())
+                        )
+                        .ok();
+                    }
+                }
+                out.push(msg);
+            }
+        }
+        out
+    }
+}
+
+/// `usbmon` text lines are `<urb> <timestamp> <S|C|E> <address> ...`
+fn is_usbmon(line: &str) -> bool {
+    let mut words = line.split_whitespace();
+    matches!(words.nth(2), Some("S" | "C" | "E"))
+        && words.next().is_some_and(|addr| addr.contains(':'))
+}
+
+/// The reverse of the mode packet and TUF `kbd_rgb_mode` layouts in
+/// [`crate::usb::effect_packets`]
+fn decode_effect(device: AuraDeviceType, p: &[u8]) -> Option<AuraEffect> {
+    let speed = |b: u8| match b {
+        x if x == Speed::Low as u8 => Some(Speed::Low),
+        x if x == Speed::Med as u8 => Some(Speed::Med),
+        x if x == Speed::High as u8 => Some(Speed::High),
+        _ => None,
+    };
+    if device.is_tuf_laptop() {
+        if p.len() != 6 || p[0] != 1 {
+            return None;
+        }
+        return Some(AuraEffect {
+            mode: AuraModeNum::from(p[1]),
+            colour1: Colour::from(&[
+                p[2], p[3], p[4],
+            ]),
+            speed: speed(p[5])?,
+            ..Default::default()
+        });
+    }
+    if p.len() < 13 || p[0] != 0x5d || p[1] != 0xb3 {
+        return None;
+    }
+    Some(AuraEffect {
+        mode: AuraModeNum::from(p[3]),
+        zone: AuraZone::from(p[2] as i32),
+        colour1: Colour::from(&[
+            p[4], p[5], p[6],
+        ]),
+        colour2: Colour::from(&[
+            p[10], p[11], p[12],
+        ]),
+        speed: speed(p[7])?,
+        direction: Direction::from(p[8] as i32),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::path::{Path, PathBuf};
+
+    use super::{CaptureInput, DeviceCapture, Packet};
+    use crate::{AuraDeviceType, AuraModeNum, Colour, Direction, Speed};
+
+    /// Every file in `data/<dir>`, none if it does not exist
+    fn captures(dir: &str) -> Vec<(PathBuf, DeviceCapture)> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("data");
+        path.push(dir);
+        let Ok(entries) = std::fs::read_dir(&path) else {
+            return Vec::new();
+        };
+        let mut files: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|e| e == "ron"))
+            .collect();
+        files.sort();
+        files
+            .into_iter()
+            .map(|path| {
+                let ron = std::fs::read_to_string(&path).unwrap();
+                let capture = DeviceCapture::from_ron(&ron)
+                    .unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+                (path, capture)
+            })
+            .collect()
+    }
+
+    /// Every step of `capture` is annotated and rebuilds to the same packets
+    fn assert_matches_builders(path: &Path, capture: &DeviceCapture) {
+        let mismatches = capture.mismatches();
+        assert!(
+            mismatches.is_empty(),
+            "{}:\n{}",
+            path.display(),
+            mismatches.join("\n")
+        );
+        assert!(
+            capture
+                .steps
+                .iter()
+                .all(|s| s.input != CaptureInput::Unannotated),
+            "{} has unannotated steps",
+            path.display()
+        );
+    }
+
+    #[test]
+    fn captures_match_builders() {
+        for (path, capture) in captures("captures") {
+            assert_matches_builders(&path, &capture);
+        }
+    }
+
+    /// Regression check only, the snapshots were produced by the builders
+    /// themselves. Update the file along with an intended change to a builder.
+    #[test]
+    fn builder_snapshots_unchanged() {
+        let mut devices = HashSet::new();
+        for (path, capture) in captures("snapshots") {
+            assert_matches_builders(&path, &capture);
+            devices.insert(capture.device);
+        }
+        for device in [
+            AuraDeviceType::LaptopKeyboardPre2021,
+            AuraDeviceType::LaptopKeyboardTuf,
+            AuraDeviceType::LaptopKeyboard2021,
+            AuraDeviceType::Ally,
+        ] {
+            assert!(devices.contains(&device), "no snapshot for {device:?}");
+        }
+    }
+
+    #[test]
+    fn packet_hex() {
+        let p = Packet::from_hex("5d:b3:00 0x01,ff").unwrap();
+        assert_eq!(p.0, [0x5d, 0xb3, 0x00, 0x01, 0xff]);
+        assert_eq!(p.to_hex(), "5d b3 00 01 ff");
+        assert!(Packet::from_hex("5d b").is_err());
+        assert!(Packet::from_hex("zz").is_err());
+        assert!(Packet::from_hex("5dé").is_err());
+    }
+
+    #[test]
+    fn traffic_to_capture() {
+        let log = "\
+# Breathe, green to blue
+ffff9c0c41e0f000 3575337574 S Co:1:003:0 s 21 09 035d 0002 0011 17 = 5db30001 00ff00e1 00000000 \
+                   ff000000 00
+ffff9c0c41e0f000 3575337801 C Co:1:003:0 0 17 >
+5d b5 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00
+5db4000000000000000000000000000000
+# Sleep animation off
+5d bd 01 0a 00 00 00
+";
+        let mut capture =
+            DeviceCapture::from_traffic(AuraDeviceType::LaptopKeyboard2021, "19b6", log).unwrap();
+        assert_eq!(capture.steps.len(), 2);
+        assert_eq!(capture.steps[0].note, "Breathe, green to blue");
+        assert_eq!(capture.steps[0].packets.len(), 3);
+        let CaptureInput::Effect(effect) = &capture.steps[0].input else {
+            panic!("mode packet not decoded");
+        };
+        assert_eq!(effect.mode, AuraModeNum::Breathe);
+        assert_eq!(effect.colour1, Colour {
+            r: 0,
+            g: 0xff,
+            b: 0
+        });
+        assert_eq!(effect.colour2, Colour {
+            r: 0,
+            g: 0,
+            b: 0xff
+        });
+        assert_eq!(effect.speed, Speed::Low);
+        assert_eq!(effect.direction, Direction::Right);
+        assert_eq!(capture.steps[1].input, CaptureInput::Unannotated);
+        assert!(capture.mismatches().is_empty());
+
+        // A wrong annotation is reported
+        capture.steps[0].packets[0].0[4] = 0xff;
+        assert_eq!(capture.mismatches().len(), 1);
+
+        let ron = capture.to_ron().unwrap();
+        assert_eq!(DeviceCapture::from_ron(&ron).unwrap(), capture);
+    }
+
+    #[test]
+    fn tuf_traffic_to_capture() {
+        let capture = DeviceCapture::from_traffic(
+            AuraDeviceType::LaptopKeyboardTuf,
+            "tuf",
+            "01 00 ff 80 00 eb",
+        )
+        .unwrap();
+        let CaptureInput::Effect(effect) = &capture.steps[0].input else {
+            panic!("kbd_rgb_mode not decoded");
+        };
+        assert_eq!(effect.mode, AuraModeNum::Static);
+        assert_eq!(effect.colour1, Colour {
+            r: 0xff,
+            g: 0x80,
+            b: 0
+        });
+        assert!(capture.mismatches().is_empty());
+    }
+}
@@ -18,6 +18,8 @@ pub use builtin_modes::*;
 
 /// Helper for detecting what is available
 pub mod aura_detection;
+/// Recorded device traffic and checking it against the packet builders
+pub mod capture;
 pub mod error;
 pub mod usb;
 
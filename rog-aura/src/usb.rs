@@ -1,3 +1,6 @@
+use crate::keyboard::{AuraLaptopUsbPackets, LaptopAuraPower};
+use crate::{AuraDeviceType, AuraEffect, PowerZones, AURA_LAPTOP_LED_MSG_LEN};
+
 // Only these two packets must be 17 bytes
 pub const AURA_LAPTOP_LED_APPLY: [u8; 17] = [
     0x5d, 0xb4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
@@ -5,3 +8,47 @@ pub const AURA_LAPTOP_LED_APPLY: [u8; 17] = [
 pub const AURA_LAPTOP_LED_SET: [u8; 17] = [
     0x5d, 0xb5, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
 ];
+
+/// The writes that set `effect` as the active mode, in order. For TUF this is
+/// the single buffer written to the `kbd_rgb_mode` sysfs attribute, for all
+/// others it is the HID packets, ending with set and apply.
+pub fn effect_packets(dev_type: AuraDeviceType, effect: &AuraEffect) -> AuraLaptopUsbPackets {
+    if dev_type.is_tuf_laptop() {
+        return vec![vec![
+            1, effect.mode as u8, effect.colour1.r, effect.colour1.g, effect.colour1.b,
+            effect.speed as u8,
+        ]];
+    }
+    let bytes: [u8; AURA_LAPTOP_LED_MSG_LEN] = effect.into();
+    vec![
+        bytes.to_vec(),
+        AURA_LAPTOP_LED_SET.to_vec(),
+        // Changes won't persist unless apply is set
+        AURA_LAPTOP_LED_APPLY.to_vec(),
+    ]
+}
+
+/// The writes that set the boot/awake/sleep/shutdown states. For TUF this is
+/// the buffer written to the `kbd_rgb_state` sysfs attribute.
+pub fn power_packets(dev_type: AuraDeviceType, power: &LaptopAuraPower) -> AuraLaptopUsbPackets {
+    if dev_type.is_tuf_laptop() {
+        return vec![power.to_bytes(dev_type)];
+    }
+    if let Some(p) = power.states.first() {
+        if p.zone == PowerZones::Ally {
+            return vec![vec![
+                0x5d,
+                0xd1,
+                0x09,
+                0x01,
+                p.new_to_byte() as u8,
+                0x0,
+                0x0,
+            ]];
+        }
+    }
+    let bytes = power.to_bytes(dev_type);
+    vec![vec![
+        0x5d, 0xbd, 0x01, bytes[0], bytes[1], bytes[2], bytes[3],
+    ]]
+}
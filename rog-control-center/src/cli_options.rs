@@ -1,3 +1,4 @@
+use cli_docs::Shell;
 use gumdrop::Options;
 
 #[derive(Default, Options)]
@@ -24,4 +25,17 @@ pub struct CliStart {
                 that might match your laptop"
     )]
     pub layout_viewing: bool,
+    #[options(
+        no_short,
+        help = "show or hide the quick settings overlay, starting ROGCC if it isn't running"
+    )]
+    pub overlay: bool,
+    #[options(
+        no_short,
+        meta = "",
+        help = "print the shell completion script for <bash, zsh, fish>"
+    )]
+    pub completions: Option<Shell>,
+    #[options(no_short, help = "print the man page")]
+    pub man_page: bool,
 }
@@ -8,6 +8,10 @@ use crate::notify::EnabledNotifications;
 const CFG_DIR: &str = "rog";
 const CFG_FILE_NAME: &str = "rog-control-center.cfg";
 
+fn default_overlay_shortcut() -> String {
+    "CTRL+ALT+O".to_owned()
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
     pub run_in_background: bool,
@@ -20,6 +24,10 @@ pub struct Config {
     pub start_fullscreen: bool,
     pub fullscreen_width: u32,
     pub fullscreen_height: u32,
+    /// The preferred trigger for the quick settings overlay, the desktop may
+    /// ask to confirm or change it. Empty to not bind a shortcut.
+    #[serde(default = "default_overlay_shortcut")]
+    pub overlay_shortcut: String,
     // This field must be last
     pub notifications: EnabledNotifications,
 }
@@ -34,6 +42,7 @@ impl Default for Config {
             start_fullscreen: false,
             fullscreen_width: 1920,
             fullscreen_height: 1080,
+            overlay_shortcut: default_overlay_shortcut(),
             notifications: EnabledNotifications::default(),
             ac_command: String::new(),
             bat_command: String::new(),
@@ -92,6 +101,7 @@ impl From<Config461> for Config {
             start_fullscreen: false,
             fullscreen_width: 1920,
             fullscreen_height: 1080,
+            overlay_shortcut: default_overlay_shortcut(),
             notifications: c.enabled_notifications,
         }
     }
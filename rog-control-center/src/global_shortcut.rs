@@ -0,0 +1,136 @@
+//! Binds the overlay shortcut through the XDG desktop portal. The compositor
+//! owns the key grab, asks the user to confirm or change the trigger, and
+//! signals the app when it is pressed.
+
+use std::collections::HashMap;
+use std::future::Future;
+
+use futures_util::StreamExt;
+use log::{debug, info};
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value};
+use zbus::{proxy, Connection};
+
+pub const OVERLAY_SHORTCUT_ID: &str = "toggle-overlay";
+
+const SESSION_TOKEN: &str = "rogcc_shortcuts";
+const CREATE_SESSION_TOKEN: &str = "rogcc_create_session";
+const BIND_SHORTCUTS_TOKEN: &str = "rogcc_bind_shortcuts";
+
+#[proxy(
+    interface = "org.freedesktop.portal.GlobalShortcuts",
+    default_service = "org.freedesktop.portal.Desktop",
+    default_path = "/org/freedesktop/portal/desktop"
+)]
+trait GlobalShortcuts {
+    fn create_session(&self, options: HashMap<&str, Value<'_>>) -> zbus::Result<OwnedObjectPath>;
+
+    fn bind_shortcuts(
+        &self,
+        session_handle: &ObjectPath<'_>,
+        shortcuts: &[(&str, HashMap<&str, Value<'_>>)],
+        parent_window: &str,
+        options: HashMap<&str, Value<'_>>,
+    ) -> zbus::Result<OwnedObjectPath>;
+
+    #[zbus(signal)]
+    fn activated(
+        &self,
+        session_handle: ObjectPath<'_>,
+        shortcut_id: &str,
+        timestamp: u64,
+        options: HashMap<String, OwnedValue>,
+    ) -> zbus::Result<()>;
+}
+
+#[proxy(
+    interface = "org.freedesktop.portal.Request",
+    default_service = "org.freedesktop.portal.Desktop"
+)]
+trait Request {
+    #[zbus(signal)]
+    fn response(&self, response: u32, results: HashMap<String, OwnedValue>) -> zbus::Result<()>;
+}
+
+/// Ask the portal for the overlay shortcut with `trigger` as the preferred
+/// keys, such as `CTRL+ALT+O`, then call `on_activated` each time it is
+/// pressed. Only returns on error, such as the portal not being available.
+pub async fn bind_overlay_shortcut(
+    trigger: &str,
+    on_activated: impl Fn() + Send + 'static,
+) -> zbus::Result<()> {
+    let conn = Connection::session().await?;
+    let portal = GlobalShortcutsProxy::new(&conn).await?;
+
+    let options = HashMap::from([
+        ("handle_token", Value::from(CREATE_SESSION_TOKEN)),
+        ("session_handle_token", Value::from(SESSION_TOKEN)),
+    ]);
+    request(&conn, CREATE_SESSION_TOKEN, portal.create_session(options)).await?;
+    let session = portal_path(&conn, "session", SESSION_TOKEN)?;
+    debug!("Created global shortcuts session {session}");
+
+    let shortcut = HashMap::from([
+        (
+            "description",
+            Value::from("Show or hide the ROG quick settings overlay"),
+        ),
+        ("preferred_trigger", Value::from(trigger)),
+    ]);
+    let options = HashMap::from([("handle_token", Value::from(BIND_SHORTCUTS_TOKEN))]);
+    let mut activated = portal.receive_activated().await?;
+    request(
+        &conn,
+        BIND_SHORTCUTS_TOKEN,
+        portal.bind_shortcuts(&session, &[(OVERLAY_SHORTCUT_ID, shortcut)], "", options),
+    )
+    .await?;
+    info!("Bound the overlay shortcut, preferring {trigger}");
+
+    while let Some(signal) = activated.next().await {
+        if let Ok(args) = signal.args() {
+            if *args.shortcut_id() == OVERLAY_SHORTCUT_ID {
+                on_activated();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Portal methods answer through a `Request` object, which must be listened to
+/// before the method is called or the response can be missed
+async fn request(
+    conn: &Connection,
+    token: &str,
+    call: impl Future<Output = zbus::Result<OwnedObjectPath>>,
+) -> zbus::Result<()> {
+    let request = RequestProxy::builder(conn)
+        .path(portal_path(conn, "request", token)?)?
+        .build()
+        .await?;
+    let mut responses = request.receive_response().await?;
+    call.await?;
+    let response = responses
+        .next()
+        .await
+        .ok_or_else(|| zbus::Error::Failure("The portal request ended early".into()))?;
+    match response.args()?.response() {
+        0 => Ok(()),
+        1 => Err(zbus::Error::Failure(
+            "The portal request was cancelled".into(),
+        )),
+        _ => Err(zbus::Error::Failure("The portal request failed".into())),
+    }
+}
+
+/// The object path the portal gives a request or session, made from our unique
+/// bus name and the token we chose
+fn portal_path(conn: &Connection, kind: &str, token: &str) -> zbus::Result<OwnedObjectPath> {
+    let sender = conn
+        .unique_name()
+        .ok_or_else(|| zbus::Error::Failure("No unique name on the session bus".into()))?
+        .trim_start_matches(':')
+        .replace('.', "_");
+    Ok(OwnedObjectPath::try_from(format!(
+        "/org/freedesktop/portal/desktop/{kind}/{sender}/{token}"
+    ))?)
+}
@@ -9,6 +9,7 @@ pub use slint;
 pub mod cli_options;
 pub mod config;
 pub mod error;
+pub mod global_shortcut;
 #[cfg(feature = "mocking")]
 pub mod mocking;
 pub mod notify;
@@ -5,6 +5,7 @@ use std::sync::{Arc, Mutex};
 use std::thread::{self, sleep};
 use std::time::Duration;
 
+use cli_docs::Command;
 use config_traits::{StdConfig, StdConfigLoad1};
 use dmi_id::DMIID;
 use gumdrop::Options;
@@ -12,9 +13,11 @@ use log::{debug, info, warn, LevelFilter};
 use rog_control_center::cli_options::CliStart;
 use rog_control_center::config::Config;
 use rog_control_center::error::Result;
+use rog_control_center::global_shortcut::bind_overlay_shortcut;
 use rog_control_center::notify::start_notifications;
 use rog_control_center::slint::ComponentHandle;
 use rog_control_center::tray::init_tray;
+use rog_control_center::ui::setup_overlay::toggle_overlay;
 use rog_control_center::ui::setup_window;
 use rog_control_center::zbus_proxies::{
     AppState, ROGCCZbus, ROGCCZbusProxyBlocking, ZBUS_IFACE, ZBUS_PATH,
@@ -32,6 +35,19 @@ async fn main() -> Result<()> {
         .format_timestamp(None)
         .init();
 
+    let args: Vec<String> = args().skip(1).collect();
+
+    let cli_parsed = match CliStart::parse_args_default(&args) {
+        Ok(p) => p,
+        Err(err) => {
+            panic!("source {}", err);
+        }
+    };
+
+    if do_cli_docs(&cli_parsed) {
+        return Ok(());
+    }
+
     // If we're running under gamescope we have to set WAYLAND_DISPLAY for winit to
     // use
     if let Ok(gamescope) = env::var("GAMESCOPE_WAYLAND_DISPLAY") {
@@ -55,9 +71,14 @@ async fn main() -> Result<()> {
         let user_con = zbus::blocking::Connection::session()?;
         if let Ok(proxy) = ROGCCZbusProxyBlocking::new(&user_con) {
             if let Ok(state) = proxy.state() {
-                info!("App is already running: {state:?}, opening the window");
                 // if there is a proxy connection assume the app is already running
-                proxy.set_state(AppState::MainWindowShouldOpen)?;
+                if cli_parsed.overlay {
+                    info!("App is already running: {state:?}, toggling the overlay");
+                    proxy.toggle_overlay()?;
+                } else {
+                    info!("App is already running: {state:?}, opening the window");
+                    proxy.set_state(AppState::MainWindowShouldOpen)?;
+                }
                 std::process::exit(0);
             }
         }
@@ -104,15 +125,6 @@ async fn main() -> Result<()> {
     info!("Running on {board_name}, product: {prod_family}");
     let is_rog_ally = board_name == "RC71L" || board_name == "RC72L" || prod_family == "ROG Ally";
 
-    let args: Vec<String> = args().skip(1).collect();
-
-    let cli_parsed = match CliStart::parse_args_default(&args) {
-        Ok(p) => p,
-        Err(err) => {
-            panic!("source {}", err);
-        }
-    };
-
     if do_cli_help(&cli_parsed) {
         return Ok(());
     }
@@ -144,10 +156,22 @@ async fn main() -> Result<()> {
 
     let enable_tray_icon = config.enable_tray_icon;
     let startup_in_background = config.startup_in_background;
+    let overlay_shortcut = config.overlay_shortcut.clone();
     let config = Arc::new(Mutex::new(config));
 
     start_notifications(config.clone(), &rt)?;
 
+    if !overlay_shortcut.is_empty() {
+        rt.spawn(async move {
+            if let Err(e) = bind_overlay_shortcut(&overlay_shortcut, toggle_overlay).await {
+                warn!(
+                    "Couldn't bind the overlay shortcut: {e}. Bind `rog-control-center --overlay` \
+                     to a key in your desktop settings instead"
+                );
+            }
+        });
+    }
+
     if enable_tray_icon {
         init_tray(supported_properties, config.clone());
     }
@@ -274,11 +298,36 @@ async fn main() -> Result<()> {
         }
     });
 
+    if cli_parsed.overlay {
+        toggle_overlay();
+    }
+
     slint::run_event_loop_until_quit().unwrap();
     rt.shutdown_background();
     Ok(())
 }
 
+/// Print the completion script or man page, these are generated while
+/// packaging so must not need a session or asusd
+fn do_cli_docs(parsed: &CliStart) -> bool {
+    if parsed.completions.is_none() && !parsed.man_page {
+        return false;
+    }
+    let cli = Command::from_options::<CliStart>(
+        "rog-control-center",
+        "graphical control of ASUS ROG laptops",
+    );
+    if let Some(shell) = parsed.completions {
+        print!("{}", cli.completions(shell));
+    }
+    if parsed.man_page {
+        for (_, page) in cli.man_pages(env!("CARGO_PKG_VERSION")) {
+            print!("{page}");
+        }
+    }
+    true
+}
+
 fn do_cli_help(parsed: &CliStart) -> bool {
     if parsed.help {
         println!("{}", CliStart::usage());
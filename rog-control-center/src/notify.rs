@@ -12,7 +12,9 @@ use std::time::Duration;
 use futures_util::StreamExt;
 use log::{debug, error, info, warn};
 use notify_rust::{Hint, Notification, Timeout, Urgency};
-use rog_platform::platform::GpuMode;
+use rog_dbus::zbus_fan_curves::FanCurvesProxy;
+use rog_dbus::zbus_platform::PlatformProxy;
+use rog_platform::platform::{GpuMode, PlatformProfile};
 use rog_platform::power::AsusPower;
 use serde::{Deserialize, Serialize};
 use supergfxctl::actions::UserActionRequired as GfxUserAction;
@@ -20,6 +22,7 @@ use supergfxctl::pci_device::{GfxMode, GfxPower};
 use supergfxctl::zbus_proxy::DaemonProxy as SuperProxy;
 use tokio::runtime::Runtime;
 use tokio::task::JoinHandle;
+use zbus::fdo::ObjectManagerProxy;
 
 use crate::config::Config;
 use crate::error::Result;
@@ -32,6 +35,15 @@ pub struct EnabledNotifications {
     pub enabled: bool,
     pub receive_notify_gfx: bool,
     pub receive_notify_gfx_status: bool,
+    /// Includes the changes made with the Fn+F5 key
+    pub receive_notify_platform_profile: bool,
+    pub receive_notify_charge_limit: bool,
+    pub receive_notify_full_charge: bool,
+    pub receive_notify_fan_curves: bool,
+    pub receive_notify_pending_reboot: bool,
+    pub receive_notify_power_plugged: bool,
+    /// Aura, AniMe, Slash and SCSI devices being plugged in or removed
+    pub receive_notify_aura_hotplug: bool,
 }
 
 impl Default for EnabledNotifications {
@@ -40,10 +52,28 @@ impl Default for EnabledNotifications {
             enabled: true,
             receive_notify_gfx: true,
             receive_notify_gfx_status: true,
+            receive_notify_platform_profile: true,
+            receive_notify_charge_limit: true,
+            receive_notify_full_charge: true,
+            receive_notify_fan_curves: true,
+            receive_notify_pending_reboot: true,
+            receive_notify_power_plugged: true,
+            receive_notify_aura_hotplug: true,
         }
     }
 }
 
+/// If notifications are on and the kind picked by `kind` is too
+fn notify_enabled(
+    config: &Arc<Mutex<Config>>,
+    kind: impl Fn(&EnabledNotifications) -> bool,
+) -> bool {
+    config
+        .lock()
+        .map(|config| config.notifications.enabled && kind(&config.notifications))
+        .unwrap_or(true)
+}
+
 fn start_dpu_status_mon(config: Arc<Mutex<Config>>) {
     use supergfxctl::pci_device::Device;
     let dev = Device::find().unwrap_or_default();
@@ -255,9 +285,198 @@ pub fn start_notifications(
         Ok::<(), zbus::Error>(())
     });
 
+    start_asusd_notifications(config);
+
     Ok(vec![blocking])
 }
 
+/// Notifications driven by the signals and property changes of asusd
+fn start_asusd_notifications(config: Arc<Mutex<Config>>) {
+    let config_copy = config.clone();
+    tokio::spawn(async move {
+        let conn = zbus::Connection::system().await?;
+        let platform = PlatformProxy::new(&conn).await?;
+
+        let mut last_profile = platform.platform_profile().await.ok();
+        let mut p = platform.receive_platform_profile_changed().await;
+        let config = config_copy.clone();
+        tokio::spawn(async move {
+            info!("Started zbus signal thread: receive_platform_profile_changed");
+            while let Some(e) = p.next().await {
+                if let Ok(profile) = e.get().await {
+                    if last_profile.replace(profile) != Some(profile)
+                        && notify_enabled(&config, |n| n.receive_notify_platform_profile)
+                    {
+                        let icon = match profile {
+                            PlatformProfile::Balanced => "asus_notif_blue",
+                            PlatformProfile::Performance => "asus_notif_red",
+                            PlatformProfile::Quiet | PlatformProfile::LowPower => {
+                                "asus_notif_green"
+                            }
+                            PlatformProfile::Custom => "asus_notif_white",
+                        };
+                        show_notification(
+                            base_notification(
+                                "Platform profile changed to",
+                                &<&str>::from(profile),
+                            )
+                            .icon(icon),
+                        );
+                    }
+                }
+            }
+        });
+
+        let mut last_plugged = platform.power_plugged().await.ok();
+        let mut p = platform.receive_power_plugged_changed().await;
+        let config = config_copy.clone();
+        tokio::spawn(async move {
+            info!("Started zbus signal thread: receive_power_plugged_changed");
+            while let Some(e) = p.next().await {
+                if let Ok(plugged) = e.get().await {
+                    if last_plugged.replace(plugged) != Some(plugged)
+                        && notify_enabled(&config, |n| n.receive_notify_power_plugged)
+                    {
+                        let (source, icon) = if plugged {
+                            ("AC", "ac-adapter")
+                        } else {
+                            ("battery", "battery")
+                        };
+                        show_notification(
+                            base_notification("Power source changed to", &source).icon(icon),
+                        );
+                    }
+                }
+            }
+        });
+
+        let mut last_pending = platform.pending_reboot().await.unwrap_or_default();
+        let mut p = platform.receive_pending_reboot_changed().await;
+        let config = config_copy.clone();
+        tokio::spawn(async move {
+            info!("Started zbus signal thread: receive_pending_reboot_changed");
+            while let Some(e) = p.next().await {
+                if let Ok(pending) = e.get().await {
+                    if pending
+                        && !last_pending
+                        && notify_enabled(&config, |n| n.receive_notify_pending_reboot)
+                    {
+                        do_reboot_notification("Firmware settings changed, reboot to apply")
+                            .map_err(|e| error!("zbus signal: do_reboot_notification: {e}"))
+                            .ok();
+                    }
+                    last_pending = pending;
+                }
+            }
+        });
+
+        let mut p = platform.receive_charge_limit_reached().await?;
+        let config = config_copy.clone();
+        tokio::spawn(async move {
+            info!("Started zbus signal thread: receive_charge_limit_reached");
+            while let Some(e) = p.next().await {
+                if let Ok(out) = e.args() {
+                    if *out.one_shot() {
+                        if notify_enabled(&config, |n| n.receive_notify_full_charge) {
+                            show_notification(
+                                base_notification("One-shot full charge done, battery at", &"100%")
+                                    .icon("battery-full-charged"),
+                            );
+                        }
+                    } else if notify_enabled(&config, |n| n.receive_notify_charge_limit) {
+                        show_notification(
+                            base_notification(
+                                "Battery charged to the limit of",
+                                &format!("{}%", out.limit()),
+                            )
+                            .icon("battery-full-charged"),
+                        );
+                    }
+                }
+            }
+        });
+
+        let fans = FanCurvesProxy::new(&conn).await?;
+        let mut p = fans.receive_fan_curves_toggled().await?;
+        let config = config_copy.clone();
+        tokio::spawn(async move {
+            info!("Started zbus signal thread: receive_fan_curves_toggled");
+            while let Some(e) = p.next().await {
+                if let Ok(out) = e.args() {
+                    if notify_enabled(&config, |n| n.receive_notify_fan_curves) {
+                        let message = if *out.enabled() {
+                            "Fan curves enabled for"
+                        } else {
+                            "Fan curves disabled for"
+                        };
+                        show_notification(
+                            base_notification(message, &<&str>::from(out.profile()))
+                                .icon("asus_notif_white"),
+                        );
+                    }
+                }
+            }
+        });
+
+        let objects = ObjectManagerProxy::builder(&conn)
+            .destination(rog_dbus::DBUS_NAME)?
+            .path("/")?
+            .build()
+            .await?;
+        let mut added = objects.receive_interfaces_added().await?;
+        let config = config_copy.clone();
+        tokio::spawn(async move {
+            info!("Started zbus signal thread: receive_interfaces_added");
+            while let Some(e) = added.next().await {
+                if let Ok(out) = e.args() {
+                    for name in out.interfaces_and_properties().keys() {
+                        if let Some(device) = aura_device_name(name) {
+                            if notify_enabled(&config, |n| n.receive_notify_aura_hotplug) {
+                                show_notification(
+                                    base_notification("Device connected:", &device)
+                                        .icon("input-keyboard"),
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let mut removed = objects.receive_interfaces_removed().await?;
+        let config = config_copy;
+        tokio::spawn(async move {
+            info!("Started zbus signal thread: receive_interfaces_removed");
+            while let Some(e) = removed.next().await {
+                if let Ok(out) = e.args() {
+                    for name in out.interfaces().iter() {
+                        if let Some(device) = aura_device_name(name) {
+                            if notify_enabled(&config, |n| n.receive_notify_aura_hotplug) {
+                                show_notification(
+                                    base_notification("Device removed:", &device)
+                                        .icon("input-keyboard"),
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        Ok::<(), zbus::Error>(())
+    });
+}
+
+/// The name shown for a device that asusd adds or removes an interface of
+fn aura_device_name(iface: &str) -> Option<&'static str> {
+    match iface {
+        "xyz.ljones.Aura" => Some("Aura lighting"),
+        "xyz.ljones.Anime" => Some("AniMe Matrix"),
+        "xyz.ljones.Slash" => Some("Slash lighting"),
+        "xyz.ljones.ScsiAura" => Some("ROG Arion drive"),
+        _ => None,
+    }
+}
+
 fn convert_gfx_mode(gfx: GfxMode) -> GpuMode {
     match gfx {
         GfxMode::Hybrid => GpuMode::Optimus,
@@ -283,6 +502,13 @@ where
     notif
 }
 
+fn show_notification(notif: &mut Notification) {
+    notif
+        .show()
+        .map_err(|e| error!("Could not show notification: {e}"))
+        .ok();
+}
+
 fn do_gpu_status_notif(message: &str, data: &GfxPower) -> Notification {
     let mut notif = base_notification(message, &<&str>::from(data).to_owned());
     let icon = match data {
@@ -350,7 +576,20 @@ fn do_gfx_action_notif(message: &str, action: GfxUserAction, mode: GpuMode) -> R
 
 /// Actual `GpuMode` unused as data is never correct until switched by reboot
 fn do_mux_notification(message: &str, m: &GpuMode) -> Result<()> {
-    let mut notif = base_notification(message, &m.to_string());
+    show_reboot_notification(base_notification(message, &m.to_string()))
+}
+
+fn do_reboot_notification(message: &str) -> Result<()> {
+    let mut notif = Notification::new();
+    notif
+        .appname(NOTIF_HEADER)
+        .summary(message)
+        .hint(Hint::Category("device".into()));
+    show_reboot_notification(notif)
+}
+
+/// Show `notif` with an action that reboots through the desktop session
+fn show_reboot_notification(mut notif: Notification) -> Result<()> {
     notif
         .action("gfx-mode-session-action", "Reboot")
         .urgency(Urgency::Critical)
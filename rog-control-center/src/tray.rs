@@ -6,18 +6,31 @@ use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
 
+use futures_util::StreamExt;
 use ksni::{Handle, Icon, TrayMethods};
 use log::{info, warn};
-use rog_platform::platform::Properties;
+use rog_aura::{AuraModeNum, LedBrightness};
+use rog_dbus::zbus_anime::AnimeProxy;
+use rog_dbus::zbus_aura::AuraProxy;
+use rog_dbus::zbus_platform::PlatformProxy;
+use rog_dbus::zbus_slash::SlashProxy;
+use rog_platform::platform::{PlatformProfile, Properties};
+use rog_platform::power::AsusPower;
 use supergfxctl::pci_device::{Device, GfxMode, GfxPower};
 use supergfxctl::zbus_proxy::DaemonProxy as GfxProxy;
 use versions::Versioning;
+use zbus::proxy::PropertyStream;
+use zbus::zvariant::OwnedValue;
 
 use crate::config::Config;
-use crate::zbus_proxies::{AppState, ROGCCZbusProxyBlocking};
+use crate::zbus_proxies::{find_iface_async, AppState, ROGCCZbusProxyBlocking};
 
 const TRAY_LABEL: &str = "ROG Control Center";
 const TRAY_ICON_PATH: &str = "/usr/share/icons/hicolor/512x512/apps/";
+const CHARGE_LIMIT_PRESETS: [u8; 5] = [
+    60, 70, 80, 90, 100,
+];
+const POWER_DRAW_INTERVAL: Duration = Duration::from_secs(5);
 
 struct Icons {
     rog_blue: Icon,
@@ -55,10 +68,233 @@ fn read_icon(file: &Path) -> Icon {
     }
 }
 
+/// What the menu shows, kept up to date from the property changes and signals
+/// of asusd and supergfxd
+#[derive(Default)]
+struct TrayState {
+    profile: Option<PlatformProfile>,
+    profile_choices: Vec<PlatformProfile>,
+    /// Battery charge or discharge rate in watts
+    power_draw: Option<f32>,
+    charge_limit: Option<u8>,
+    brightness: Option<LedBrightness>,
+    brightness_choices: Vec<LedBrightness>,
+    aura_mode: Option<AuraModeNum>,
+    aura_modes: Vec<AuraModeNum>,
+    anime_display: Option<bool>,
+    slash_enabled: Option<bool>,
+    gfx_mode: Option<GfxMode>,
+    gfx_modes: Vec<GfxMode>,
+}
+
+/// The menu acts through these, each is `None` if the laptop or the installed
+/// daemons lack it
+#[derive(Default)]
+struct TrayProxies {
+    platform: Option<PlatformProxy<'static>>,
+    aura: Option<AuraProxy<'static>>,
+    anime: Option<AnimeProxy<'static>>,
+    slash: Option<SlashProxy<'static>>,
+    gfx: Option<GfxProxy<'static>>,
+}
+
 struct AsusTray {
     current_title: String,
     current_icon: Icon,
     proxy: ROGCCZbusProxyBlocking<'static>,
+    state: TrayState,
+    proxies: TrayProxies,
+    /// Menu callbacks are run by ksni, dbus calls are sent to tokio
+    rt: tokio::runtime::Handle,
+}
+
+impl AsusTray {
+    /// Run a dbus call from a menu item without blocking the menu
+    fn spawn<F, T>(&self, call: F)
+    where
+        F: std::future::Future<Output = zbus::Result<T>> + Send + 'static,
+    {
+        self.rt.spawn(async move {
+            if let Err(e) = call.await {
+                warn!("Tray: {e}");
+            }
+        });
+    }
+
+    /// A submenu holding a radio group of `choices`, the selected one being
+    /// `current`. Not shown when `current` isn't known.
+    fn radio_menu<T>(
+        label: &str,
+        current: Option<T>,
+        choices: &[T],
+        name: impl Fn(&T) -> String,
+        select: impl Fn(&mut Self, T) + Send + 'static,
+    ) -> Option<ksni::MenuItem<Self>>
+    where
+        T: Copy + PartialEq + Send + 'static,
+    {
+        use ksni::menu::*;
+        let current = current?;
+        let options = choices.to_vec();
+        Some(
+            SubMenu {
+                label: format!("{label}: {}", name(&current)),
+                submenu: vec![
+                    RadioGroup {
+                        selected: choices
+                            .iter()
+                            .position(|c| *c == current)
+                            .unwrap_or_default(),
+                        select: Box::new(move |tray: &mut Self, i| {
+                            if let Some(choice) = options.get(i) {
+                                select(tray, *choice);
+                            }
+                        }),
+                        options: choices
+                            .iter()
+                            .map(|c| RadioItem {
+                                label: name(c),
+                                ..Default::default()
+                            })
+                            .collect(),
+                    }
+                    .into(),
+                ],
+                ..Default::default()
+            }
+            .into(),
+        )
+    }
+
+    fn quick_controls(&self) -> Vec<ksni::MenuItem<Self>> {
+        use ksni::menu::*;
+        let state = &self.state;
+        let mut items = Vec::new();
+
+        items.extend(Self::radio_menu(
+            "Profile",
+            state.profile,
+            &state.profile_choices,
+            |p| <&str>::from(p).to_owned(),
+            |tray, profile| {
+                if let Some(platform) = tray.proxies.platform.clone() {
+                    tray.spawn(async move { platform.set_platform_profile(profile).await });
+                }
+            },
+        ));
+
+        if let Some(limit) = state.charge_limit {
+            let mut submenu: Vec<MenuItem<Self>> = CHARGE_LIMIT_PRESETS
+                .iter()
+                .map(|&preset| {
+                    CheckmarkItem {
+                        label: format!("{preset}%"),
+                        checked: preset == limit,
+                        activate: Box::new(move |tray: &mut Self| {
+                            if let Some(platform) = tray.proxies.platform.clone() {
+                                tray.spawn(async move {
+                                    platform.set_charge_control_end_threshold(preset).await
+                                });
+                            }
+                        }),
+                        ..Default::default()
+                    }
+                    .into()
+                })
+                .collect();
+            submenu.push(MenuItem::Separator);
+            submenu.push(
+                StandardItem {
+                    label: "One-shot full charge".into(),
+                    icon_name: "battery-full-charging".into(),
+                    activate: Box::new(|tray: &mut Self| {
+                        if let Some(platform) = tray.proxies.platform.clone() {
+                            tray.spawn(async move { platform.one_shot_full_charge().await });
+                        }
+                    }),
+                    ..Default::default()
+                }
+                .into(),
+            );
+            items.push(
+                SubMenu {
+                    label: format!("Charge limit: {limit}%"),
+                    submenu,
+                    ..Default::default()
+                }
+                .into(),
+            );
+        }
+
+        items.extend(Self::radio_menu(
+            "Keyboard brightness",
+            state.brightness,
+            &state.brightness_choices,
+            |b| format!("{b:?}"),
+            |tray, brightness| {
+                if let Some(aura) = tray.proxies.aura.clone() {
+                    tray.spawn(async move { aura.set_brightness(brightness).await });
+                }
+            },
+        ));
+
+        items.extend(Self::radio_menu(
+            "Aura mode",
+            state.aura_mode,
+            &state.aura_modes,
+            |m| <&str>::from(m).to_owned(),
+            |tray, mode| {
+                if let Some(aura) = tray.proxies.aura.clone() {
+                    tray.spawn(async move { aura.set_led_mode(mode).await });
+                }
+            },
+        ));
+
+        if let Some(enabled) = state.anime_display {
+            items.push(
+                CheckmarkItem {
+                    label: "AniMe Matrix".into(),
+                    checked: enabled,
+                    activate: Box::new(move |tray: &mut Self| {
+                        if let Some(anime) = tray.proxies.anime.clone() {
+                            tray.spawn(async move { anime.set_enable_display(!enabled).await });
+                        }
+                    }),
+                    ..Default::default()
+                }
+                .into(),
+            );
+        }
+
+        if let Some(enabled) = state.slash_enabled {
+            items.push(
+                CheckmarkItem {
+                    label: "Slash lighting".into(),
+                    checked: enabled,
+                    activate: Box::new(move |tray: &mut Self| {
+                        if let Some(slash) = tray.proxies.slash.clone() {
+                            tray.spawn(async move { slash.set_enabled(!enabled).await });
+                        }
+                    }),
+                    ..Default::default()
+                }
+                .into(),
+            );
+        }
+
+        items.extend(Self::radio_menu(
+            "GPU mode",
+            state.gfx_mode,
+            &state.gfx_modes,
+            |m| format!("{m:?}"),
+            |tray, mode| {
+                if let Some(gfx) = tray.proxies.gfx.clone() {
+                    tray.spawn(async move { gfx.set_mode(&mode).await });
+                }
+            },
+        ));
+        items
+    }
 }
 
 impl ksni::Tray for AsusTray {
@@ -78,9 +314,28 @@ impl ksni::Tray for AsusTray {
         ksni::Status::Active
     }
 
+    fn tool_tip(&self) -> ksni::ToolTip {
+        let mut description = Vec::new();
+        if let Some(profile) = self.state.profile {
+            description.push(format!("Profile: {}", <&str>::from(profile)));
+        }
+        if let Some(watts) = self.state.power_draw {
+            description.push(format!("Power draw: {watts:.1}W"));
+        }
+        ksni::ToolTip {
+            title: TRAY_LABEL.into(),
+            description: description.join("\n"),
+            ..Default::default()
+        }
+    }
+
     fn menu(&self) -> Vec<ksni::MenuItem<Self>> {
         use ksni::menu::*;
-        vec![
+        let mut menu = self.quick_controls();
+        if !menu.is_empty() {
+            menu.push(MenuItem::Separator);
+        }
+        menu.extend([
             StandardItem {
                 label: "Open ROGCC".into(),
                 icon_name: "rog-control-center".into(),
@@ -98,7 +353,140 @@ impl ksni::Tray for AsusTray {
                 ..Default::default()
             }
             .into(),
-        ]
+        ]);
+        menu
+    }
+}
+
+/// Update the tray with `apply` each time the property in `changes` changes
+fn watch_state<T>(
+    tray: &Handle<AsusTray>,
+    mut changes: PropertyStream<'static, T>,
+    apply: fn(&mut TrayState, T),
+) where
+    T: TryFrom<OwnedValue> + Send + Sync + Unpin + 'static,
+    T::Error: Into<zbus::Error>,
+{
+    let tray = tray.clone();
+    tokio::spawn(async move {
+        while let Some(change) = changes.next().await {
+            if let Ok(value) = change.get().await {
+                tray.update(|tray: &mut AsusTray| apply(&mut tray.state, value))
+                    .await;
+            }
+        }
+    });
+}
+
+/// Fill the quick controls with what asusd and supergfxd offer, then keep them
+/// up to date. `gfx` is `None` if supergfxd is missing or too old to change
+/// modes with.
+async fn setup_quick_controls(
+    tray: &Handle<AsusTray>,
+    conn: &zbus::Connection,
+    gfx: Option<GfxProxy<'static>>,
+) {
+    let mut state = TrayState::default();
+    let mut proxies = TrayProxies::default();
+
+    if let Ok(platform) = PlatformProxy::new(conn).await {
+        // Checked first as `find_iface_async` panics without asusd
+        if platform.version().await.is_ok() {
+            state.profile = platform.platform_profile().await.ok();
+            state.profile_choices = platform
+                .platform_profile_choices()
+                .await
+                .unwrap_or_default();
+            state.charge_limit = platform.charge_control_end_threshold().await.ok();
+            watch_state(
+                tray,
+                platform.receive_platform_profile_changed().await,
+                |s, v| s.profile = Some(v),
+            );
+            watch_state(
+                tray,
+                platform
+                    .receive_charge_control_end_threshold_changed()
+                    .await,
+                |s, v| s.charge_limit = Some(v),
+            );
+            proxies.platform = Some(platform);
+
+            // Only the first of each kind of device is controlled
+            let aura = find_iface_async::<AuraProxy>("xyz.ljones.Aura").await;
+            if let Some(aura) = aura.unwrap_or_default().into_iter().next() {
+                state.brightness = aura.brightness().await.ok();
+                state.brightness_choices = aura.supported_brightness().await.unwrap_or_default();
+                state.aura_mode = aura.led_mode().await.ok();
+                state.aura_modes = aura.supported_basic_modes().await.unwrap_or_default();
+                watch_state(tray, aura.receive_brightness_changed().await, |s, v| {
+                    s.brightness = Some(v)
+                });
+                watch_state(tray, aura.receive_led_mode_changed().await, |s, v| {
+                    s.aura_mode = Some(v)
+                });
+                proxies.aura = Some(aura);
+            }
+
+            let anime = find_iface_async::<AnimeProxy>("xyz.ljones.Anime").await;
+            if let Some(anime) = anime.unwrap_or_default().into_iter().next() {
+                state.anime_display = anime.enable_display().await.ok();
+                watch_state(
+                    tray,
+                    anime.receive_enable_display_changed().await,
+                    |s, v| s.anime_display = Some(v),
+                );
+                proxies.anime = Some(anime);
+            }
+
+            let slash = find_iface_async::<SlashProxy>("xyz.ljones.Slash").await;
+            if let Some(slash) = slash.unwrap_or_default().into_iter().next() {
+                state.slash_enabled = slash.enabled().await.ok();
+                watch_state(tray, slash.receive_enabled_changed().await, |s, v| {
+                    s.slash_enabled = Some(v)
+                });
+                proxies.slash = Some(slash);
+            }
+        }
+    }
+
+    if let Some(gfx) = gfx {
+        if let Ok(mode) = gfx.mode().await {
+            state.gfx_mode = Some(mode);
+            state.gfx_modes = gfx.supported().await.unwrap_or_default();
+            if let Ok(mut changes) = gfx.receive_notify_gfx().await {
+                let tray = tray.clone();
+                tokio::spawn(async move {
+                    while let Some(change) = changes.next().await {
+                        if let Ok(out) = change.args() {
+                            let mode = out.mode;
+                            tray.update(|tray: &mut AsusTray| tray.state.gfx_mode = Some(mode))
+                                .await;
+                        }
+                    }
+                });
+            }
+            proxies.gfx = Some(gfx);
+        }
+    }
+
+    tray.update(|tray: &mut AsusTray| {
+        tray.state = state;
+        tray.proxies = proxies;
+    })
+    .await;
+
+    // The battery has no change events for this
+    if let Ok(power) = AsusPower::new() {
+        let tray = tray.clone();
+        tokio::spawn(async move {
+            loop {
+                let watts = power.get_power_draw().ok();
+                tray.update(|tray: &mut AsusTray| tray.state.power_draw = watts)
+                    .await;
+                tokio::time::sleep(POWER_DRAW_INTERVAL).await;
+            }
+        });
     }
 }
 
@@ -166,6 +554,9 @@ pub fn init_tray(_supported_properties: Vec<Properties>, config: Arc<Mutex<Confi
             current_title: TRAY_LABEL.to_string(),
             current_icon: rog_red.clone(),
             proxy,
+            state: TrayState::default(),
+            proxies: TrayProxies::default(),
+            rt: tokio::runtime::Handle::current(),
         };
 
         // TODO: return an error to the UI
@@ -222,6 +613,8 @@ pub fn init_tray(_supported_properties: Vec<Properties>, config: Arc<Mutex<Confi
                 },
             }
 
+            setup_quick_controls(&tray, &conn, has_supergfx.then(|| gfx_proxy.clone())).await;
+
             info!("Started ROGTray");
             let mut last_power = GfxPower::Unknown;
             let dev = find_dgpu();
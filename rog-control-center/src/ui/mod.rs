@@ -1,19 +1,24 @@
 pub mod setup_anime;
 pub mod setup_aura;
 pub mod setup_fans;
+pub mod setup_monitor;
+pub mod setup_overlay;
 pub mod setup_system;
 
+use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 
 use config_traits::StdConfig;
 use log::warn;
 use rog_dbus::list_iface_blocking;
-use slint::{ComponentHandle, SharedString, Weak};
+use slint::{ComponentHandle, SharedString, VecModel, Weak};
 
 use crate::config::Config;
+use crate::notify::EnabledNotifications;
 use crate::ui::setup_anime::setup_anime_page;
 use crate::ui::setup_aura::setup_aura_page;
 use crate::ui::setup_fans::setup_fan_curve_page;
+use crate::ui::setup_monitor::setup_monitor_page;
 use crate::ui::setup_system::{setup_system_page, setup_system_page_callbacks};
 use crate::{AppSettingsPageData, MainWindow};
 
@@ -104,6 +109,7 @@ pub fn setup_window(config: Arc<Mutex<Config>>) -> MainWindow {
             available.contains(&"xyz.ljones.FanCurves".to_string()),
             true,
             true,
+            true,
         ]
         .into(),
     );
@@ -113,6 +119,7 @@ pub fn setup_window(config: Arc<Mutex<Config>>) -> MainWindow {
     });
 
     setup_app_settings_page(&ui, config.clone());
+    setup_monitor_page(&ui);
     if available.contains(&"xyz.ljones.Platform".to_string()) {
         setup_system_page(&ui, config.clone());
         setup_system_page_callbacks(&ui, config.clone());
@@ -161,10 +168,40 @@ pub fn setup_app_settings_page(ui: &MainWindow, config: Arc<Mutex<Config>>) {
         }
     });
 
-    if let Ok(lock) = config.try_lock() {
+    let config_copy = config.clone();
+    global.on_set_notification_kind(move |kind, enable| {
+        if let Ok(mut lock) = config_copy.try_lock() {
+            if let Some(enabled) = notification_kind(&mut lock.notifications, kind) {
+                *enabled = enable;
+                lock.write();
+            }
+        }
+    });
+
+    if let Ok(mut lock) = config.try_lock() {
         global.set_run_in_background(lock.run_in_background);
         global.set_startup_in_background(lock.startup_in_background);
         global.set_enable_tray_icon(lock.enable_tray_icon);
         global.set_enable_dgpu_notifications(lock.notifications.enabled);
+        let kinds: Vec<bool> = (0..)
+            .map_while(|kind| notification_kind(&mut lock.notifications, kind).map(|e| *e))
+            .collect();
+        global.set_notification_kinds(Rc::new(VecModel::from(kinds)).into());
     }
 }
+
+/// The setting of each kind of notification, in the order of
+/// `AppSettingsPageData.notification_kind_names`
+fn notification_kind(notifications: &mut EnabledNotifications, kind: i32) -> Option<&mut bool> {
+    Some(match kind {
+        0 => &mut notifications.receive_notify_gfx_status,
+        1 => &mut notifications.receive_notify_platform_profile,
+        2 => &mut notifications.receive_notify_charge_limit,
+        3 => &mut notifications.receive_notify_full_charge,
+        4 => &mut notifications.receive_notify_fan_curves,
+        5 => &mut notifications.receive_notify_pending_reboot,
+        6 => &mut notifications.receive_notify_power_plugged,
+        7 => &mut notifications.receive_notify_aura_hotplug,
+        _ => return None,
+    })
+}
@@ -36,7 +36,7 @@ fn decode_hex(s: &str) -> RgbaColor<u8> {
 
 /// Returns the first available Aura interface
 // TODO: return all
-async fn find_aura_iface() -> Result<AuraProxy<'static>, Box<dyn std::error::Error>> {
+pub(crate) async fn find_aura_iface() -> Result<AuraProxy<'static>, Box<dyn std::error::Error>> {
     let conn = zbus::Connection::system().await?;
     let f = zbus::fdo::ObjectManagerProxy::new(&conn, "xyz.ljones.Asusd", "/").await?;
     let interfaces = f.get_managed_objects().await?;
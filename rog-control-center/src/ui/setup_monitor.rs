@@ -0,0 +1,220 @@
+//! The monitor page samples the battery, temperatures, fan speeds, CPU
+//! frequency and platform profile once a second and plots the history of the
+//! chosen window. Everything is read from sysfs so this works without asusd.
+
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use log::info;
+use rog_platform::platform::RogPlatform;
+use rog_platform::power::AsusPower;
+use rog_platform::sensors::{SensorReadings, Sensors};
+use slint::{ComponentHandle, ModelRc, VecModel};
+
+use crate::{MainWindow, MonitorPageData, MonitorSeries, ProfileMarker};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+/// The choices of `MonitorPageData.history_names` in minutes
+const HISTORY_MINUTES: [u64; 4] = [
+    1, 5, 15, 30,
+];
+/// The viewbox of `TimeSeriesGraph`
+const VIEW_WIDTH: f32 = 1000.0;
+const VIEW_HEIGHT: f32 = 100.0;
+
+#[derive(Debug, Clone)]
+struct Sample {
+    at: Instant,
+    power_draw: Option<f32>,
+    sensors: SensorReadings,
+    profile: Option<String>,
+}
+
+struct Series {
+    value: fn(&Sample) -> Option<f32>,
+    /// The top of the plot is the next multiple of this above the largest
+    /// value
+    step: f32,
+    precision: usize,
+}
+
+/// In the order of `MonitorPageData.series_names`
+const SERIES: [Series; 6] = [
+    Series {
+        value: |s| s.power_draw,
+        step: 10.0,
+        precision: 1,
+    },
+    Series {
+        value: |s| s.sensors.cpu_temp,
+        step: 20.0,
+        precision: 0,
+    },
+    Series {
+        value: |s| s.sensors.gpu_temp,
+        step: 20.0,
+        precision: 0,
+    },
+    Series {
+        value: |s| s.sensors.cpu_fan.map(|v| v as f32),
+        step: 1000.0,
+        precision: 0,
+    },
+    Series {
+        value: |s| s.sensors.gpu_fan.map(|v| v as f32),
+        step: 1000.0,
+        precision: 0,
+    },
+    Series {
+        value: |s| s.sensors.cpu_freq.map(|v| v as f32),
+        step: 1000.0,
+        precision: 0,
+    },
+];
+
+pub fn setup_monitor_page(ui: &MainWindow) {
+    let history = Arc::new(AtomicUsize::new(
+        ui.global::<MonitorPageData>().get_history() as usize,
+    ));
+    let history_copy = history.clone();
+    ui.global::<MonitorPageData>().on_cb_history(move |index| {
+        history_copy.store(index as usize, Ordering::Relaxed);
+    });
+
+    let sensors = Sensors::new()
+        .map_err(|e| info!("Monitor: no sensors: {e}"))
+        .ok();
+    let power = AsusPower::new()
+        .map_err(|e| info!("Monitor: no battery: {e}"))
+        .ok();
+    let platform = RogPlatform::new()
+        .map_err(|e| info!("Monitor: no platform profile: {e}"))
+        .ok();
+
+    let handle = ui.as_weak();
+    tokio::spawn(async move {
+        let capacity =
+            (HISTORY_MINUTES[HISTORY_MINUTES.len() - 1] * 60 / SAMPLE_INTERVAL.as_secs()) as usize;
+        let mut samples = VecDeque::with_capacity(capacity);
+        let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+        loop {
+            interval.tick().await;
+            if samples.len() == capacity {
+                samples.pop_front();
+            }
+            samples.push_back(Sample {
+                at: Instant::now(),
+                power_draw: power.as_ref().and_then(|p| p.get_power_draw().ok()),
+                sensors: sensors.as_ref().map(|s| s.read()).unwrap_or_default(),
+                profile: platform
+                    .as_ref()
+                    .and_then(|p| p.get_platform_profile().ok()),
+            });
+
+            let minutes = HISTORY_MINUTES
+                .get(history.load(Ordering::Relaxed))
+                .copied()
+                .unwrap_or(HISTORY_MINUTES[1]);
+            let window = Duration::from_secs(minutes * 60);
+            let series = plot_series(&samples, window);
+            let markers = profile_markers(&samples, window);
+            let profile = samples
+                .back()
+                .and_then(|s| s.profile.clone())
+                .unwrap_or_default();
+
+            if handle
+                .upgrade_in_event_loop(move |handle| {
+                    let global = handle.global::<MonitorPageData>();
+                    global.set_profile(profile.into());
+                    global.set_series(ModelRc::new(VecModel::from(series)));
+                    global.set_markers(ModelRc::new(VecModel::from(markers)));
+                })
+                .is_err()
+            {
+                break;
+            }
+        }
+    });
+}
+
+/// Position of a sample from 0.0 at the start of the window to 1.0 at the
+/// newest sample
+fn position(now: Instant, sample: &Sample, window: Duration) -> f32 {
+    1.0 - now.duration_since(sample.at).as_secs_f32() / window.as_secs_f32()
+}
+
+/// Plot each series with at least one value in the window. Missing values
+/// leave a gap in the line.
+fn plot_series(samples: &VecDeque<Sample>, window: Duration) -> Vec<MonitorSeries> {
+    let Some(newest) = samples.back() else {
+        return Vec::new();
+    };
+    let now = newest.at;
+    let visible: Vec<&Sample> = samples
+        .iter()
+        .filter(|s| now.duration_since(s.at) <= window)
+        .collect();
+
+    SERIES
+        .iter()
+        .enumerate()
+        .filter_map(|(kind, series)| {
+            let max = visible
+                .iter()
+                .filter_map(|s| (series.value)(s))
+                .reduce(f32::max)?;
+            let scale = ((max / series.step).floor() + 1.0) * series.step;
+
+            let mut commands = String::new();
+            let mut pen_down = false;
+            for sample in &visible {
+                let Some(value) = (series.value)(sample) else {
+                    pen_down = false;
+                    continue;
+                };
+                let command = if pen_down { 'L' } else { 'M' };
+                write!(
+                    commands,
+                    "{command} {:.1} {:.1} ",
+                    position(now, sample, window) * VIEW_WIDTH,
+                    VIEW_HEIGHT - value.max(0.0) / scale * VIEW_HEIGHT
+                )
+                .ok();
+                pen_down = true;
+            }
+
+            let current = (series.value)(newest)
+                .map(|v| format!("{v:.precision$}", precision = series.precision))
+                .unwrap_or_else(|| "-".to_owned());
+            Some(MonitorSeries {
+                kind: kind as i32,
+                current: current.into(),
+                scale: format!("{scale:.0}").into(),
+                commands: commands.trim_end().into(),
+            })
+        })
+        .collect()
+}
+
+/// A marker at each sample in the window where the profile changed
+fn profile_markers(samples: &VecDeque<Sample>, window: Duration) -> Vec<ProfileMarker> {
+    let Some(now) = samples.back().map(|s| s.at) else {
+        return Vec::new();
+    };
+    samples
+        .iter()
+        .zip(samples.iter().skip(1))
+        .filter(|(_, s)| now.duration_since(s.at) <= window)
+        .filter_map(|(prev, s)| {
+            let profile = s.profile.as_ref()?;
+            (prev.profile.as_ref() != Some(profile)).then(|| ProfileMarker {
+                position: position(now, s, window),
+                profile: profile.into(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,403 @@
+use std::cell::RefCell;
+
+use futures_util::StreamExt;
+use log::{error, warn};
+use rog_dbus::asus_armoury::AsusArmouryProxy;
+use rog_dbus::zbus_aura::AuraProxy;
+use rog_dbus::zbus_backlight::BacklightProxy;
+use rog_dbus::zbus_platform::PlatformProxy;
+use rog_platform::asus_armoury::FirmwareAttribute;
+use rog_platform::platform::{PlatformProfile, Properties};
+use slint::{ComponentHandle, Model, ModelRc, SharedString, VecModel, Weak};
+use zbus::proxy::PropertyStream;
+use zbus::zvariant::OwnedValue;
+
+use crate::ui::setup_aura::find_aura_iface;
+use crate::zbus_proxies::find_iface_async;
+use crate::{OverlayData, OverlayItem, QuickOverlay};
+
+// The overlay rows, these need to match the order of `OverlayData.titles`
+const PROFILE: i32 = 0;
+const PPT_PL1_SPL: i32 = 1;
+const PPT_PL2_SPPT: i32 = 2;
+const PPT_FPPT: i32 = 3;
+const CHARGE_LIMIT: i32 = 4;
+const KEYBOARD_BRIGHTNESS: i32 = 5;
+const SCREEN_BRIGHTNESS: i32 = 6;
+
+thread_local! { static OVERLAY: RefCell<Option<QuickOverlay>> = Default::default() }
+
+/// Show the quick settings overlay, or hide it if it is showing. This may be
+/// called from any thread, the overlay is created on first use.
+pub fn toggle_overlay() {
+    slint::invoke_from_event_loop(|| {
+        OVERLAY.with(|overlay| {
+            let mut overlay = overlay.borrow_mut();
+            let overlay = overlay.get_or_insert_with(setup_overlay);
+            let show = !overlay.get_showing();
+            overlay.set_showing(show);
+            let result = if show { overlay.show() } else { overlay.hide() };
+            result
+                .map_err(|e| warn!("Couldn't toggle the overlay: {e:?}"))
+                .ok();
+        });
+    })
+    .map_err(|e| warn!("Couldn't toggle the overlay: {e:?}"))
+    .ok();
+}
+
+fn setup_overlay() -> QuickOverlay {
+    let overlay = QuickOverlay::new()
+        .map_err(|e| warn!("Couldn't create the overlay: {e:?}"))
+        .unwrap();
+
+    let handle = overlay.as_weak();
+    overlay.on_close_overlay(move || {
+        if let Some(overlay) = handle.upgrade() {
+            overlay.set_showing(false);
+            overlay.hide().ok();
+        }
+    });
+    let handle = overlay.as_weak();
+    overlay.window().on_close_requested(move || {
+        if let Some(overlay) = handle.upgrade() {
+            overlay.set_showing(false);
+        }
+        slint::CloseRequestResponse::HideWindow
+    });
+
+    let handle = overlay.as_weak();
+    tokio::spawn(async move {
+        let controls = match Controls::find().await {
+            Ok(controls) => controls,
+            Err(e) => {
+                error!("Overlay couldn't connect to asusd: {e}");
+                return;
+            }
+        };
+        let rows = controls.rows().await;
+        controls.watch(&handle).await;
+        handle
+            .upgrade_in_event_loop(move |overlay| {
+                let data = overlay.global::<OverlayData>();
+                data.set_items(ModelRc::new(VecModel::from(rows)));
+
+                let controls_copy = controls.clone();
+                let handle = overlay.as_weak();
+                data.on_cb_step(move |id, direction| {
+                    if let Some(overlay) = handle.upgrade() {
+                        controls_copy.step(&overlay, id, direction);
+                    }
+                });
+                let handle = overlay.as_weak();
+                data.on_cb_set(move |id, value| {
+                    if let Some(overlay) = handle.upgrade() {
+                        controls.set(&overlay, id, value);
+                    }
+                });
+            })
+            .ok();
+    });
+
+    overlay
+}
+
+/// The same proxies the system page uses, for only the rows the overlay has
+#[derive(Clone)]
+struct Controls {
+    platform: PlatformProxy<'static>,
+    backlight: BacklightProxy<'static>,
+    aura: Option<AuraProxy<'static>>,
+    ppt: Vec<(i32, AsusArmouryProxy<'static>)>,
+    profiles: Vec<PlatformProfile>,
+}
+
+impl Controls {
+    async fn find() -> zbus::Result<Self> {
+        let conn = zbus::Connection::system().await?;
+        let platform = PlatformProxy::builder(&conn).build().await?;
+        let backlight = BacklightProxy::builder(&conn).build().await?;
+        let aura = find_aura_iface().await.ok();
+
+        let mut ppt = Vec::new();
+        // The error isn't Send so mustn't be held over the awaits below
+        let attrs = find_iface_async::<AsusArmouryProxy>("xyz.ljones.AsusArmoury")
+            .await
+            .unwrap_or_default();
+        for attr in attrs {
+            let id = match attr.name().await {
+                Ok(FirmwareAttribute::PptPl1Spl) => PPT_PL1_SPL,
+                Ok(FirmwareAttribute::PptPl2Sppt) => PPT_PL2_SPPT,
+                Ok(FirmwareAttribute::PptFppt) => PPT_FPPT,
+                _ => continue,
+            };
+            ppt.push((id, attr));
+        }
+
+        let mut profiles = platform
+            .platform_profile_choices()
+            .await
+            .unwrap_or_default();
+        profiles.sort();
+        Ok(Self {
+            platform,
+            backlight,
+            aura,
+            ppt,
+            profiles,
+        })
+    }
+
+    /// The rows for what this laptop has, in the order they are shown
+    async fn rows(&self) -> Vec<OverlayItem> {
+        let mut rows = Vec::new();
+        if let Ok(profile) = self.platform.platform_profile().await {
+            rows.push(OverlayItem {
+                id: PROFILE,
+                value: profile.into(),
+                choice: true,
+                ..Default::default()
+            });
+        }
+        for (id, attr) in &self.ppt {
+            if let Some(row) = ppt_row(*id, attr).await {
+                rows.push(row);
+            }
+        }
+        let properties = self
+            .platform
+            .supported_properties()
+            .await
+            .unwrap_or_default();
+        if properties.contains(&Properties::ChargeControlEndThreshold) {
+            if let Ok(limit) = self.platform.charge_control_end_threshold().await {
+                rows.push(OverlayItem {
+                    id: CHARGE_LIMIT,
+                    value: limit.into(),
+                    minimum: 20,
+                    maximum: 100,
+                    choice: false,
+                });
+            }
+        }
+        if let Some(aura) = &self.aura {
+            if let Ok(brightness) = aura.brightness().await {
+                rows.push(OverlayItem {
+                    id: KEYBOARD_BRIGHTNESS,
+                    value: brightness.into(),
+                    minimum: 0,
+                    maximum: 3,
+                    choice: true,
+                });
+            }
+        }
+        if let Ok(brightness) = self.backlight.primary_brightness().await {
+            rows.push(OverlayItem {
+                id: SCREEN_BRIGHTNESS,
+                value: brightness,
+                // Zero turns the panel off which is no use in a quick setting
+                minimum: 5,
+                maximum: 100,
+                choice: false,
+            });
+        }
+        rows.sort_by_key(|row| row.id);
+        rows
+    }
+
+    /// Keep the rows in step with changes made elsewhere, such as the main
+    /// window, asusctl or the fan key
+    async fn watch(&self, handle: &Weak<QuickOverlay>) {
+        // The PPT limits change with the profile
+        let handle_copy = handle.clone();
+        let ppt = self.ppt.clone();
+        let mut profiles = self.platform.receive_platform_profile_changed().await;
+        tokio::spawn(async move {
+            while let Some(change) = profiles.next().await {
+                let Ok(profile) = change.get().await else {
+                    continue;
+                };
+                update_row(&handle_copy, PROFILE, move |item| {
+                    item.value = profile.into()
+                });
+                for (id, attr) in &ppt {
+                    if let Some(row) = ppt_row(*id, attr).await {
+                        update_row(&handle_copy, *id, move |item| *item = row);
+                    }
+                }
+            }
+        });
+
+        for (id, attr) in &self.ppt {
+            watch_row(
+                handle.clone(),
+                *id,
+                attr.receive_current_value_changed().await,
+                |value| value,
+            );
+        }
+        watch_row(
+            handle.clone(),
+            CHARGE_LIMIT,
+            self.platform
+                .receive_charge_control_end_threshold_changed()
+                .await,
+            i32::from,
+        );
+        if let Some(aura) = &self.aura {
+            watch_row(
+                handle.clone(),
+                KEYBOARD_BRIGHTNESS,
+                aura.receive_brightness_changed().await,
+                i32::from,
+            );
+        }
+        watch_row(
+            handle.clone(),
+            SCREEN_BRIGHTNESS,
+            self.backlight.receive_primary_brightness_changed().await,
+            |value| value,
+        );
+    }
+
+    /// Move a row one step, from the arrow keys or the row buttons
+    fn step(&self, overlay: &QuickOverlay, id: i32, direction: i32) {
+        let Some((_, item)) = find_row(overlay, id) else {
+            return;
+        };
+        let value = match id {
+            PROFILE => {
+                let Some(index) = self
+                    .profiles
+                    .iter()
+                    .position(|profile| i32::from(*profile) == item.value)
+                else {
+                    return;
+                };
+                let next = (index as i32 + direction).rem_euclid(self.profiles.len() as i32);
+                self.profiles[next as usize].into()
+            }
+            CHARGE_LIMIT | SCREEN_BRIGHTNESS => item.value + direction * 5,
+            _ => item.value + direction,
+        };
+        self.set(overlay, id, value);
+    }
+
+    /// Show the new value straight away then send it to asusd
+    fn set(&self, overlay: &QuickOverlay, id: i32, value: i32) {
+        let Some((row, mut item)) = find_row(overlay, id) else {
+            return;
+        };
+        // The profile values aren't a range, step() picks from the choices
+        let value = if id == PROFILE {
+            value
+        } else {
+            value.clamp(item.minimum, item.maximum)
+        };
+        if value == item.value {
+            return;
+        }
+        item.value = value;
+        overlay
+            .global::<OverlayData>()
+            .get_items()
+            .set_row_data(row, item);
+
+        let controls = self.clone();
+        let handle = overlay.as_weak();
+        tokio::spawn(async move {
+            let result = controls.apply(id, value).await;
+            handle
+                .upgrade_in_event_loop(move |overlay| {
+                    let data = overlay.global::<OverlayData>();
+                    let text = match result {
+                        Ok(_) => SharedString::new(),
+                        Err(e) => {
+                            let title = data.get_titles().row_data(id as usize).unwrap_or_default();
+                            warn!("Setting {title} failed: {e}");
+                            format!("Setting {title} failed").into()
+                        }
+                    };
+                    data.set_error_text(text);
+                })
+                .ok();
+        });
+    }
+
+    async fn apply(&self, id: i32, value: i32) -> zbus::Result<()> {
+        match id {
+            PROFILE => self.platform.set_platform_profile(value.into()).await,
+            CHARGE_LIMIT => {
+                self.platform
+                    .set_charge_control_end_threshold(value as u8)
+                    .await
+            }
+            KEYBOARD_BRIGHTNESS => match &self.aura {
+                Some(aura) => aura.set_brightness(value.into()).await,
+                None => Ok(()),
+            },
+            SCREEN_BRIGHTNESS => self.backlight.set_primary_brightness(value).await,
+            _ => match self.ppt.iter().find(|(ppt, _)| *ppt == id) {
+                Some((_, attr)) => attr.set_current_value(value).await,
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+async fn ppt_row(id: i32, attr: &AsusArmouryProxy<'_>) -> Option<OverlayItem> {
+    Some(OverlayItem {
+        id,
+        value: attr.current_value().await.ok()?,
+        minimum: attr.min_value().await.ok()?,
+        maximum: attr.max_value().await.ok()?,
+        choice: false,
+    })
+}
+
+fn find_row(overlay: &QuickOverlay, id: i32) -> Option<(usize, OverlayItem)> {
+    overlay
+        .global::<OverlayData>()
+        .get_items()
+        .iter()
+        .enumerate()
+        .find(|(_, item)| item.id == id)
+}
+
+fn update_row(
+    handle: &Weak<QuickOverlay>,
+    id: i32,
+    update: impl FnOnce(&mut OverlayItem) + Send + 'static,
+) {
+    handle
+        .upgrade_in_event_loop(move |overlay| {
+            if let Some((row, mut item)) = find_row(&overlay, id) {
+                update(&mut item);
+                overlay
+                    .global::<OverlayData>()
+                    .get_items()
+                    .set_row_data(row, item);
+            }
+        })
+        .ok();
+}
+
+fn watch_row<T>(
+    handle: Weak<QuickOverlay>,
+    id: i32,
+    mut changes: PropertyStream<'static, T>,
+    convert: fn(T) -> i32,
+) where
+    T: TryFrom<OwnedValue> + Send + Sync + Unpin + 'static,
+    T::Error: Into<zbus::Error>,
+{
+    // spawn required since the while let never exits
+    tokio::spawn(async move {
+        while let Some(change) = changes.next().await {
+            if let Ok(value) = change.get().await {
+                let value = convert(value);
+                update_row(&handle, id, move |item| item.value = value);
+            }
+        }
+    });
+}
@@ -5,6 +5,8 @@ use zbus::blocking::{fdo, Connection};
 use zbus::zvariant::{OwnedValue, Type, Value};
 use zbus::{interface, proxy};
 
+use crate::ui::setup_overlay::toggle_overlay;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Type, Value, OwnedValue)]
 #[zvariant(signature = "u")]
 pub enum AppState {
@@ -54,6 +56,11 @@ impl ROGCCZbus {
             *lock = state;
         }
     }
+
+    /// Show the quick settings overlay, or hide it if it is showing
+    async fn toggle_overlay(&self) {
+        toggle_overlay();
+    }
 }
 
 #[proxy(
@@ -68,6 +75,8 @@ pub trait ROGCCZbus {
 
     #[zbus(property)]
     fn set_state(&self, state: AppState) -> zbus::Result<()>;
+
+    fn toggle_overlay(&self) -> zbus::Result<()>;
 }
 
 pub fn find_iface<T>(iface_name: &str) -> Result<Vec<T>, Box<dyn std::error::Error>>
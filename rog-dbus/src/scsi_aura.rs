@@ -20,7 +20,7 @@
 //!
 //! [Writing a client proxy]: https://dbus2.github.io/zbus/client.html
 //! [D-Bus standard interfaces]: https://dbus.freedesktop.org/doc/dbus-specification.html#standard-interfaces,
-use rog_scsi::{AuraEffect, AuraMode};
+use rog_scsi::{AuraEffect, AuraMode, Colour};
 use zbus::proxy;
 #[proxy(
     interface = "xyz.ljones.ScsiAura",
@@ -32,6 +32,18 @@ pub trait ScsiAura {
     #[allow(clippy::type_complexity)]
     fn all_mode_data(&self) -> zbus::Result<std::collections::HashMap<AuraMode, AuraEffect>>;
 
+    /// DeviceEffect method
+    fn device_effect(&self) -> zbus::Result<AuraEffect>;
+
+    /// DeviceDirectColours method
+    fn device_direct_colours(&self) -> zbus::Result<Vec<Colour>>;
+
+    /// SetDirectColours method
+    fn set_direct_colours(&self, colours: &[Colour]) -> zbus::Result<()>;
+
+    /// Save method
+    fn save(&self) -> zbus::Result<()>;
+
     /// DeviceType property
     #[zbus(property)]
     fn device_type(&self) -> zbus::Result<u32>;
@@ -53,4 +65,10 @@ pub trait ScsiAura {
     fn led_mode_data(&self) -> zbus::Result<AuraEffect>;
     #[zbus(property)]
     fn set_led_mode_data(&self, effect: AuraEffect) -> zbus::Result<()>;
+
+    /// SaveToDevice property
+    #[zbus(property)]
+    fn save_to_device(&self) -> zbus::Result<bool>;
+    #[zbus(property)]
+    fn set_save_to_device(&self, value: bool) -> zbus::Result<()>;
 }
@@ -27,13 +27,23 @@ use zbus::proxy;
     default_path = "/xyz/ljones"
 )]
 pub trait Backlight {
+    /// ToggleScreenpad method
+    fn toggle_screenpad(&self) -> zbus::Result<()>;
+
     /// PrimaryBrightness property
     #[zbus(property)]
     fn primary_brightness(&self) -> zbus::Result<i32>;
     #[zbus(property)]
     fn set_primary_brightness(&self, value: i32) -> zbus::Result<()>;
 
-    /// ScreenpadBrightness property
+    /// ScreenpadAmbientBrightness property
+    #[zbus(property)]
+    fn screenpad_ambient_brightness(&self) -> zbus::Result<bool>;
+    #[zbus(property)]
+    fn set_screenpad_ambient_brightness(&self, value: bool) -> zbus::Result<()>;
+
+    /// ScreenpadBrightness property, set automatically while any process named
+    /// in `app_brightness` runs, fullscreen or not
     #[zbus(property)]
     fn screenpad_brightness(&self) -> zbus::Result<i32>;
     #[zbus(property)]
@@ -45,6 +55,12 @@ pub trait Backlight {
     #[zbus(property)]
     fn set_screenpad_gamma(&self, value: &str) -> zbus::Result<()>;
 
+    /// ScreenpadIdleTimeout property
+    #[zbus(property)]
+    fn screenpad_idle_timeout(&self) -> zbus::Result<u32>;
+    #[zbus(property)]
+    fn set_screenpad_idle_timeout(&self, value: u32) -> zbus::Result<()>;
+
     /// ScreenpadPower property
     #[zbus(property)]
     fn screenpad_power(&self) -> zbus::Result<bool>;
@@ -59,4 +59,9 @@ pub trait FanCurves {
         fan: FanCurvePU,
         enabled: bool,
     ) -> zbus::Result<()>;
+
+    /// Emitted when a profile goes from using no custom fan curves to using at
+    /// least one, or back
+    #[zbus(signal)]
+    fn fan_curves_toggled(&self, profile: PlatformProfile, enabled: bool) -> zbus::Result<()>;
 }
@@ -48,6 +48,11 @@ pub trait Platform {
     // Toggle one-shot charge to 100%
     fn one_shot_full_charge(&self) -> zbus::Result<()>;
 
+    /// Emitted once each time the battery charges up to the charge limit while
+    /// on AC. `one_shot` is true if it was the 100% of a one-shot full charge.
+    #[zbus(signal)]
+    fn charge_limit_reached(&self, limit: u8, one_shot: bool) -> zbus::Result<()>;
+
     /// ThrottleBalancedEpp property
     #[zbus(property)]
     fn profile_balanced_epp(&self) -> zbus::Result<CPUEPP>;
@@ -66,6 +71,18 @@ pub trait Platform {
     #[zbus(property)]
     fn set_platform_profile_linked_epp(&self, value: bool) -> zbus::Result<()>;
 
+    /// CpuEpp property, the EPP currently set on the CPUs
+    #[zbus(property)]
+    fn cpu_epp(&self) -> zbus::Result<CPUEPP>;
+
+    /// PowerPlugged property
+    #[zbus(property)]
+    fn power_plugged(&self) -> zbus::Result<bool>;
+
+    /// PendingReboot property
+    #[zbus(property)]
+    fn pending_reboot(&self) -> zbus::Result<bool>;
+
     /// ThrottlePolicyOnAc property
     #[zbus(property)]
     fn platform_profile_on_ac(&self) -> zbus::Result<PlatformProfile>;
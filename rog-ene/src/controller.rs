@@ -0,0 +1,159 @@
+use std::io::Result;
+
+use serde::{Deserialize, Serialize};
+
+pub const ENE_REG_DEVICE_NAME: u16 = 0x1000; // 16 byte ASCII name
+pub const ENE_REG_DIRECT: u16 = 0x8020; // Direct Access Selection Register
+pub const ENE_REG_MODE: u16 = 0x8021; // Mode Selection Register
+pub const ENE_REG_SPEED: u16 = 0x8022; // Speed Control Register
+pub const ENE_REG_DIRECTION: u16 = 0x8023; // Direction Control Register
+pub const ENE_REG_APPLY: u16 = 0x80a0; // Apply Changes Register
+
+pub const ENE_REG_COLORS_DIRECT: u16 = 0x8000;
+pub const ENE_REG_COLORS_EFFECT: u16 = 0x8010;
+pub const ENE_REG_COLORS_DIRECT_V2: u16 = 0x8100;
+pub const ENE_REG_COLORS_EFFECT_V2: u16 = 0x8160;
+
+pub const ENE_APPLY_VAL: u8 = 0x01; // Value for Apply Changes Register
+pub const ENE_SAVE_VAL: u8 = 0xaa; // Value for Apply Changes Register to also save
+
+const DEVICE_NAME_LEN: usize = 16;
+
+/// Controller generations differ in where the colour registers are
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum EneVersion {
+    V1,
+    #[default]
+    V2,
+}
+
+impl EneVersion {
+    pub const fn direct_colours(&self) -> u16 {
+        match self {
+            EneVersion::V1 => ENE_REG_COLORS_DIRECT,
+            EneVersion::V2 => ENE_REG_COLORS_DIRECT_V2,
+        }
+    }
+
+    pub const fn effect_colours(&self) -> u16 {
+        match self {
+            EneVersion::V1 => ENE_REG_COLORS_EFFECT,
+            EneVersion::V2 => ENE_REG_COLORS_EFFECT_V2,
+        }
+    }
+}
+
+/// Register level access to an ENE controller. A backend only needs to
+/// implement `read_registers()`, `write_registers()`, and `version()`, the
+/// rest are built on those.
+///
+/// Colours are `[u8; 3]` in the order the controller stores them, which is
+/// red, blue, green.
+pub trait EneController {
+    /// Read `buf.len()` consecutive registers starting at `reg`
+    fn read_registers(&mut self, reg: u16, buf: &mut [u8]) -> Result<()>;
+
+    /// Write `data` to consecutive registers starting at `reg`
+    fn write_registers(&mut self, reg: u16, data: &[u8]) -> Result<()>;
+
+    fn version(&self) -> EneVersion;
+
+    fn read_register(&mut self, reg: u16) -> Result<u8> {
+        let mut buf = [0];
+        self.read_registers(reg, &mut buf)?;
+        Ok(buf[0])
+    }
+
+    fn write_register(&mut self, reg: u16, value: u8) -> Result<()> {
+        self.write_registers(reg, &[value])
+    }
+
+    /// The controller name, such as `AUMA0-E6K5-0106`
+    fn device_name(&mut self) -> Result<String> {
+        let mut buf = [0; DEVICE_NAME_LEN];
+        self.read_registers(ENE_REG_DEVICE_NAME, &mut buf)?;
+        Ok(String::from_utf8_lossy(&buf)
+            .trim_end_matches('\0')
+            .to_owned())
+    }
+
+    fn mode(&mut self) -> Result<u8> {
+        self.read_register(ENE_REG_MODE)
+    }
+
+    fn set_mode(&mut self, mode: u8) -> Result<()> {
+        self.write_register(ENE_REG_MODE, mode)
+    }
+
+    fn speed(&mut self) -> Result<u8> {
+        self.read_register(ENE_REG_SPEED)
+    }
+
+    fn set_speed(&mut self, speed: u8) -> Result<()> {
+        self.write_register(ENE_REG_SPEED, speed)
+    }
+
+    fn direction(&mut self) -> Result<u8> {
+        self.read_register(ENE_REG_DIRECTION)
+    }
+
+    fn set_direction(&mut self, direction: u8) -> Result<()> {
+        self.write_register(ENE_REG_DIRECTION, direction)
+    }
+
+    /// If the LEDs show the direct colours instead of the effect
+    fn direct(&mut self) -> Result<bool> {
+        Ok(self.read_register(ENE_REG_DIRECT)? == 1)
+    }
+
+    fn set_direct(&mut self, direct: bool) -> Result<()> {
+        self.write_register(ENE_REG_DIRECT, direct as u8)
+    }
+
+    fn effect_colours(&mut self, count: usize) -> Result<Vec<[u8; 3]>> {
+        let reg = self.version().effect_colours();
+        read_colours(self, reg, count)
+    }
+
+    fn set_effect_colour(&mut self, led: usize, rgb: [u8; 3]) -> Result<()> {
+        let reg = self.version().effect_colours() + led as u16 * 3;
+        self.write_registers(reg, &rgb)
+    }
+
+    fn direct_colours(&mut self, count: usize) -> Result<Vec<[u8; 3]>> {
+        let reg = self.version().direct_colours();
+        read_colours(self, reg, count)
+    }
+
+    fn set_direct_colour(&mut self, led: usize, rgb: [u8; 3]) -> Result<()> {
+        let reg = self.version().direct_colours() + led as u16 * 3;
+        self.write_registers(reg, &rgb)
+    }
+
+    /// Show the values written since the last apply
+    fn apply(&mut self) -> Result<()> {
+        self.write_register(ENE_REG_APPLY, ENE_APPLY_VAL)
+    }
+
+    /// Store the current state so that it is restored on power up
+    fn save(&mut self) -> Result<()> {
+        self.write_register(ENE_REG_APPLY, ENE_SAVE_VAL)
+    }
+}
+
+fn read_colours<C: EneController + ?Sized>(
+    ctrl: &mut C,
+    reg: u16,
+    count: usize,
+) -> Result<Vec<[u8; 3]>> {
+    let mut buf = vec![0; count * 3];
+    ctrl.read_registers(reg, &mut buf)?;
+    Ok(buf
+        .chunks_exact(3)
+        .map(|c| {
+            [
+                c[0], c[1], c[2],
+            ]
+        })
+        .collect())
+}
@@ -0,0 +1,87 @@
+use std::sync::OnceLock;
+
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::EneVersion;
+
+pub const ENE_DEVICES_CONF: &str = "/usr/share/asusd/ene_devices.ron";
+const ENE_DEVICES_BUILTIN: &str = include_str!("../data/ene_devices.ron");
+
+/// How the controller registers are reached
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum EneTransport {
+    /// A USB enclosure exposed as a SCSI generic device
+    Scsi { prod_id: String },
+    /// An SMBus device, `adapter` is matched against part of the adapter name
+    I2c { adapter: String, address: u16 },
+}
+
+/// Describes one model of ENE controller
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EneDeviceData {
+    pub name: String,
+    pub transport: EneTransport,
+    pub version: EneVersion,
+    pub led_count: u8,
+    /// Expected contents of the controller name register. Required for
+    /// `EneTransport::I2c` as the address alone is not enough to be sure
+    /// what is on the other end.
+    pub device_name: Option<String>,
+}
+
+impl EneDeviceData {
+    /// Find the entry for a SCSI device by USB product ID, `0x` prefix and
+    /// case are ignored
+    pub fn find_scsi(prod_id: &str) -> Option<&'static EneDeviceData> {
+        let prod_id = prod_id.to_lowercase();
+        let prod_id = prod_id.trim_start_matches("0x");
+        ene_devices().iter().find(|d| {
+            matches!(&d.transport, EneTransport::Scsi { prod_id: id } if id.to_lowercase() == prod_id)
+        })
+    }
+}
+
+/// Load the builtin device data, then replace or append with those from
+/// `/usr/share/asusd/ene_devices.ron` if that file is available.
+pub fn ene_devices() -> &'static [EneDeviceData] {
+    static DEVICES: OnceLock<Vec<EneDeviceData>> = OnceLock::new();
+    DEVICES.get_or_init(|| {
+        let mut data: Vec<EneDeviceData> = ron::from_str(ENE_DEVICES_BUILTIN)
+            .unwrap_or_else(|e| panic!("Could not deserialise builtin ene_devices.ron: {e}"));
+
+        if let Ok(file) = std::fs::read_to_string(ENE_DEVICES_CONF) {
+            match ron::from_str::<Vec<EneDeviceData>>(&file) {
+                Ok(tmp) => {
+                    for dev in tmp {
+                        if let Some(existing) = data.iter_mut().find(|d| d.name == dev.name) {
+                            *existing = dev;
+                        } else {
+                            data.push(dev);
+                        }
+                    }
+                    info!("Loaded ENE device data from {ENE_DEVICES_CONF}");
+                }
+                Err(e) => error!("Could not deserialise {ENE_DEVICES_CONF}: {e}"),
+            }
+        } else {
+            warn!("{ENE_DEVICES_CONF} not found, using builtin ENE device data");
+        }
+        data
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EneDeviceData;
+    use crate::EneVersion;
+
+    #[test]
+    fn builtin_arion() {
+        let arion = EneDeviceData::find_scsi("0x1932").unwrap();
+        assert_eq!(arion.name, "Arion");
+        assert_eq!(arion.version, EneVersion::V2);
+        assert_eq!(arion.led_count, 4);
+        assert!(EneDeviceData::find_scsi("19b6").is_none());
+    }
+}
@@ -0,0 +1,186 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{Error, ErrorKind, Result};
+use std::os::fd::AsRawFd;
+use std::path::Path;
+
+use log::debug;
+
+use crate::{EneController, EneVersion};
+
+const I2C_ADAPTERS: &str = "/sys/class/i2c-adapter";
+
+/// The only addresses an [`I2cEne`] is opened at. ENE controllers answer at
+/// 0x77 until they are remapped, then at 0x70-0x7f. Selecting a register is a
+/// write, so anything else on the bus, such as the SPD EEPROMs at 0x50-0x57
+/// or their write protection at 0x30-0x37, must never be tried.
+pub const ENE_SMBUS_ADDRESSES: std::ops::RangeInclusive<u16> = 0x70..=0x7f;
+
+// From linux/i2c-dev.h and linux/i2c.h
+const I2C_SLAVE: libc::c_ulong = 0x0703;
+const I2C_SMBUS: libc::c_ulong = 0x0720;
+const I2C_SMBUS_WRITE: u8 = 0;
+const I2C_SMBUS_READ: u8 = 1;
+const I2C_SMBUS_BYTE_DATA: u32 = 2;
+const I2C_SMBUS_WORD_DATA: u32 = 3;
+
+// ENE SMBus commands, the register address is selected first then read or
+// written a byte at a time
+const ENE_SMBUS_SELECT: u8 = 0x00;
+const ENE_SMBUS_WRITE: u8 = 0x01;
+const ENE_SMBUS_READ: u8 = 0x81;
+
+#[repr(C)]
+union SmbusData {
+    byte: u8,
+    word: u16,
+    block: [u8; 34],
+}
+
+#[repr(C)]
+struct SmbusIoctlData {
+    read_write: u8,
+    command: u8,
+    size: u32,
+    data: *mut SmbusData,
+}
+
+/// Find the `/dev/i2c-*` path of the first adapter with a name containing
+/// `adapter`
+pub fn find_adapter(adapter: &str) -> Option<String> {
+    let mut entries: Vec<_> = fs::read_dir(I2C_ADAPTERS)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .collect();
+    entries.sort();
+    entries.iter().find_map(|path| {
+        let name = fs::read_to_string(path.join("name")).ok()?;
+        if name.contains(adapter) {
+            let dev = path.file_name()?.to_string_lossy();
+            debug!("Found i2c adapter {dev}: {}", name.trim());
+            Some(format!("/dev/{dev}"))
+        } else {
+            None
+        }
+    })
+}
+
+/// An ENE controller on an SMBus
+pub struct I2cEne {
+    file: File,
+    version: EneVersion,
+}
+
+impl I2cEne {
+    /// Fails with `InvalidInput` for an address outside of
+    /// [`ENE_SMBUS_ADDRESSES`]
+    pub fn open(path: impl AsRef<Path>, address: u16, version: EneVersion) -> Result<Self> {
+        if !ENE_SMBUS_ADDRESSES.contains(&address) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("{address:#x} is not an ENE SMBus address"),
+            ));
+        }
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        // SAFETY: the fd is valid for the life of `file`, I2C_SLAVE takes the
+        // address by value
+        if unsafe { libc::ioctl(file.as_raw_fd(), I2C_SLAVE as _, address as libc::c_ulong) } < 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(Self { file, version })
+    }
+
+    fn smbus(&self, read_write: u8, command: u8, size: u32, data: &mut SmbusData) -> Result<()> {
+        let mut args = SmbusIoctlData {
+            read_write,
+            command,
+            size,
+            data,
+        };
+        // SAFETY: `args` and the `data` it points to outlive the call
+        if unsafe { libc::ioctl(self.file.as_raw_fd(), I2C_SMBUS as _, &mut args) } < 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    fn select(&self, reg: u16) -> Result<()> {
+        // The controller takes the register big endian
+        let mut data = SmbusData {
+            word: reg.swap_bytes(),
+        };
+        self.smbus(
+            I2C_SMBUS_WRITE, ENE_SMBUS_SELECT, I2C_SMBUS_WORD_DATA, &mut data,
+        )
+    }
+
+    fn read_byte(&self, reg: u16) -> Result<u8> {
+        self.select(reg)?;
+        let mut data = SmbusData { byte: 0 };
+        self.smbus(
+            I2C_SMBUS_READ, ENE_SMBUS_READ, I2C_SMBUS_BYTE_DATA, &mut data,
+        )?;
+        // SAFETY: a byte read fills `byte`
+        Ok(unsafe { data.byte })
+    }
+
+    fn write_byte(&self, reg: u16, value: u8) -> Result<()> {
+        self.select(reg)?;
+        let mut data = SmbusData { byte: value };
+        self.smbus(
+            I2C_SMBUS_WRITE, ENE_SMBUS_WRITE, I2C_SMBUS_BYTE_DATA, &mut data,
+        )
+    }
+}
+
+impl EneController for I2cEne {
+    fn read_registers(&mut self, reg: u16, buf: &mut [u8]) -> Result<()> {
+        for (i, byte) in buf.iter_mut().enumerate() {
+            let reg = reg
+                .checked_add(i as u16)
+                .ok_or_else(|| Error::from(ErrorKind::InvalidInput))?;
+            *byte = self.read_byte(reg)?;
+        }
+        Ok(())
+    }
+
+    fn write_registers(&mut self, reg: u16, data: &[u8]) -> Result<()> {
+        for (i, byte) in data.iter().enumerate() {
+            let reg = reg
+                .checked_add(i as u16)
+                .ok_or_else(|| Error::from(ErrorKind::InvalidInput))?;
+            self.write_byte(reg, *byte)?;
+        }
+        Ok(())
+    }
+
+    fn version(&self) -> EneVersion {
+        self.version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use super::I2cEne;
+    use crate::EneVersion;
+
+    #[test]
+    fn only_ene_addresses() {
+        // SPD EEPROM and its write protection, refused before the path is
+        // opened
+        for address in [
+            0x50, 0x57, 0x36, 0x00, 0x80,
+        ] {
+            let err = I2cEne::open("/nonexistent", address, EneVersion::V2)
+                .err()
+                .unwrap();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        }
+        let err = I2cEne::open("/nonexistent", 0x77, EneVersion::V2)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+}
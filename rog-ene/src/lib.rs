@@ -0,0 +1,20 @@
+//! Control of ENE aura controllers as found in ASUS external drive
+//! enclosures, RAM, and GPUs. The register protocol is the same for all of
+//! them, only the transport to reach the registers differs.
+
+/// The `EneController` trait and register map
+mod controller;
+pub use controller::*;
+
+/// Device descriptions loaded from `ene_devices.ron`
+mod data;
+pub use data::*;
+
+/// An in-memory register file for tests and simulators
+pub mod memory;
+
+/// ENE controllers on an SMBus, through `/dev/i2c-*`
+#[cfg(feature = "i2c")]
+pub mod i2c;
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -0,0 +1,97 @@
+use std::io::Result;
+
+use crate::{EneController, EneVersion, ENE_REG_DEVICE_NAME};
+
+/// A full 16 bit register file held in memory. Reads return whatever was last
+/// written, so it stands in for a device in tests.
+#[derive(Debug, Clone)]
+pub struct MemoryEne {
+    registers: Vec<u8>,
+    version: EneVersion,
+}
+
+impl MemoryEne {
+    pub fn new(version: EneVersion) -> Self {
+        Self {
+            registers: vec![0; u16::MAX as usize + 1],
+            version,
+        }
+    }
+
+    /// Set the controller name register
+    pub fn with_name(mut self, name: &str) -> Self {
+        let start = ENE_REG_DEVICE_NAME as usize;
+        for (reg, byte) in self.registers[start..start + 16]
+            .iter_mut()
+            .zip(name.bytes().chain(std::iter::repeat(0)))
+        {
+            *reg = byte;
+        }
+        self
+    }
+
+    /// Get the raw value of a register
+    pub fn register(&self, reg: u16) -> u8 {
+        self.registers[reg as usize]
+    }
+}
+
+impl Default for MemoryEne {
+    fn default() -> Self {
+        Self::new(EneVersion::default())
+    }
+}
+
+impl EneController for MemoryEne {
+    fn read_registers(&mut self, reg: u16, buf: &mut [u8]) -> Result<()> {
+        let start = reg as usize;
+        buf.copy_from_slice(&self.registers[start..start + buf.len()]);
+        Ok(())
+    }
+
+    fn write_registers(&mut self, reg: u16, data: &[u8]) -> Result<()> {
+        let start = reg as usize;
+        self.registers[start..start + data.len()].copy_from_slice(data);
+        Ok(())
+    }
+
+    fn version(&self) -> EneVersion {
+        self.version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MemoryEne;
+    use crate::{
+        EneController, EneVersion, ENE_REG_APPLY, ENE_REG_COLORS_DIRECT, ENE_REG_COLORS_EFFECT_V2,
+        ENE_SAVE_VAL,
+    };
+
+    #[test]
+    fn register_round_trip() {
+        let mut ene = MemoryEne::default().with_name("AUMA0-E6K5-0106");
+        assert_eq!(ene.device_name().unwrap(), "AUMA0-E6K5-0106");
+
+        ene.set_mode(4).unwrap();
+        ene.set_direct(true).unwrap();
+        ene.set_effect_colour(1, [1, 2, 3]).unwrap();
+        ene.save().unwrap();
+        assert_eq!(ene.mode().unwrap(), 4);
+        assert!(ene.direct().unwrap());
+        assert_eq!(ene.register(ENE_REG_COLORS_EFFECT_V2 + 3), 1);
+        assert_eq!(ene.effect_colours(2).unwrap(), vec![
+            [0, 0, 0],
+            [1, 2, 3]
+        ]);
+        assert_eq!(ene.register(ENE_REG_APPLY), ENE_SAVE_VAL);
+    }
+
+    #[test]
+    fn version_colour_registers() {
+        let mut ene = MemoryEne::new(EneVersion::V1);
+        ene.set_direct_colour(0, [9, 8, 7]).unwrap();
+        assert_eq!(ene.register(ENE_REG_COLORS_DIRECT + 2), 7);
+        assert_eq!(ene.direct_colours(1).unwrap(), vec![[9, 8, 7]]);
+    }
+}
@@ -0,0 +1,19 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use rog_platform::fixture::SysfsFixture;
+use rog_platform::sysfs::SYSFS_ROOT_ENV;
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = std::env::args().skip(1);
+    let (Some(description), Some(root)) = (args.next(), args.next()) else {
+        return Err("usage: sysfs-fixture <description.ron> <output dir>".into());
+    };
+
+    let fixture = SysfsFixture::from_ron(&std::fs::read_to_string(description)?)?;
+    let root = PathBuf::from(root);
+    fixture.create(&root)?;
+    println!("Created fake sysfs at {root:?}");
+    println!("Run asusd with {SYSFS_ROOT_ENV}={}", root.display());
+    Ok(())
+}
@@ -0,0 +1,50 @@
+//! Ambient light sensors, found through the IIO subsystem
+use std::path::{Path, PathBuf};
+
+use log::info;
+
+use crate::error::{PlatformError, Result};
+use crate::{has_attr, read_attr_num, sysfs};
+
+const ATTR_INPUT: &str = "in_illuminance_input";
+const ATTR_RAW: &str = "in_illuminance_raw";
+const ATTR_SCALE: &str = "in_illuminance_scale";
+const ATTR_OFFSET: &str = "in_illuminance_offset";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AmbientLight {
+    path: PathBuf,
+}
+
+impl AmbientLight {
+    pub fn new() -> Result<Self> {
+        Self::from_sysfs(sysfs::root())
+    }
+
+    /// Find the first light sensor under the given sysfs root
+    pub fn from_sysfs(root: &Path) -> Result<Self> {
+        for device in sysfs::devices(root, "iio")? {
+            if has_attr(device.syspath(), ATTR_INPUT) || has_attr(device.syspath(), ATTR_RAW) {
+                info!("Found ambient light sensor at {:?}", device.sysname());
+                return Ok(Self {
+                    path: device.syspath().to_path_buf(),
+                });
+            }
+        }
+        Err(PlatformError::MissingFunction(
+            "No ambient light sensor found".into(),
+        ))
+    }
+
+    /// The current illuminance in lux. Sensors without a processed value
+    /// have the raw value scaled as described by the IIO ABI.
+    pub fn get_illuminance(&self) -> Result<f32> {
+        if let Ok(lux) = read_attr_num::<f32>(&self.path, ATTR_INPUT) {
+            return Ok(lux);
+        }
+        let raw: f32 = read_attr_num(&self.path, ATTR_RAW)?;
+        let offset: f32 = read_attr_num(&self.path, ATTR_OFFSET).unwrap_or(0.0);
+        let scale: f32 = read_attr_num(&self.path, ATTR_SCALE).unwrap_or(1.0);
+        Ok((raw + offset) * scale)
+    }
+}
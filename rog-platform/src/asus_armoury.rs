@@ -8,9 +8,9 @@ use zbus::zvariant::{OwnedValue, Type, Value};
 
 use crate::error::PlatformError;
 
-/// The root sysfs path. This path should never change in kernel so
-/// using udev to find it *should* not be required.
-const BASE_DIR: &str = "/sys/class/firmware-attributes/asus-armoury/attributes/";
+/// The attributes path relative to the sysfs root. This path should never
+/// change in kernel so using udev to find it *should* not be required.
+const BASE_DIR: &str = "class/firmware-attributes/asus-armoury/attributes/";
 
 fn read_i32(path: &Path) -> Result<i32, PlatformError> {
     if let Ok(mut f) = File::open(path) {
@@ -95,7 +95,7 @@ impl Attribute {
             _ => return Err(PlatformError::InvalidValue),
         };
 
-        let mut file = OpenOptions::new().write(true).open(&path)?;
+        let mut file = OpenOptions::new().write(true).truncate(true).open(&path)?;
         file.write_all(value_str.as_bytes())?;
         Ok(())
     }
@@ -195,13 +195,21 @@ impl Attribute {
 #[derive(Clone)]
 pub struct FirmwareAttributes {
     attrs: Vec<Attribute>,
+    /// The `pending_reboot` file beside the attributes
+    pending_reboot: PathBuf,
 }
 
 #[allow(clippy::new_without_default)]
 impl FirmwareAttributes {
     pub fn new() -> Self {
+        Self::from_sysfs(crate::sysfs::root())
+    }
+
+    /// Read the `asus-armoury` attributes under the given sysfs root
+    pub fn from_sysfs(root: &Path) -> Self {
         let mut attrs = Vec::new();
-        if let Ok(dir) = read_dir(BASE_DIR) {
+        let pending_reboot = root.join(BASE_DIR).join("pending_reboot");
+        if let Ok(dir) = read_dir(root.join(BASE_DIR)) {
             for entry in dir.flatten() {
                 let base_path = entry.path();
                 let name = base_path.file_name().unwrap().to_string_lossy().to_string();
@@ -225,7 +233,30 @@ impl FirmwareAttributes {
                 });
             }
         }
-        Self { attrs }
+        Self {
+            attrs,
+            pending_reboot,
+        }
+    }
+
+    /// True once an attribute was changed that only applies after a reboot
+    pub fn pending_reboot(&self) -> Result<bool, PlatformError> {
+        Ok(read_i32(&self.pending_reboot)? == 1)
+    }
+
+    pub fn monitor_pending_reboot(&self) -> Result<inotify::Inotify, PlatformError> {
+        let inotify = inotify::Inotify::init()?;
+        inotify
+            .watches()
+            .add(&self.pending_reboot, inotify::WatchMask::MODIFY)
+            .map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    PlatformError::AttrNotFound("pending_reboot".to_string())
+                } else {
+                    PlatformError::IoPath(self.pending_reboot.to_string_lossy().to_string(), e)
+                }
+            })?;
+        Ok(inotify)
     }
 
     pub fn attributes(&self) -> &Vec<Attribute> {
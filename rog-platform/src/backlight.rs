@@ -1,9 +1,9 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use log::{info, warn};
+use log::info;
 
 use crate::error::{PlatformError, Result};
-use crate::{attr_num, to_device};
+use crate::{attr_num, sysfs};
 
 /// The "backlight" device provides access to screen brightness control
 #[derive(Debug, PartialEq, Eq, PartialOrd, Clone)]
@@ -26,23 +26,16 @@ impl Backlight {
     attr_num!("bl_power", path, i32);
 
     pub fn new(device_type: BacklightType) -> Result<Self> {
-        let mut enumerator = udev::Enumerator::new().map_err(|err| {
-            warn!("{}", err);
-            PlatformError::Udev("enumerator failed".into(), err)
-        })?;
-        enumerator.match_subsystem("backlight").map_err(|err| {
-            warn!("{}", err);
-            PlatformError::Udev("match_subsystem failed".into(), err)
-        })?;
+        Self::from_sysfs(sysfs::root(), device_type)
+    }
 
-        for device in enumerator.scan_devices().map_err(|err| {
-            warn!("{}", err);
-            PlatformError::Udev("scan_devices failed".into(), err)
-        })? {
+    /// Find the backlight of `device_type` under the given sysfs root
+    pub fn from_sysfs(root: &Path, device_type: BacklightType) -> Result<Self> {
+        for device in sysfs::devices(root, "backlight")? {
             info!("Backlight: Checking {:?}", device.syspath());
             match device_type {
                 BacklightType::Primary => {
-                    if device.sysname().to_string_lossy() == "intel_backlight" {
+                    if device.sysname() == "intel_backlight" {
                         info!("Found primary backlight at {:?}", device.sysname());
                         return Ok(Self {
                             path: device.syspath().to_path_buf(),
@@ -51,7 +44,7 @@ impl Backlight {
                     }
                 }
                 BacklightType::Screenpad => {
-                    let name = device.sysname().to_string_lossy();
+                    let name = device.sysname();
                     if name == "asus_screenpad" || name == "asus_screenpad_backlight" {
                         info!("Found screenpad backlight at {:?}", device.sysname());
                         return Ok(Self {
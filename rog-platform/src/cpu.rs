@@ -1,12 +1,12 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use log::{info, warn};
+use log::info;
 use serde::{Deserialize, Serialize};
 use zbus::zvariant::{OwnedValue, Type, Value};
 
 use crate::error::{PlatformError, Result};
 use crate::platform::PlatformProfile;
-use crate::{read_attr_string, to_device};
+use crate::{read_attr_string, sysfs, write_attr_string};
 
 const ATTR_AVAILABLE_GOVERNORS: &str = "cpufreq/scaling_available_governors";
 const ATTR_GOVERNOR: &str = "cpufreq/scaling_governor";
@@ -23,21 +23,14 @@ pub struct CPUControl {
 
 impl CPUControl {
     pub fn new() -> Result<Self> {
-        let mut enumerator = udev::Enumerator::new().map_err(|err| {
-            warn!("{}", err);
-            PlatformError::Udev("enumerator failed".into(), err)
-        })?;
-        enumerator.match_subsystem("cpu").map_err(|err| {
-            warn!("{}", err);
-            PlatformError::Udev("match_subsystem failed".into(), err)
-        })?;
+        Self::from_sysfs(sysfs::root())
+    }
 
+    /// Find the CPU devices under the given sysfs root
+    pub fn from_sysfs(root: &Path) -> Result<Self> {
         let mut supported = false;
         let mut cpu = CPUControl { paths: Vec::new() };
-        for device in enumerator.scan_devices().map_err(|err| {
-            warn!("{}", err);
-            PlatformError::Udev("CPU: scan_devices failed".into(), err)
-        })? {
+        for device in sysfs::devices(root, "cpu")? {
             if !supported {
                 info!(
                     "Found CPU support at {:?}, checking supported items",
@@ -85,7 +78,7 @@ impl CPUControl {
 
     pub fn get_governor(&self) -> Result<CPUGovernor> {
         if let Some(path) = self.paths.first() {
-            let s = read_attr_string(&to_device(path)?, ATTR_GOVERNOR)?;
+            let s = read_attr_string(path, ATTR_GOVERNOR)?;
             Ok(s.as_str().into())
             // TODO: check cpu are sync
         } else {
@@ -95,7 +88,7 @@ impl CPUControl {
 
     pub fn get_available_governors(&self) -> Result<Vec<CPUGovernor>> {
         if let Some(path) = self.paths.first() {
-            read_attr_string(&to_device(path)?, ATTR_AVAILABLE_GOVERNORS)
+            read_attr_string(path, ATTR_AVAILABLE_GOVERNORS)
                 .map(|s| s.split_whitespace().map(|s| s.into()).collect())
             // TODO: check cpu are sync
         } else {
@@ -108,15 +101,14 @@ impl CPUControl {
             return Err(PlatformError::CPU(format!("{gov:?} is not available")));
         }
         for path in &self.paths {
-            let mut dev = to_device(path)?;
-            dev.set_attribute_value(ATTR_AVAILABLE_GOVERNORS, String::from(gov))?;
+            write_attr_string(path, ATTR_GOVERNOR, &String::from(gov))?;
         }
         Ok(())
     }
 
     pub fn get_epp(&self) -> Result<CPUEPP> {
         if let Some(path) = self.paths.first() {
-            let s = read_attr_string(&to_device(path)?, ATTR_EPP)?;
+            let s = read_attr_string(path, ATTR_EPP)?;
             Ok(s.as_str().into())
             // TODO: check cpu are sync
         } else {
@@ -126,7 +118,7 @@ impl CPUControl {
 
     pub fn get_available_epp(&self) -> Result<Vec<CPUEPP>> {
         if let Some(path) = self.paths.first() {
-            read_attr_string(&to_device(path)?, ATTR_AVAILABLE_EPP)
+            read_attr_string(path, ATTR_AVAILABLE_EPP)
                 .map(|s| s.split_whitespace().map(|s| s.into()).collect())
             // TODO: check cpu are sync
         } else {
@@ -139,8 +131,7 @@ impl CPUControl {
             return Err(PlatformError::CPU(format!("{epp:?} is not available")));
         }
         for path in &self.paths {
-            let mut dev = to_device(path)?;
-            dev.set_attribute_value(ATTR_EPP, String::from(epp))?;
+            write_attr_string(path, ATTR_EPP, &String::from(epp))?;
         }
         Ok(())
     }
@@ -259,8 +250,41 @@ impl From<CPUEPP> for i32 {
 
 #[cfg(test)]
 mod tests {
+    use std::fs::{read_to_string, remove_dir_all};
+
     use super::CPUControl;
     use crate::cpu::{CPUGovernor, CPUEPP};
+    use crate::fixture::SysfsFixture;
+
+    #[test]
+    fn set_governor() {
+        let root =
+            std::env::temp_dir().join(format!("rog-platform-governor-{}", std::process::id()));
+        remove_dir_all(&root).ok();
+        SysfsFixture::from_ron(include_str!("../data/sysfs/ga402.ron"))
+            .unwrap()
+            .create(&root)
+            .unwrap();
+
+        let cpu = CPUControl::from_sysfs(&root).unwrap();
+        cpu.set_governor(CPUGovernor::Performance).unwrap();
+        for n in 0..2 {
+            let cpufreq = root.join(format!("bus/cpu/devices/cpu{n}/cpufreq"));
+            assert_eq!(
+                read_to_string(cpufreq.join("scaling_governor")).unwrap(),
+                "performance"
+            );
+            assert_eq!(
+                read_to_string(cpufreq.join("scaling_available_governors"))
+                    .unwrap()
+                    .trim(),
+                "performance powersave"
+            );
+        }
+        assert_eq!(cpu.get_governor().unwrap(), CPUGovernor::Performance);
+
+        remove_dir_all(root).ok();
+    }
 
     #[test]
     #[ignore = "Can't run this in a docker image"]
@@ -23,6 +23,7 @@ pub enum PlatformError {
     NoAuraKeyboard,
     NoAuraNode,
     CPU(String),
+    Fixture(String),
 }
 
 impl fmt::Display for PlatformError {
@@ -53,6 +54,7 @@ impl fmt::Display for PlatformError {
             PlatformError::NoAuraKeyboard => write!(f, "No supported Aura keyboard"),
             PlatformError::NoAuraNode => write!(f, "No Aura keyboard node found"),
             PlatformError::CPU(s) => write!(f, "CPU control: {s}"),
+            PlatformError::Fixture(s) => write!(f, "sysfs fixture: {s}"),
         }
     }
 }
@@ -0,0 +1,347 @@
+//! Build a fake sysfs tree from a RON description so that asusd and the
+//! controls in this crate can be run without ASUS hardware. Point
+//! `ASUSD_SYSFS_ROOT` at the generated directory, or pass it to the
+//! `from_sysfs()` constructors.
+//!
+//! The layout mirrors the kernel: devices live under `devices/` and are
+//! linked from `class/<subsystem>` or `bus/<bus>/devices`, so discovery goes
+//! through the same paths as on a real system.
+
+use std::collections::BTreeMap;
+use std::fs::{create_dir_all, write};
+use std::os::unix::fs::symlink;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PlatformError, Result};
+
+/// Attribute file names and their contents. Names may contain a
+/// subdirectory, e.g. `cpufreq/scaling_governor`.
+pub type Attributes = BTreeMap<String, String>;
+
+#[derive(Deserialize, Serialize, Default, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct SysfsFixture {
+    /// Attributes of the `asus-nb-wmi` platform device, the device is not
+    /// created if this is `None`
+    pub asus_nb_wmi: Option<Attributes>,
+    /// Files in `firmware/acpi` such as `platform_profile`
+    pub acpi: Attributes,
+    /// `asus-armoury` firmware attributes by name, each a set of files such
+    /// as `current_value`, `default_value` and `display_name`
+    pub armoury: BTreeMap<String, Attributes>,
+    /// `power_supply` devices by name, `type` selects mains/battery/usb
+    pub power_supply: BTreeMap<String, Attributes>,
+    /// `backlight` devices by name, e.g. `intel_backlight`
+    pub backlight: BTreeMap<String, Attributes>,
+    /// `leds` devices by name, e.g. `asus::kbd_backlight`
+    pub leds: BTreeMap<String, Attributes>,
+    /// One entry per CPU, created as `cpu0`, `cpu1`, ...
+    pub cpus: Vec<Attributes>,
+    /// `hwmon` devices of `asus-nb-wmi` by name, e.g. `hwmon7` holding the
+    /// `asus_custom_fan_curve` node
+    pub hwmon: BTreeMap<String, Attributes>,
+    /// Files in `class/dmi/id` such as `board_name` and `product_name`
+    pub dmi: Attributes,
+    /// `iio` devices by name, e.g. `iio:device0` holding an ambient light
+    /// sensor's `in_illuminance_raw`
+    pub iio: BTreeMap<String, Attributes>,
+    /// `input` devices by name, e.g. `event3` with `device/name` and
+    /// `device/capabilities/ev`
+    pub input: BTreeMap<String, Attributes>,
+}
+
+impl SysfsFixture {
+    pub fn from_ron(ron: &str) -> Result<Self> {
+        ron::from_str(ron).map_err(|e| PlatformError::Fixture(e.to_string()))
+    }
+
+    /// Create the tree under `root`, which becomes the sysfs root. Existing
+    /// files are overwritten.
+    pub fn create(&self, root: &Path) -> Result<()> {
+        if let Some(attrs) = &self.asus_nb_wmi {
+            Self::bus_device(root, "platform", "devices/platform", "asus-nb-wmi", attrs)?;
+        }
+        Self::write_attrs(&root.join("firmware/acpi"), &self.acpi)?;
+
+        let armoury = root.join("class/firmware-attributes/asus-armoury/attributes");
+        for (name, attrs) in &self.armoury {
+            Self::write_attrs(&armoury.join(name), attrs)?;
+        }
+
+        for (subsystem, devices) in [
+            ("power_supply", &self.power_supply),
+            ("backlight", &self.backlight),
+            ("leds", &self.leds),
+            ("input", &self.input),
+        ] {
+            for (name, attrs) in devices {
+                Self::class_device(root, subsystem, name, attrs)?;
+            }
+        }
+
+        for (n, attrs) in self.cpus.iter().enumerate() {
+            Self::bus_device(root, "cpu", "devices/system/cpu", &format!("cpu{n}"), attrs)?;
+        }
+
+        for (name, attrs) in &self.iio {
+            Self::bus_device(root, "iio", "devices/virtual/iio", name, attrs)?;
+        }
+
+        for (name, attrs) in &self.hwmon {
+            let device = format!("devices/platform/asus-nb-wmi/hwmon/{name}");
+            Self::write_attrs(&root.join(&device), attrs)?;
+            Self::link(
+                root,
+                &format!("class/hwmon/{name}"),
+                &format!("../../{device}"),
+            )?;
+        }
+
+        if !self.dmi.is_empty() {
+            Self::write_attrs(&root.join("class/dmi/id"), &self.dmi)?;
+        }
+        Ok(())
+    }
+
+    fn class_device(root: &Path, subsystem: &str, name: &str, attrs: &Attributes) -> Result<()> {
+        let device = format!("devices/virtual/{subsystem}/{name}");
+        Self::write_attrs(&root.join(&device), attrs)?;
+        Self::link(
+            root,
+            &format!("class/{subsystem}/{name}"),
+            &format!("../../{device}"),
+        )
+    }
+
+    fn bus_device(
+        root: &Path,
+        bus: &str,
+        parent: &str,
+        name: &str,
+        attrs: &Attributes,
+    ) -> Result<()> {
+        let device = format!("{parent}/{name}");
+        Self::write_attrs(&root.join(&device), attrs)?;
+        Self::link(
+            root,
+            &format!("bus/{bus}/devices/{name}"),
+            &format!("../../../{device}"),
+        )
+    }
+
+    fn link(root: &Path, link: &str, target: &str) -> Result<()> {
+        let link = root.join(link);
+        if let Some(parent) = link.parent() {
+            create_dir_all(parent)
+                .map_err(|e| PlatformError::IoPath(parent.to_string_lossy().into(), e))?;
+        }
+        if link.symlink_metadata().is_ok() {
+            return Ok(());
+        }
+        symlink(target, &link).map_err(|e| PlatformError::IoPath(link.to_string_lossy().into(), e))
+    }
+
+    fn write_attrs(dir: &Path, attrs: &Attributes) -> Result<()> {
+        create_dir_all(dir).map_err(|e| PlatformError::IoPath(dir.to_string_lossy().into(), e))?;
+        for (name, value) in attrs {
+            let path = dir.join(name);
+            if let Some(parent) = path.parent() {
+                create_dir_all(parent)
+                    .map_err(|e| PlatformError::IoPath(parent.to_string_lossy().into(), e))?;
+            }
+            write(&path, format!("{value}\n"))
+                .map_err(|e| PlatformError::IoPath(path.to_string_lossy().into(), e))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{read_to_string, remove_dir_all};
+    use std::path::{Path, PathBuf};
+
+    use super::SysfsFixture;
+    use crate::ambient_light::AmbientLight;
+    use crate::asus_armoury::{AttrValue, FirmwareAttributes};
+    use crate::backlight::{Backlight, BacklightType};
+    use crate::cpu::{CPUControl, CPUGovernor, CPUEPP};
+    use crate::input::keyboard_and_touch_devices;
+    use crate::keyboard_led::KeyboardBacklight;
+    use crate::platform::{PlatformProfile, RogPlatform};
+    use crate::power::AsusPower;
+    use crate::sensors::{SensorReadings, Sensors};
+
+    const GA402: &str = include_str!("../data/sysfs/ga402.ron");
+
+    fn fixture_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "rog-platform-fixture-{name}-{}",
+            std::process::id()
+        ));
+        remove_dir_all(&root).ok();
+        SysfsFixture::from_ron(GA402)
+            .unwrap()
+            .create(&root)
+            .unwrap();
+        root
+    }
+
+    fn read(root: &Path, path: &str) -> String {
+        read_to_string(root.join(path)).unwrap()
+    }
+
+    #[test]
+    fn platform_and_power() {
+        let root = fixture_root("platform");
+
+        let platform = RogPlatform::from_sysfs(&root).unwrap();
+        assert_eq!(platform.get_platform_profile().unwrap(), "balanced");
+        assert_eq!(platform.get_platform_profile_choices().unwrap().len(), 3);
+        platform
+            .set_platform_profile(PlatformProfile::Performance.into())
+            .unwrap();
+        assert_eq!(read(&root, "firmware/acpi/platform_profile"), "performance");
+
+        let power = AsusPower::from_sysfs(&root).unwrap();
+        assert_eq!(power.get_online().unwrap(), 1);
+        assert_eq!(power.get_capacity().unwrap(), 76);
+        assert_eq!(power.get_power_draw().unwrap(), 18.25);
+        power.set_charge_control_end_threshold(60).unwrap();
+        assert_eq!(
+            read(
+                &root,
+                "class/power_supply/BAT0/charge_control_end_threshold"
+            ),
+            "60"
+        );
+
+        remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn backlights_and_leds() {
+        let root = fixture_root("backlight");
+
+        let primary = Backlight::from_sysfs(&root, BacklightType::Primary).unwrap();
+        assert_eq!(primary.get_max_brightness().unwrap(), 512);
+        primary.set_brightness(128).unwrap();
+        assert_eq!(
+            read(&root, "class/backlight/intel_backlight/brightness"),
+            "128"
+        );
+        assert!(Backlight::from_sysfs(&root, BacklightType::Screenpad).is_err());
+
+        let leds = KeyboardBacklight::from_sysfs(&root).unwrap();
+        assert!(leds.has_kbd_rgb_mode());
+        leds.set_kbd_rgb_mode(&[
+            1, 0, 255, 0, 0, 0,
+        ])
+        .unwrap();
+        assert_eq!(
+            read(&root, "class/leds/asus::kbd_backlight/kbd_rgb_mode"),
+            "1 0 255 0 0 0"
+        );
+
+        remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn cpu_and_armoury() {
+        let root = fixture_root("cpu");
+
+        let cpu = CPUControl::from_sysfs(&root).unwrap();
+        assert_eq!(cpu.get_governor().unwrap(), CPUGovernor::Powersave);
+        cpu.set_epp(CPUEPP::Performance).unwrap();
+        for n in 0..2 {
+            assert_eq!(
+                read(
+                    &root,
+                    &format!("bus/cpu/devices/cpu{n}/cpufreq/energy_performance_preference")
+                ),
+                "performance"
+            );
+        }
+
+        let attrs = FirmwareAttributes::from_sysfs(&root);
+        let boot_sound = attrs.boot_sound().unwrap();
+        assert_eq!(boot_sound.current_value().unwrap(), AttrValue::Integer(0));
+        boot_sound
+            .set_current_value(&AttrValue::Integer(1))
+            .unwrap();
+        assert_eq!(
+            read(
+                &root,
+                "class/firmware-attributes/asus-armoury/attributes/boot_sound/current_value"
+            ),
+            "1"
+        );
+        assert!(attrs.ppt_pl1_spl().is_some());
+
+        remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn sensors() {
+        let root = fixture_root("sensors");
+
+        let sensors = Sensors::from_sysfs(&root).unwrap();
+        assert_eq!(sensors.read(), SensorReadings {
+            cpu_temp: Some(61.25),
+            gpu_temp: Some(48.0),
+            cpu_fan: Some(2900),
+            gpu_fan: Some(3100),
+            cpu_freq: Some(3000),
+        });
+
+        remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn light_sensor_and_input() {
+        let root =
+            std::env::temp_dir().join(format!("rog-platform-fixture-iio-{}", std::process::id()));
+        remove_dir_all(&root).ok();
+        let mut fixture = SysfsFixture::default();
+        fixture.iio.insert(
+            "iio:device0".into(),
+            [
+                ("name".into(), "als".into()),
+                ("in_illuminance_raw".into(), "200".into()),
+                ("in_illuminance_scale".into(), "0.5".into()),
+            ]
+            .into(),
+        );
+        for (event, name, ev) in [
+            ("event2", "Lid Switch", "21"),
+            ("event3", "Asus Keyboard", "120013"),
+            ("event5", "ELAN9008:00 04F3:2C82", "1b"),
+            ("event6", "Logitech USB Receiver Mouse", "17"),
+        ] {
+            fixture.input.insert(
+                event.into(),
+                [
+                    ("device/name".into(), name.into()),
+                    ("device/capabilities/ev".into(), ev.into()),
+                ]
+                .into(),
+            );
+        }
+        fixture.create(&root).unwrap();
+
+        let als = AmbientLight::from_sysfs(&root).unwrap();
+        assert_eq!(als.get_illuminance().unwrap(), 100.0);
+
+        let inputs = keyboard_and_touch_devices(&root).unwrap();
+        let names: Vec<&str> = inputs.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, [
+            "Asus Keyboard",
+            "ELAN9008:00 04F3:2C82"
+        ]);
+        assert_eq!(inputs[0].devnode, Path::new("/dev/input/event3"));
+
+        remove_dir_all(root).ok();
+    }
+}
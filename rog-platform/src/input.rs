@@ -0,0 +1,48 @@
+//! Input event devices, used to tell when the keyboard or a touch surface was
+//! last used
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+use crate::{read_attr_string, sysfs};
+
+const EV_KEY: u64 = 1 << 0x01;
+const EV_ABS: u64 = 1 << 0x03;
+const EV_REP: u64 = 1 << 0x14;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InputDevice {
+    /// The name reported by the driver, e.g. `Asus Keyboard`
+    pub name: String,
+    /// The device node to read events from, `/dev/input/eventN`
+    pub devnode: PathBuf,
+}
+
+/// Keyboards (keys with autorepeat) and touch surfaces (absolute axes) found
+/// under the given sysfs root. Mice and buttons such as the lid switch are
+/// left out.
+pub fn keyboard_and_touch_devices(root: &Path) -> Result<Vec<InputDevice>> {
+    let mut found = Vec::new();
+    for device in sysfs::devices(root, "input")? {
+        if !device.sysname().starts_with("event") {
+            continue;
+        }
+        let Ok(ev) = read_attr_string(device.syspath(), "device/capabilities/ev") else {
+            continue;
+        };
+        // Long bitmaps are space separated words, the lowest is last
+        let ev = ev
+            .split_whitespace()
+            .last()
+            .and_then(|w| u64::from_str_radix(w, 16).ok())
+            .unwrap_or_default();
+        let keyboard = ev & EV_KEY != 0 && ev & EV_REP != 0;
+        let touch = ev & EV_ABS != 0;
+        if keyboard || touch {
+            found.push(InputDevice {
+                name: read_attr_string(device.syspath(), "device/name").unwrap_or_default(),
+                devnode: Path::new("/dev/input").join(device.sysname()),
+            });
+        }
+    }
+    Ok(found)
+}
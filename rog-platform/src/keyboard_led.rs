@@ -1,9 +1,9 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use log::{info, warn};
+use log::info;
 
 use crate::error::{PlatformError, Result};
-use crate::{attr_num, has_attr, set_attr_u8_array, to_device};
+use crate::{attr_num, has_attr, set_attr_u8_array, sysfs};
 
 /// The sysfs control for backlight levels. This is only for the 3-step
 /// backlight setting, and for TUF laptops. It is not a hard requirement
@@ -33,21 +33,13 @@ impl KeyboardBacklight {
     );
 
     pub fn new() -> Result<Self> {
-        let mut enumerator = udev::Enumerator::new().map_err(|err| {
-            warn!("{}", err);
-            PlatformError::Udev("enumerator failed".into(), err)
-        })?;
-
-        enumerator.match_subsystem("leds").map_err(|err| {
-            warn!("{}", err);
-            PlatformError::Udev("match_subsystem failed".into(), err)
-        })?;
-
-        for device in enumerator.scan_devices().map_err(|err| {
-            warn!("{}", err);
-            PlatformError::Udev("scan_devices failed".into(), err)
-        })? {
-            let sys = device.sysname().to_string_lossy();
+        Self::from_sysfs(sysfs::root())
+    }
+
+    /// Find the keyboard LED controls under the given sysfs root
+    pub fn from_sysfs(root: &Path) -> Result<Self> {
+        for device in sysfs::devices(root, "leds")? {
+            let sys = device.sysname();
             if sys.contains("kbd_backlight") || sys.contains("ally:rgb:gamepad") {
                 info!("Found keyboard LED controls at {:?}", device.sysname());
                 return Ok(Self {
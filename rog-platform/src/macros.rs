@@ -4,10 +4,7 @@ macro_rules! has_attr {
         concat_idents::concat_idents!(fn_name = has_, $attr_name {
             $(#[$attr])*
             pub fn fn_name(&self) -> bool {
-                match to_device(&self.$item) {
-                    Ok(p) => $crate::has_attr(&p, $attr_name),
-                    Err(_) => false,
-                }
+                $crate::has_attr(&self.$item, $attr_name)
             }
         });
     };
@@ -45,7 +42,7 @@ macro_rules! get_attr_bool {
         concat_idents::concat_idents!(fn_name = get_, $attr_name {
             $(#[$attr])*
             pub fn fn_name(&self) -> Result<bool> {
-                $crate::read_attr_bool(&to_device(&self.$item)?, $attr_name)
+                $crate::read_attr_bool(&self.$item, $attr_name)
             }
         });
     };
@@ -57,7 +54,7 @@ macro_rules! set_attr_bool {
         concat_idents::concat_idents!(fn_name = set_, $attr_name {
             $(#[$attr])*
             pub fn fn_name(&self, value: bool) -> Result<()> {
-                $crate::write_attr_bool(&mut to_device(&self.$item)?, $attr_name, value)
+                $crate::write_attr_bool(&self.$item, $attr_name, value)
             }
         });
     };
@@ -79,7 +76,7 @@ macro_rules! get_attr_num {
         concat_idents::concat_idents!(fn_name = get_, $attr_name {
             $(#[$attr])*
             pub fn fn_name(&self) -> Result<$type> {
-                $crate::read_attr_num::<$type>(&to_device(&self.$item)?, $attr_name)
+                $crate::read_attr_num::<$type>(&self.$item, $attr_name)
             }
         });
     };
@@ -94,7 +91,7 @@ macro_rules! set_attr_num {
         concat_idents::concat_idents!(fn_name = set_, $attr_name {
             $(#[$attr])*
             pub fn fn_name(&self, value: $type) -> Result<()> {
-                $crate::write_attr_num(&mut to_device(&self.$item)?, $attr_name, value as $type)
+                $crate::write_attr_num(&self.$item, $attr_name, value as $type)
             }
         });
     };
@@ -119,7 +116,7 @@ macro_rules! get_attr_u8_array {
         concat_idents::concat_idents!(fn_name = get_, $attr_name {
             $(#[$attr])*
             pub fn fn_name(&self) -> Result<Vec<u8>> {
-                $crate::read_attr_u8_array(&to_device(&self.$item)?, $attr_name)
+                $crate::read_attr_u8_array(&self.$item, $attr_name)
             }
         });
     };
@@ -131,7 +128,7 @@ macro_rules! set_attr_u8_array {
         concat_idents::concat_idents!(fn_name = set_, $attr_name {
             $(#[$attr])*
             pub fn fn_name(&self, values: &[u8]) -> Result<()> {
-                $crate::write_attr_u8_array(&mut to_device(&self.$item)?, $attr_name, values)
+                $crate::write_attr_u8_array(&self.$item, $attr_name, values)
             }
         });
     };
@@ -153,7 +150,7 @@ macro_rules! get_attr_string {
         concat_idents::concat_idents!(fn_name = get_, $attr_name {
             $(#[$attr])*
             pub fn fn_name(&self) -> Result<String> {
-                $crate::read_attr_string(&to_device(&self.$item)?, $attr_name)
+                $crate::read_attr_string(&self.$item, $attr_name)
             }
         });
     };
@@ -165,7 +162,7 @@ macro_rules! get_attr_string_array {
         concat_idents::concat_idents!(fn_name = get_, $attr_name {
             $(#[$attr])*
             pub fn fn_name(&self) -> Result<Vec<PlatformProfile>> {
-                $crate::read_attr_string_array(&to_device(&self.$item)?, $attr_name)
+                $crate::read_attr_string_array(&self.$item, $attr_name)
             }
         });
     };
@@ -177,7 +174,7 @@ macro_rules! set_attr_string {
         concat_idents::concat_idents!(fn_name = set_, $attr_name {
             $(#[$attr])*
             pub fn fn_name(&self, values: &str) -> Result<()> {
-                $crate::write_attr_string(&mut to_device(&self.$item)?, $attr_name, values)
+                $crate::write_attr_string(&self.$item, $attr_name, values)
             }
         });
     };
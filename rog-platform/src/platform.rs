@@ -1,5 +1,5 @@
 use std::fmt::Display;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use log::{info, warn};
@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use zbus::zvariant::{OwnedValue, Type, Value};
 
 use crate::error::{PlatformError, Result};
-use crate::{attr_string, attr_string_array, to_device};
+use crate::{attr_string, attr_string_array, sysfs};
 
 /// The "platform" device provides access to things like:
 /// - `dgpu_disable`
@@ -37,29 +37,19 @@ impl RogPlatform {
     );
 
     pub fn new() -> Result<Self> {
-        let mut enumerator = udev::Enumerator::new().map_err(|err| {
-            warn!("{}", err);
-            PlatformError::Udev("enumerator failed".into(), err)
-        })?;
-        enumerator.match_subsystem("platform").map_err(|err| {
-            warn!("{}", err);
-            PlatformError::Udev("match_subsystem failed".into(), err)
-        })?;
-        enumerator.match_sysname("asus-nb-wmi").map_err(|err| {
-            warn!("{}", err);
-            PlatformError::Udev("match_subsystem failed".into(), err)
-        })?;
+        Self::from_sysfs(sysfs::root())
+    }
 
-        if let Some(device) = (enumerator.scan_devices().map_err(|err| {
-            warn!("{}", err);
-            PlatformError::Udev("scan_devices failed".into(), err)
-        })?)
-        .next()
+    /// Find the `asus-nb-wmi` platform device under the given sysfs root
+    pub fn from_sysfs(root: &Path) -> Result<Self> {
+        if let Some(device) = sysfs::devices(root, "platform")?
+            .into_iter()
+            .find(|d| d.sysname() == "asus-nb-wmi")
         {
             info!("Found platform support at {:?}", device.sysname());
             return Ok(Self {
                 path: device.syspath().to_owned(),
-                pp_path: PathBuf::from_str("/sys/firmware/acpi").unwrap(),
+                pp_path: root.join("firmware/acpi"),
             });
         }
         Err(PlatformError::MissingFunction(
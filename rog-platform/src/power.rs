@@ -1,9 +1,9 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use log::{info, warn};
+use log::info;
 
 use crate::error::{PlatformError, Result};
-use crate::{attr_num, to_device};
+use crate::{attr_num, get_attr_num, has_attr, sysfs, watch_attr};
 
 /// The "platform" device provides access to things like:
 /// - `dgpu_disable`
@@ -24,6 +24,35 @@ impl AsusPower {
 
     attr_num!("online", mains, u8);
 
+    has_attr!(
+        /// Battery charge level in percent
+        "capacity" battery
+    );
+
+    get_attr_num!("capacity" battery u8);
+
+    watch_attr!("capacity" battery);
+
+    get_attr_num!(
+        /// Battery charge or discharge rate in µW
+        "power_now" battery u64
+    );
+
+    get_attr_num!("current_now" battery u64);
+
+    get_attr_num!("voltage_now" battery u64);
+
+    /// The battery charge or discharge rate in watts, from `current_now` and
+    /// `voltage_now` on batteries without `power_now`
+    pub fn get_power_draw(&self) -> Result<f32> {
+        if let Ok(power) = self.get_power_now() {
+            return Ok(power as f32 / 1_000_000.0);
+        }
+        let current = self.get_current_now()? as f32 / 1_000_000.0;
+        let voltage = self.get_voltage_now()? as f32 / 1_000_000.0;
+        Ok(current * voltage)
+    }
+
     /// When checking for battery this will look in order:
     /// - if attr `manufacturer` contains `asus`
     /// - if attr `charge_control_end_threshold` exists and `energy_full_design`
@@ -31,26 +60,19 @@ impl AsusPower {
     /// - if syspath end conatins `BAT`
     /// - if attr `type` is `battery` (last resort)
     pub fn new() -> Result<Self> {
+        Self::from_sysfs(sysfs::root())
+    }
+
+    /// Find the power supplies under the given sysfs root, see [`Self::new`]
+    pub fn from_sysfs(root: &Path) -> Result<Self> {
         let mut mains = PathBuf::new();
         let mut battery = None;
         let mut usb = None;
 
-        let mut enumerator = udev::Enumerator::new().map_err(|err| {
-            warn!("{}", err);
-            PlatformError::Udev("enumerator failed".into(), err)
-        })?;
-        enumerator.match_subsystem("power_supply").map_err(|err| {
-            warn!("{}", err);
-            PlatformError::Udev("match_subsystem failed".into(), err)
-        })?;
-
-        for device in enumerator.scan_devices().map_err(|err| {
-            warn!("{}", err);
-            PlatformError::Udev("scan_devices failed".into(), err)
-        })? {
+        for device in sysfs::devices(root, "power_supply")? {
             if let Some(attr) = device.attribute_value("type") {
                 info!("Power: Checking {:?}", device.syspath());
-                match attr.to_string_lossy().to_ascii_lowercase().trim() {
+                match attr.to_ascii_lowercase().trim() {
                     "mains" => {
                         info!("Found mains power at {:?}", device.sysname());
                         mains = device.syspath().to_path_buf();
@@ -69,7 +91,7 @@ impl AsusPower {
                                     device.sysname()
                                 );
                                 battery = Some(device.syspath().to_path_buf());
-                            } else if device.sysname().to_string_lossy().starts_with("BAT") {
+                            } else if device.sysname().starts_with("BAT") {
                                 info!(
                                     "Found battery power at {:?}, sysfs path ended with BAT<n>",
                                     device.sysname()
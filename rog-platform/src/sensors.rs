@@ -0,0 +1,122 @@
+//! Read only temperatures, fan speeds and CPU frequency for monitoring.
+//!
+//! The hwmon devices are matched by the driver `name`, so these work the same
+//! on AMD and Intel machines and on a fake tree from [`crate::fixture`].
+
+use std::path::{Path, PathBuf};
+
+use log::info;
+
+use crate::error::{PlatformError, Result};
+use crate::{read_attr_num, sysfs};
+
+/// hwmon drivers reporting the CPU package temperature as `temp1_input`
+const CPU_TEMP_DRIVERS: [&str; 3] = [
+    "k10temp", "zenpower", "coretemp",
+];
+/// hwmon drivers reporting a GPU edge temperature as `temp1_input`
+const GPU_TEMP_DRIVERS: [&str; 3] = [
+    "amdgpu", "nouveau", "radeon",
+];
+/// The `asus-nb-wmi` hwmon with `fan1_input` (CPU) and `fan2_input` (GPU)
+const FAN_DRIVER: &str = "asus";
+const ATTR_CUR_FREQ: &str = "cpufreq/scaling_cur_freq";
+
+/// One poll of every sensor found, `None` for those missing or unreadable
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct SensorReadings {
+    /// CPU package temperature in °C
+    pub cpu_temp: Option<f32>,
+    /// GPU temperature in °C
+    pub gpu_temp: Option<f32>,
+    /// CPU fan speed in RPM
+    pub cpu_fan: Option<u32>,
+    /// GPU fan speed in RPM
+    pub gpu_fan: Option<u32>,
+    /// Average of the current frequency of all cores in MHz
+    pub cpu_freq: Option<u32>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Sensors {
+    cpu_temp: Option<PathBuf>,
+    gpu_temp: Option<PathBuf>,
+    fans: Option<PathBuf>,
+    cpus: Vec<PathBuf>,
+}
+
+impl Sensors {
+    pub fn new() -> Result<Self> {
+        Self::from_sysfs(sysfs::root())
+    }
+
+    /// Find the sensors under the given sysfs root. A discrete GPU (one that
+    /// is not `boot_vga`) is preferred when several GPUs report a
+    /// temperature.
+    pub fn from_sysfs(root: &Path) -> Result<Self> {
+        let mut sensors = Sensors::default();
+        let mut gpu_is_discrete = false;
+
+        for device in sysfs::devices(root, "hwmon")? {
+            let Some(name) = device.attribute_value("name") else {
+                continue;
+            };
+            let path = device.syspath().to_owned();
+            if CPU_TEMP_DRIVERS.contains(&name.as_str()) && sensors.cpu_temp.is_none() {
+                info!("Found CPU temperature at {:?}", device.sysname());
+                sensors.cpu_temp = Some(path);
+            } else if GPU_TEMP_DRIVERS.contains(&name.as_str()) && !gpu_is_discrete {
+                gpu_is_discrete = crate::read_attr_string(&path, "device/boot_vga")
+                    .is_ok_and(|v| v.trim() == "0");
+                info!("Found GPU temperature at {:?}", device.sysname());
+                sensors.gpu_temp = Some(path);
+            } else if name == FAN_DRIVER && sensors.fans.is_none() {
+                info!("Found fan speeds at {:?}", device.sysname());
+                sensors.fans = Some(path);
+            }
+        }
+
+        sensors.cpus = sysfs::devices(root, "cpu")?
+            .into_iter()
+            .filter(|d| d.syspath().join(ATTR_CUR_FREQ).exists())
+            .map(|d| d.syspath().to_owned())
+            .collect();
+
+        if sensors == Sensors::default() {
+            return Err(PlatformError::MissingFunction(
+                "no temperature, fan or cpufreq sensors".into(),
+            ));
+        }
+        Ok(sensors)
+    }
+
+    /// Read all sensors. Anything that fails to read is left as `None`.
+    pub fn read(&self) -> SensorReadings {
+        let temp = |path: &Option<PathBuf>| {
+            path.as_ref()
+                .and_then(|p| read_attr_num::<i32>(p, "temp1_input").ok())
+                .map(|t| t as f32 / 1000.0)
+        };
+        let fan = |attr| {
+            self.fans
+                .as_ref()
+                .and_then(|p| read_attr_num::<u32>(p, attr).ok())
+        };
+
+        let freqs: Vec<u64> = self
+            .cpus
+            .iter()
+            .filter_map(|p| read_attr_num::<u64>(p, ATTR_CUR_FREQ).ok())
+            .collect();
+        let cpu_freq = (!freqs.is_empty())
+            .then(|| (freqs.iter().sum::<u64>() / freqs.len() as u64 / 1000) as u32);
+
+        SensorReadings {
+            cpu_temp: temp(&self.cpu_temp),
+            gpu_temp: temp(&self.gpu_temp),
+            cpu_fan: fan("fan1_input"),
+            gpu_fan: fan("fan2_input"),
+            cpu_freq,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Location of sysfs and discovery of the devices within it.
+//!
+//! Every path used by the sysfs backed controls in this crate is resolved
+//! against [`root()`], which is `/sys` unless `ASUSD_SYSFS_ROOT` is set. This
+//! allows asusd to be started against a fake tree built by
+//! [`crate::fixture`] on machines without ASUS hardware.
+
+use std::fs::read_dir;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use log::info;
+
+use crate::error::{PlatformError, Result};
+
+/// Environment variable that overrides the sysfs root
+pub const SYSFS_ROOT_ENV: &str = "ASUSD_SYSFS_ROOT";
+const SYSFS_DEFAULT: &str = "/sys";
+
+static ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+/// The sysfs root used by all `new()` constructors in this crate
+pub fn root() -> &'static Path {
+    ROOT.get_or_init(|| match std::env::var_os(SYSFS_ROOT_ENV) {
+        Some(path) if !path.is_empty() => {
+            info!("Using sysfs root {path:?} from {SYSFS_ROOT_ENV}");
+            PathBuf::from(path)
+        }
+        _ => PathBuf::from(SYSFS_DEFAULT),
+    })
+}
+
+/// False if running against a fake tree, in which case anything found through
+/// udev instead of [`root()`] is real hardware and should be left alone
+pub fn is_default_root() -> bool {
+    root() == Path::new(SYSFS_DEFAULT)
+}
+
+/// A device directory found in sysfs
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SysDevice {
+    syspath: PathBuf,
+    sysname: String,
+}
+
+impl SysDevice {
+    /// The resolved path of the device, `/sys/devices/...` on a real system
+    pub fn syspath(&self) -> &Path {
+        &self.syspath
+    }
+
+    pub fn sysname(&self) -> &str {
+        &self.sysname
+    }
+
+    /// Read an attribute, stripped of the trailing newline
+    pub fn attribute_value(&self, attr_name: &str) -> Option<String> {
+        crate::read_attr_string(&self.syspath, attr_name).ok()
+    }
+}
+
+/// All devices of `subsystem` under `root`. Classes are listed from
+/// `class/<subsystem>` and buses from `bus/<subsystem>/devices`, mirroring
+/// what a udev enumerator matching on the subsystem returns.
+pub fn devices(root: &Path, subsystem: &str) -> Result<Vec<SysDevice>> {
+    let class = root.join("class").join(subsystem);
+    let dir = if class.is_dir() {
+        class
+    } else {
+        root.join("bus").join(subsystem).join("devices")
+    };
+
+    let entries = match read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(PlatformError::IoPath(dir.to_string_lossy().into(), e)),
+    };
+
+    let mut devices: Vec<SysDevice> = entries
+        .flatten()
+        .map(|entry| {
+            let path = entry.path();
+            SysDevice {
+                syspath: path.canonicalize().unwrap_or(path),
+                sysname: entry.file_name().to_string_lossy().to_string(),
+            }
+        })
+        .collect();
+    devices.sort_by(|a, b| a.sysname.cmp(&b.sysname));
+    Ok(devices)
+}
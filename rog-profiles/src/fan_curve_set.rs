@@ -1,12 +1,24 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
 use log::{error, trace};
 use serde::{Deserialize, Serialize};
-use udev::Device;
 #[cfg(feature = "dbus")]
 use zbus::zvariant::Type;
 
 use crate::error::ProfileError;
 use crate::FanCurvePU;
 
+/// Write an existing attribute of the fan curve node
+pub(crate) fn write_attr(device: &Path, attr: &str, value: &str) -> std::io::Result<()> {
+    OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .open(device.join(attr))?
+        .write_all(value.as_bytes())
+}
+
 fn set_sysfs_name(string: &mut [u8], fan: char, index: usize) {
     string[3] = fan as u8;
     string[15] = char::from_digit(index as u32 + 1, 10).unwrap() as u8;
@@ -136,49 +148,47 @@ impl CurveData {
         self.fan = fan;
     }
 
-    fn set_val_from_attr(tmp: &str, device: &Device, buf: &mut [u8; 8]) {
+    fn set_val_from_attr(tmp: &str, device: &Path, buf: &mut [u8; 8]) {
         if let Some(n) = tmp.chars().nth(15) {
             let i = n.to_digit(10).unwrap() as usize;
-            let d = device.attribute_value(tmp).unwrap();
-            let d: u8 = d.to_string_lossy().parse().unwrap();
+            let d = rog_platform::read_attr_string(device, tmp).unwrap();
+            let d: u8 = d.trim().parse().unwrap();
             buf[i - 1] = d;
         }
     }
 
-    pub fn read_from_device(&mut self, device: &Device) {
-        for attr in device.attributes() {
-            let tmp = attr.name().to_string_lossy();
+    pub fn read_from_device(&mut self, device: &Path) {
+        for tmp in crate::attribute_names(device) {
             let pwm_num: char = self.fan.into();
             let pwm = format!("pwm{pwm_num}");
             if tmp.starts_with(&pwm) && tmp.ends_with("_temp") {
-                Self::set_val_from_attr(tmp.as_ref(), device, &mut self.temp);
+                Self::set_val_from_attr(&tmp, device, &mut self.temp);
             }
             if tmp.starts_with(&pwm) && tmp.ends_with("_pwm") {
-                Self::set_val_from_attr(tmp.as_ref(), device, &mut self.pwm);
+                Self::set_val_from_attr(&tmp, device, &mut self.pwm);
             }
         }
     }
 
     /// Write this curve to the device fan specified by `self.fan`
-    pub fn write_to_device(&self, device: &mut Device) -> std::io::Result<()> {
+    pub fn write_to_device(&self, device: &Path) -> std::io::Result<()> {
         let pwm_num: char = self.fan.into();
         let enable = if self.enabled { '1' } else { '2' };
 
         for (index, out) in self.pwm.iter().enumerate() {
             let pwm = pwm_str(pwm_num, index);
             trace!("writing {pwm}");
-            device.set_attribute_value(&pwm, out.to_string())?;
+            write_attr(device, &pwm, &out.to_string())?;
         }
 
         for (index, out) in self.temp.iter().enumerate() {
             let temp = temp_str(pwm_num, index);
             trace!("writing {temp}");
-            device.set_attribute_value(&temp, out.to_string())?;
+            write_attr(device, &temp, &out.to_string())?;
         }
 
         // Enable must be done *after* all points are written pwm3_enable
-        device
-            .set_attribute_value(format!("pwm{pwm_num}_enable"), enable.to_string())
+        write_attr(device, &format!("pwm{pwm_num}_enable"), &enable.to_string())
             .map_err(|e| error!("Failed to set pwm{pwm_num}_enable to {enable}: {e:?}"))
             .ok();
         Ok(())
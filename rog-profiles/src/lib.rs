@@ -1,51 +1,86 @@
 pub mod error;
 pub mod fan_curve_set;
 
+use std::fs::read_dir;
+use std::path::{Path, PathBuf};
+
 use error::ProfileError;
 use fan_curve_set::CurveData;
 use log::debug;
 use rog_platform::platform::PlatformProfile;
+use rog_platform::sysfs;
 use serde::{Deserialize, Serialize};
-pub use udev::Device;
 #[cfg(feature = "dbus")]
 use zbus::zvariant::Type;
 use zbus::zvariant::{OwnedValue, Value};
 
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
-/// Use udev system to find the fan curve path/node which is labelled with
-/// "asus_custom_fan_curve" in the kernel
-pub fn find_fan_curve_node() -> Result<Device, ProfileError> {
-    let mut enumerator = udev::Enumerator::new()?;
-    enumerator.match_subsystem("hwmon")?;
-
-    for device in enumerator.scan_devices()? {
-        if device.parent_with_subsystem("platform")?.is_some() {
-            if let Some(name) = device.attribute_value("name") {
-                if name == "asus_custom_fan_curve" {
-                    return Ok(device);
-                }
-            }
+/// Find the fan curve hwmon node which is labelled with
+/// "asus_custom_fan_curve" in the kernel, under the `rog_platform` sysfs root
+pub fn find_fan_curve_node() -> Result<PathBuf, ProfileError> {
+    find_fan_curve_node_in(sysfs::root())
+}
+
+/// As [`find_fan_curve_node`] but with an explicit sysfs root
+pub fn find_fan_curve_node_in(root: &Path) -> Result<PathBuf, ProfileError> {
+    let platform = root.join("devices/platform").canonicalize().ok();
+    let devices =
+        sysfs::devices(root, "hwmon").map_err(|e| ProfileError::NotFound(format!("hwmon: {e}")))?;
+
+    for device in devices {
+        // The node must have a platform device parent
+        if !platform
+            .as_ref()
+            .is_some_and(|p| device.syspath().starts_with(p))
+        {
+            continue;
+        }
+        if device.attribute_value("name").as_deref() == Some("asus_custom_fan_curve") {
+            return Ok(device.syspath().to_owned());
         }
     }
 
     Err(ProfileError::NotSupported)
 }
 
+/// The names of all attributes of a sysfs device
+pub(crate) fn attribute_names(device: &Path) -> Vec<String> {
+    read_dir(device)
+        .map(|dir| {
+            dir.flatten()
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Check if any fan is currently running a custom curve instead of the
+/// platform default
+pub fn fan_curves_active() -> Result<bool, ProfileError> {
+    let device = find_fan_curve_node()?;
+    Ok(FanCurvePU::which_fans(&device).into_iter().any(|fan| {
+        let pwm_num: char = fan.into();
+        rog_platform::read_attr_string(&device, &format!("pwm{pwm_num}_enable"))
+            .is_ok_and(|v| v.trim() == "1")
+    }))
+}
+
 #[cfg_attr(
     feature = "dbus",
     derive(Type, Value, OwnedValue),
     zvariant(signature = "s")
 )]
-#[derive(Deserialize, Serialize, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[derive(Deserialize, Serialize, Default, Debug, Hash, PartialEq, Eq, Clone, Copy)]
 pub enum FanCurvePU {
+    #[default]
     CPU = 0,
     GPU = 1,
     MID = 2,
 }
 
 impl FanCurvePU {
-    fn which_fans(device: &Device) -> Vec<Self> {
+    fn which_fans(device: &Path) -> Vec<Self> {
         let mut fans = Vec::with_capacity(3);
         for fan in [
             Self::CPU,
@@ -55,9 +90,8 @@ impl FanCurvePU {
             let pwm_num: char = fan.into();
             let pwm_enable = format!("pwm{pwm_num}_enable");
             debug!("Looking for {pwm_enable}");
-            for attr in device.attributes() {
-                let tmp = attr.name().to_string_lossy();
-                if tmp.contains(&pwm_enable) {
+            for attr in attribute_names(device) {
+                if attr.contains(&pwm_enable) {
                     debug!("Found {pwm_enable}");
                     fans.push(fan);
                 }
@@ -100,12 +134,6 @@ impl std::str::FromStr for FanCurvePU {
     }
 }
 
-impl Default for FanCurvePU {
-    fn default() -> Self {
-        Self::CPU
-    }
-}
-
 /// Main purpose of `FanCurves` is to enable restoring state on system boot
 #[cfg_attr(feature = "dbus", derive(Type))]
 #[derive(Deserialize, Serialize, Debug, Default)]
@@ -127,9 +155,9 @@ impl FanCurveProfiles {
     pub fn read_from_dev_profile(
         &mut self,
         profile: PlatformProfile,
-        device: &Device,
+        device: &Path,
     ) -> Result<(), ProfileError> {
-        let fans = Self::supported_fans()?;
+        let fans = FanCurvePU::which_fans(device);
         let mut curves = Vec::with_capacity(3);
 
         for fan in fans {
@@ -160,14 +188,14 @@ impl FanCurveProfiles {
     pub fn set_active_curve_to_defaults(
         &mut self,
         profile: PlatformProfile,
-        device: &mut Device,
+        device: &Path,
     ) -> Result<(), ProfileError> {
-        let fans = Self::supported_fans()?;
+        let fans = FanCurvePU::which_fans(device);
         // Do reset for all
         for fan in fans {
             let pwm_num: char = fan.into();
             let pwm = format!("pwm{pwm_num}_enable");
-            device.set_attribute_value(&pwm, "3")?;
+            fan_curve_set::write_attr(device, &pwm, "3")?;
         }
         self.read_from_dev_profile(profile, device)?;
         Ok(())
@@ -178,7 +206,7 @@ impl FanCurveProfiles {
     pub fn write_profile_curve_to_platform(
         &mut self,
         profile: PlatformProfile,
-        device: &mut Device,
+        device: &Path,
     ) -> Result<(), ProfileError> {
         let fans = match profile {
             PlatformProfile::Balanced => &mut self.balanced,
@@ -351,3 +379,49 @@ impl FanCurveProfiles {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{read_to_string, remove_dir_all};
+
+    use rog_platform::fixture::SysfsFixture;
+    use rog_platform::platform::PlatformProfile;
+
+    use crate::{find_fan_curve_node_in, FanCurvePU, FanCurveProfiles};
+
+    #[test]
+    fn fan_curves_from_fixture() {
+        let root =
+            std::env::temp_dir().join(format!("rog-profiles-fixture-{}", std::process::id()));
+        remove_dir_all(&root).ok();
+        SysfsFixture::from_ron(include_str!("../../rog-platform/data/sysfs/ga402.ron"))
+            .unwrap()
+            .create(&root)
+            .unwrap();
+
+        let node = find_fan_curve_node_in(&root).unwrap();
+        assert_eq!(FanCurvePU::which_fans(&node), vec![
+            FanCurvePU::CPU,
+            FanCurvePU::GPU
+        ]);
+
+        let mut profiles = FanCurveProfiles::default();
+        profiles
+            .read_from_dev_profile(PlatformProfile::Balanced, &node)
+            .unwrap();
+        let cpu = profiles
+            .get_fan_curve_for(&PlatformProfile::Balanced, FanCurvePU::CPU)
+            .unwrap();
+        assert_eq!(cpu.temp, [30, 40, 50, 60, 70, 80, 90, 100]);
+        assert_eq!(cpu.pwm, [8, 10, 20, 35, 55, 80, 100, 130]);
+
+        profiles.set_profile_fan_curve_enabled(PlatformProfile::Balanced, FanCurvePU::CPU, true);
+        profiles
+            .write_profile_curve_to_platform(PlatformProfile::Balanced, &node)
+            .unwrap();
+        assert_eq!(read_to_string(node.join("pwm1_enable")).unwrap(), "1");
+        assert_eq!(read_to_string(node.join("pwm2_enable")).unwrap(), "2");
+
+        remove_dir_all(root).ok();
+    }
+}
@@ -1,12 +1,15 @@
 use std::fmt::Display;
 use std::str::FromStr;
 
+use rog_ene::EneController;
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "dbus")]
 use zbus::zvariant::{OwnedValue, Type, Value};
 
 use crate::error::Error;
-use crate::scsi::{apply_task, dir_task, mode_task, rgb_task, save_task, speed_task};
+
+/// The count of colours in an effect
+pub const EFFECT_COLOURS: usize = 4;
 
 #[cfg_attr(feature = "dbus", derive(Type, Value, OwnedValue))]
 #[derive(Debug, Clone, PartialEq, Eq, Copy, Deserialize, Serialize)]
@@ -46,6 +49,17 @@ impl From<&[u8; 3]> for Colour {
     }
 }
 
+impl Colour {
+    /// Colours are stored on the device in RBG order
+    fn from_device(rbg: [u8; 3]) -> Self {
+        Self {
+            r: rbg[0],
+            g: rbg[2],
+            b: rbg[1],
+        }
+    }
+}
+
 impl From<Colour> for [u8; 3] {
     fn from(c: Colour) -> Self {
         [
@@ -332,6 +346,122 @@ impl AuraEffect {
     }
 }
 
+impl AuraEffect {
+    /// Read the effect the device is currently showing, such as after it was
+    /// plugged in
+    pub fn read_from_device(ctrl: &mut dyn EneController) -> std::io::Result<Self> {
+        let mode = ctrl.mode()?;
+        let speed = ctrl.speed()?;
+        let direction = ctrl.direction()?;
+        let colours = ctrl.effect_colours(EFFECT_COLOURS)?;
+        let colour = |i: usize| Colour::from_device(colours[i]);
+        Ok(Self {
+            mode: mode.into(),
+            speed: speed.into(),
+            direction: direction.into(),
+            colour1: colour(0),
+            colour2: colour(1),
+            colour3: colour(2),
+            colour4: colour(3),
+        })
+    }
+
+    /// Write the effect and apply it. This also returns the device to the
+    /// effect engine if it was showing direct colours. The effect is not
+    /// saved, use `EneController::save()` for that.
+    pub fn write_to(&self, ctrl: &mut dyn EneController) -> std::io::Result<()> {
+        ctrl.set_direct(false)?;
+        ctrl.set_mode((self.mode as u8).min(13))?;
+        for (led, colour) in [
+            self.colour1, self.colour2, self.colour3, self.colour4,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            ctrl.set_effect_colour(led, colour.into())?;
+        }
+
+        if self.writes_speed() {
+            // 0-4, fast to slow
+            ctrl.set_speed((self.speed as u8).min(4))?;
+        }
+        if self.writes_direction() {
+            // 0 = forward, 1 = backward
+            ctrl.set_direction((self.direction as u8).min(1))?;
+        }
+
+        ctrl.apply()
+    }
+
+    /// If `other` looks the same on the device. The speed and direction are
+    /// only compared if the mode writes them, the device keeps whatever an
+    /// earlier mode left there.
+    pub fn shows_same(&self, other: &Self) -> bool {
+        self.mode == other.mode
+            && [
+                self.colour1, self.colour2, self.colour3, self.colour4,
+            ] == [
+                other.colour1, other.colour2, other.colour3, other.colour4,
+            ]
+            && (!self.writes_speed() || self.speed == other.speed)
+            && (!self.writes_direction() || self.direction == other.direction)
+    }
+
+    fn writes_speed(&self) -> bool {
+        !matches!(self.mode, AuraMode::Static | AuraMode::Off)
+    }
+
+    fn writes_direction(&self) -> bool {
+        matches!(
+            self.mode,
+            AuraMode::RainbowWave
+                | AuraMode::ChaseFade
+                | AuraMode::RainbowCycleChaseFade
+                | AuraMode::Chase
+                | AuraMode::RainbowCycleChase
+                | AuraMode::RainbowCycleWave
+                | AuraMode::RainbowPulseChase
+        )
+    }
+}
+
+/// Check if the device is showing direct colours instead of an effect
+pub fn read_direct_mode(ctrl: &mut dyn EneController) -> std::io::Result<bool> {
+    ctrl.direct()
+}
+
+/// Read the colour of each of `led_count` LEDs as set by
+/// `write_direct_colours()`
+pub fn read_direct_colours(
+    ctrl: &mut dyn EneController,
+    led_count: usize,
+) -> std::io::Result<Vec<Colour>> {
+    Ok(ctrl
+        .direct_colours(led_count)?
+        .into_iter()
+        .map(Colour::from_device)
+        .collect())
+}
+
+/// Set the colour of each of `led_count` LEDs directly, bypassing the effect
+/// engine. LEDs without a colour given are turned off. Writing an `AuraEffect`
+/// returns the device to the effect engine.
+pub fn write_direct_colours(
+    ctrl: &mut dyn EneController,
+    colours: &[Colour],
+    led_count: usize,
+) -> std::io::Result<()> {
+    ctrl.set_direct(true)?;
+    for led in 0..led_count {
+        let colour = colours
+            .get(led)
+            .copied()
+            .unwrap_or(Colour { r: 0, g: 0, b: 0 });
+        ctrl.set_direct_colour(led, colour.into())?;
+    }
+    ctrl.apply()
+}
+
 impl Default for AuraEffect {
     fn default() -> Self {
         Self {
@@ -360,38 +490,64 @@ impl Display for AuraEffect {
     }
 }
 
-impl From<&AuraEffect> for Vec<sg::Task> {
-    fn from(effect: &AuraEffect) -> Self {
-        let mut tasks = Vec::new();
-
-        tasks.append(&mut vec![
-            mode_task(effect.mode as u8),
-            rgb_task(0, &effect.colour1.into()),
-            rgb_task(1, &effect.colour2.into()),
-            rgb_task(2, &effect.colour3.into()),
-            rgb_task(3, &effect.colour4.into()),
-        ]);
+#[cfg(test)]
+mod tests {
+    use rog_ene::memory::MemoryEne;
+    use rog_ene::{EneController, ENE_REG_COLORS_EFFECT_V2};
+
+    use super::{
+        read_direct_colours, write_direct_colours, AuraEffect, AuraMode, Colour, Direction, Speed,
+    };
+
+    #[test]
+    fn effect_round_trip() {
+        let mut ene = MemoryEne::default();
+        let effect = AuraEffect {
+            mode: AuraMode::RainbowWave,
+            colour2: Colour { r: 1, g: 2, b: 3 },
+            ..Default::default()
+        };
+        effect.write_to(&mut ene).unwrap();
+        // Stored as RBG
+        assert_eq!(ene.register(ENE_REG_COLORS_EFFECT_V2 + 4), 3);
+        assert_eq!(AuraEffect::read_from_device(&mut ene).unwrap(), effect);
+    }
 
-        if !matches!(effect.mode, AuraMode::Static | AuraMode::Off) {
-            tasks.push(speed_task(effect.speed as u8));
-        }
-        if matches!(
-            effect.mode,
-            AuraMode::RainbowWave
-                | AuraMode::ChaseFade
-                | AuraMode::RainbowCycleChaseFade
-                | AuraMode::Chase
-                | AuraMode::RainbowCycleChase
-                | AuraMode::RainbowCycleWave
-                | AuraMode::RainbowPulseChase
-        ) {
-            tasks.push(dir_task(effect.direction as u8));
+    #[test]
+    fn static_ignores_left_over_speed() {
+        let mut ene = MemoryEne::default();
+        AuraEffect {
+            mode: AuraMode::RainbowWave,
+            speed: Speed::Fastest,
+            direction: Direction::Reverse,
+            ..Default::default()
         }
+        .write_to(&mut ene)
+        .unwrap();
+        let effect = AuraEffect::default();
+        effect.write_to(&mut ene).unwrap();
+
+        let current = AuraEffect::read_from_device(&mut ene).unwrap();
+        assert_ne!(current, effect);
+        assert!(effect.shows_same(&current));
+        assert!(!effect.shows_same(&AuraEffect {
+            colour1: Colour { r: 0, g: 0, b: 1 },
+            ..current
+        }));
+    }
 
-        tasks.append(&mut vec![
-            apply_task(),
-            save_task(),
+    #[test]
+    fn direct_colours() {
+        let mut ene = MemoryEne::default();
+        let red = Colour { r: 255, g: 0, b: 0 };
+        write_direct_colours(&mut ene, &[red], 2).unwrap();
+        assert!(ene.direct().unwrap());
+        assert_eq!(read_direct_colours(&mut ene, 2).unwrap(), vec![
+            red,
+            Colour { r: 0, g: 0, b: 0 }
         ]);
-        tasks
+
+        AuraEffect::default().write_to(&mut ene).unwrap();
+        assert!(!ene.direct().unwrap());
     }
 }
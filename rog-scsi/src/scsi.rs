@@ -1,23 +1,21 @@
 extern crate sg;
 
-pub use sg::Task;
+use std::io::{Error, ErrorKind, Result};
+use std::ops::Range;
 
-static ENE_APPLY_VAL: u8 = 0x01; // Value for Apply Changes Register
-static ENE_SAVE_VAL: u8 = 0xaa;
+use rog_ene::{EneController, EneVersion};
+use sg::{Device, Task};
 
-static ENE_REG_MODE: u32 = 0x8021; // Mode Selection Register
-static ENE_REG_SPEED: u32 = 0x8022; // Speed Control Register
-static ENE_REG_DIRECTION: u32 = 0x8023; // Direction Control Register
+static ENE_CMD_READ: u8 = 0x52;
+static ENE_CMD_WRITE: u8 = 0x53;
 
-static ENE_REG_APPLY: u32 = 0x80a0;
-static _ENE_REG_COLORS_DIRECT_V2: u32 = 0x8100; // to read the colurs
-static ENE_REG_COLORS_EFFECT_V2: u32 = 0x8160;
+const MAX_TRANSFER: usize = u8::MAX as usize;
 
-fn data(reg: u32, arg_count: u8) -> [u8; 16] {
+fn cdb(cmd: u8, reg: u16, arg_count: u8) -> [u8; 16] {
     let mut cdb = [0u8; 16];
     cdb[0] = 0xec;
     cdb[1] = 0x41;
-    cdb[2] = 0x53;
+    cdb[2] = cmd;
     cdb[3] = ((reg >> 8) & 0x00ff) as u8;
     cdb[4] = (reg & 0x00ff) as u8;
     cdb[5] = 0x00;
@@ -34,47 +32,90 @@ fn data(reg: u32, arg_count: u8) -> [u8; 16] {
     cdb
 }
 
-pub(crate) fn rgb_task(led: u32, rgb: &[u8; 3]) -> Task {
-    let mut task = Task::new();
-    task.set_cdb(data(led * 3 + ENE_REG_COLORS_EFFECT_V2, 3).as_slice());
-    task.set_data(rgb, sg::Direction::ToDevice);
-    task
+/// An ENE controller reached through vendor commands on a SCSI generic device
+pub struct ScsiEne {
+    device: Device,
+    version: EneVersion,
 }
 
-/// 0-13
-pub(crate) fn mode_task(mode: u8) -> Task {
-    let mut task = Task::new();
-    task.set_cdb(data(ENE_REG_MODE, 1).as_slice());
-    task.set_data(&[mode.min(13)], sg::Direction::ToDevice);
-    task
+impl ScsiEne {
+    pub fn open(path: &str, version: EneVersion) -> Result<Self> {
+        Ok(Self {
+            device: Device::open(path)?,
+            version,
+        })
+    }
 }
 
-/// 0-4, fast to slow
-pub(crate) fn speed_task(speed: u8) -> Task {
-    let mut task = Task::new();
-    task.set_cdb(data(ENE_REG_SPEED, 1).as_slice());
-    task.set_data(&[speed.min(4)], sg::Direction::ToDevice);
-    task
+/// The CDB holds the length of the data in one byte, so longer reads and
+/// writes are split. Gives the register and the range of the data for each.
+fn transfers(reg: u16, len: usize) -> Result<Vec<(u16, Range<usize>)>> {
+    (0..len)
+        .step_by(MAX_TRANSFER)
+        .map(|start| {
+            let reg = u16::try_from(start)
+                .ok()
+                .and_then(|offset| reg.checked_add(offset))
+                .ok_or_else(|| Error::from(ErrorKind::InvalidInput))?;
+            Ok((reg, start..len.min(start + MAX_TRANSFER)))
+        })
+        .collect()
 }
 
-/// 0 = forward, 1 = backward
-pub(crate) fn dir_task(mode: u8) -> Task {
-    let mut task = Task::new();
-    task.set_cdb(data(ENE_REG_DIRECTION, 1).as_slice());
-    task.set_data(&[mode.min(1)], sg::Direction::ToDevice);
-    task
-}
+impl EneController for ScsiEne {
+    fn read_registers(&mut self, reg: u16, buf: &mut [u8]) -> Result<()> {
+        for (reg, range) in transfers(reg, buf.len())? {
+            let buf = &mut buf[range];
+            let mut task = Task::new();
+            task.set_cdb(cdb(ENE_CMD_READ, reg, buf.len() as u8).as_slice());
+            task.set_data(&vec![0; buf.len()], sg::Direction::FromDevice);
+            self.device.perform(&task)?;
+            let data = task.data();
+            let len = data.len().min(buf.len());
+            buf[..len].copy_from_slice(&data[..len]);
+        }
+        Ok(())
+    }
+
+    fn write_registers(&mut self, reg: u16, data: &[u8]) -> Result<()> {
+        for (reg, range) in transfers(reg, data.len())? {
+            let data = &data[range];
+            let mut task = Task::new();
+            task.set_cdb(cdb(ENE_CMD_WRITE, reg, data.len() as u8).as_slice());
+            task.set_data(data, sg::Direction::ToDevice);
+            self.device.perform(&task)?;
+        }
+        Ok(())
+    }
 
-pub(crate) fn apply_task() -> Task {
-    let mut task = Task::new();
-    task.set_cdb(data(ENE_REG_APPLY, 1).as_slice());
-    task.set_data(&[ENE_APPLY_VAL], sg::Direction::ToDevice);
-    task
+    fn version(&self) -> EneVersion {
+        self.version
+    }
 }
 
-pub(crate) fn save_task() -> Task {
-    let mut task = Task::new();
-    task.set_cdb(data(ENE_REG_APPLY, 1).as_slice());
-    task.set_data(&[ENE_SAVE_VAL], sg::Direction::ToDevice);
-    task
+#[cfg(test)]
+mod tests {
+    use super::{cdb, transfers, ENE_CMD_READ, ENE_CMD_WRITE};
+
+    #[test]
+    fn read_and_write_cdb() {
+        let write = cdb(ENE_CMD_WRITE, 0x8163, 3);
+        assert_eq!(&write[..5], &[0xec, 0x41, 0x53, 0x81, 0x63]);
+        assert_eq!(write[13], 3);
+
+        let read = cdb(ENE_CMD_READ, 0x8021, 1);
+        assert_eq!(&read[..5], &[0xec, 0x41, 0x52, 0x80, 0x21]);
+    }
+
+    #[test]
+    fn long_transfers_are_split() {
+        assert_eq!(transfers(0x8100, 12).unwrap(), vec![(0x8100, 0..12)]);
+        assert_eq!(transfers(0x8100, 600).unwrap(), vec![
+            (0x8100, 0..255),
+            (0x81ff, 255..510),
+            (0x82fe, 510..600)
+        ]);
+        assert!(transfers(0x8100, 0).unwrap().is_empty());
+        assert!(transfers(0xff00, 600).is_err());
+    }
 }
@@ -0,0 +1,76 @@
+use std::path::Path;
+
+/// The brightness of each LED in display rows. Positions without an LED are
+/// `None`. Odd and even rows are staggered by half an LED on the display, this
+/// is not reflected in the grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedGrid {
+    width: usize,
+    height: usize,
+    cells: Vec<Option<u8>>,
+}
+
+impl LedGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![None; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.cells[y * self.width + x]
+    }
+
+    /// Set an LED, positions outside the grid are ignored
+    pub fn set(&mut self, x: usize, y: usize, value: u8) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = Some(value);
+        }
+    }
+
+    /// One line per row, two hex digits per LED and two spaces where there is
+    /// no LED
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for row in self.cells.chunks(self.width.max(1)) {
+            let line: String = row
+                .iter()
+                .map(|c| c.map_or("  ".to_owned(), |v| format!("{v:02x}")))
+                .collect();
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Write as a greyscale PNG, one pixel per LED
+    pub fn write_png(&self, path: &Path) -> std::io::Result<()> {
+        let data: Vec<u8> = self.cells.iter().map(|c| c.unwrap_or_default()).collect();
+        let raster = pix::Raster::<pix::gray::SGray8>::with_u8_buffer(
+            self.width as u32, self.height as u32, data,
+        );
+        let mut out = Vec::new();
+        let mut encoder = png_pong::Encoder::new(&mut out).into_step_enc();
+        let step = png_pong::Step {
+            raster: png_pong::PngRaster::Gray8(raster),
+            delay: 0,
+        };
+        encoder
+            .encode(&step)
+            .map_err(|e| std::io::Error::other(format!("{e:?}")))?;
+        std::fs::write(path, out)
+    }
+}
@@ -1,27 +1,13 @@
-use rog_anime::AnimeType;
+use rog_anime::{AnimeType, PacketRow, USB_PREFIX1, USB_PREFIX2, USB_PREFIX3};
 
-use self::map_ga401::GA401;
-use self::map_ga402::GA402;
-use self::map_gu604::GU604;
+/// The LED grid decoded from the USB packets
+mod grid;
+pub use grid::*;
 
-mod map_ga401;
-mod map_ga402;
-mod map_gu604;
-
-#[derive(Clone, Copy)]
-pub struct Row(
-    /// The USB packet index, this is mapped to the 4th byte (idx = 3) and is
-    /// one of (in order of packets): 1. `0x01`
-    /// 2. `0x74`
-    /// 3. `0xe7`
-    pub u8,
-    /// Starting index in that packet
-    pub usize,
-    /// The length to read inclusive
-    pub usize,
-    /// Offset to the right by how many LEDs
-    pub i32,
-);
+/// The first three bytes of every packet carrying display data
+const DATA_PREFIX: [u8; 3] = [
+    0x5e, 0xc0, 0x02,
+];
 
 #[derive(Clone, Copy)]
 pub struct LedShape {
@@ -32,42 +18,174 @@ pub struct LedShape {
 }
 
 pub struct AniMatrix {
-    rows: Vec<Row>,
+    rows: Vec<PacketRow>,
+    /// The display row of each packet row. A short row at the start of a later
+    /// packet continues the display row before it.
+    display_rows: Vec<usize>,
+    /// The prefix of the final pane, a frame is complete once it is received
+    last_prefix: [u8; 7],
     led_shape: LedShape,
 }
 
 impl AniMatrix {
     pub fn new(model: AnimeType) -> Self {
-        let led_shape = match model {
-            AnimeType::GA401 => LedShape {
-                vertical: 2,
-                horizontal: 5,
-            },
-            AnimeType::GA402 | AnimeType::Unsupported => LedShape {
-                vertical: 2,
-                horizontal: 5,
-            },
-            AnimeType::GU604 => LedShape {
-                vertical: 2,
-                horizontal: 5,
-            },
+        let led_shape = LedShape {
+            vertical: 2,
+            horizontal: 5,
         };
 
-        // Do a hard mapping of each (derived from wireshardk captures)
-        let rows = match model {
-            AnimeType::GA401 => GA401.to_vec(),
-            AnimeType::GA402 | AnimeType::Unsupported => GA402.to_vec(),
-            AnimeType::GU604 => GU604.to_vec(),
-        };
+        // Do a hard mapping of each (derived from wireshardk captures), these are
+        // stored in the `packet_rows` of `anime_support.ron`
+        let rows = model.descriptor().packet_rows.clone();
 
-        Self { rows, led_shape }
-    }
+        let mut continued = 0;
+        let display_rows = rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                if row.index != 0x01 && row.start < 10 && row.len < 15 {
+                    continued += 1;
+                }
+                i - continued
+            })
+            .collect();
+        let last_prefix = [
+            USB_PREFIX1,
+            USB_PREFIX2,
+            USB_PREFIX3,
+        ]
+        .get(model.descriptor().panes.saturating_sub(1))
+        .copied()
+        .unwrap_or(USB_PREFIX3);
 
-    pub fn rows(&self) -> &[Row] {
-        &self.rows
+        Self {
+            rows,
+            display_rows,
+            last_prefix,
+            led_shape,
+        }
     }
 
     pub fn led_shape(&self) -> LedShape {
         self.led_shape
     }
+
+    /// The LED positions of this model, as `(x, y)`
+    fn positions(&self) -> impl Iterator<Item = (usize, usize, &PacketRow)> + '_ {
+        self.rows
+            .iter()
+            .zip(self.display_rows.iter())
+            .flat_map(|(row, y)| {
+                (0..=row.len).map(move |x| ((row.offset.max(0) as usize) + x, *y, row))
+            })
+    }
+
+    /// An all-off grid of the size and shape of this model
+    pub fn new_grid(&self) -> LedGrid {
+        let width = self.positions().map(|(x, ..)| x + 1).max().unwrap_or(0);
+        let height = self.display_rows.last().map_or(0, |y| y + 1);
+        let mut grid = LedGrid::new(width, height);
+        for (x, y, _) in self.positions() {
+            grid.set(x, y, 0);
+        }
+        grid
+    }
+
+    /// Copy the LED values from a packet in to `grid`. Returns `true` if this
+    /// was the final packet of a frame. Packets that are not display data are
+    /// ignored, as are rows past the end of a short packet.
+    pub fn decode(&self, packet: &[u8], grid: &mut LedGrid) -> bool {
+        if packet.len() < 4 || packet[..3] != DATA_PREFIX {
+            return false;
+        }
+        let index = packet[3];
+        for (row, y) in self.rows.iter().zip(self.display_rows.iter()) {
+            if row.index != index {
+                continue;
+            }
+            // A short packet only sets the LEDs it has data for
+            let data = packet.get(row.start..).unwrap_or_default();
+            for (x, b) in data.iter().take(row.len + 1).enumerate() {
+                grid.set(row.offset.max(0) as usize + x, *y, *b);
+            }
+        }
+        packet.len() >= 7 && packet[..7] == self.last_prefix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use rog_anime::{AnimeDataBuffer, AnimePacketType, AnimeType};
+
+    use super::{AniMatrix, DATA_PREFIX};
+
+    /// Fill a buffer with a known pattern, convert it to packets as asusd
+    /// does, then decode. Set `UPDATE_GOLDEN` to rewrite the golden files
+    /// after an intended geometry change.
+    fn check_golden(model: AnimeType, name: &str) {
+        let mut buffer = AnimeDataBuffer::new(model);
+        for (i, b) in buffer.data_mut().iter_mut().enumerate() {
+            *b = (i % 255) as u8 + 1;
+        }
+        let packets = AnimePacketType::try_from(buffer).unwrap();
+
+        let matrix = AniMatrix::new(model);
+        let mut grid = matrix.new_grid();
+        let complete: Vec<bool> = packets
+            .iter()
+            .map(|pkt| matrix.decode(pkt, &mut grid))
+            .collect();
+        assert_eq!(complete.last(), Some(&true));
+        assert!(!complete[..complete.len() - 1].contains(&true));
+
+        let path = format!("{}/data/golden/{name}.txt", env!("CARGO_MANIFEST_DIR"));
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            fs::write(&path, grid.to_text()).unwrap();
+        }
+        assert_eq!(grid.to_text(), fs::read_to_string(&path).unwrap());
+    }
+
+    #[test]
+    fn golden_ga401() {
+        check_golden(AnimeType::GA401, "ga401");
+    }
+
+    #[test]
+    fn golden_ga402() {
+        check_golden(AnimeType::GA402, "ga402");
+    }
+
+    #[test]
+    fn golden_gu604() {
+        check_golden(AnimeType::GU604, "gu604");
+    }
+
+    #[test]
+    fn ignores_other_packets() {
+        let matrix = AniMatrix::new(AnimeType::GA402);
+        let mut grid = matrix.new_grid();
+        let blank = grid.clone();
+        let mut brightness = [0u8; 640];
+        brightness[..4].copy_from_slice(&[
+            0x5e, 0xc0, 0x04, 0x03,
+        ]);
+        assert!(!matrix.decode(&brightness, &mut grid));
+        assert_eq!(grid, blank);
+    }
+
+    #[test]
+    fn short_packets() {
+        let matrix = AniMatrix::new(AnimeType::GA402);
+        let mut grid = matrix.new_grid();
+        let row = &matrix.rows[0];
+        for len in 4..row.start + 2 {
+            let mut packet = vec![0xff; len];
+            packet[..3].copy_from_slice(&DATA_PREFIX);
+            packet[3] = row.index;
+            assert!(!matrix.decode(&packet, &mut grid));
+        }
+        assert_ne!(grid, matrix.new_grid());
+    }
 }
@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs::File;
+
+use rog_aura::{AuraModeNum, Colour};
+use uhid_virt::UHIDDevice;
+
+use crate::virt_hid::{create_device, read_report, vendor_report_descriptor};
+
+/// The N-KEY keyboard found on most 2021+ laptops
+pub const PROD_ID_NKEY: u16 = 0x19b6;
+const REPORT_ID: u8 = 0x5d;
+/// Per-key packets hold up to 18 LEDs from byte 9
+pub const PER_KEY_GROUP_LEN: usize = 18;
+pub const PER_KEY_GROUPS: usize = 12;
+
+/// A builtin mode as written by `AuraEffect` for one zone
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ZoneEffect {
+    pub mode: AuraModeNum,
+    pub colour1: Colour,
+    pub colour2: Colour,
+    pub speed: u8,
+    pub direction: u8,
+}
+
+impl ZoneEffect {
+    fn from_packet(pkt: &[u8]) -> Self {
+        Self {
+            mode: AuraModeNum::from(pkt[3]),
+            colour1: colour(&pkt[4..7]),
+            speed: pkt[7],
+            direction: pkt[8],
+            colour2: colour(&pkt[10..13]),
+        }
+    }
+}
+
+fn colour(rgb: &[u8]) -> Colour {
+    Colour {
+        r: rgb[0],
+        g: rgb[1],
+        b: rgb[2],
+    }
+}
+
+/// The state of the keyboard as decoded from the packets `rog-aura` builds
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AuraState {
+    /// Builtin effects keyed by zone number, `0` is the whole keyboard
+    pub effects: BTreeMap<u8, ZoneEffect>,
+    /// Each LED group of per-key mode, `None` while a builtin mode is active
+    pub per_key: Option<Vec<[Colour; PER_KEY_GROUP_LEN]>>,
+    /// The four power state bytes from `LaptopAuraPower::to_bytes()`
+    pub power: [u8; 4],
+    pub sets: u32,
+    pub applies: u32,
+}
+
+impl AuraState {
+    /// Update from one packet. Returns `false` if the packet was not
+    /// recognised.
+    pub fn decode(&mut self, pkt: &[u8]) -> bool {
+        if pkt.len() < 17 || pkt[0] != REPORT_ID {
+            return false;
+        }
+        match pkt[1] {
+            0xb3 => {
+                self.per_key = None;
+                self.effects.insert(pkt[2], ZoneEffect::from_packet(pkt));
+            }
+            0xb4 => self.applies += 1,
+            0xb5 => self.sets += 1,
+            0xbd if pkt[2] == 0x01 => self.power.copy_from_slice(&pkt[3..7]),
+            0xbc => self.decode_per_key(pkt),
+            _ => return false,
+        }
+        true
+    }
+
+    fn decode_per_key(&mut self, pkt: &[u8]) {
+        let groups = self.per_key.get_or_insert_with(|| {
+            vec![[Colour { r: 0, g: 0, b: 0 }; PER_KEY_GROUP_LEN]; PER_KEY_GROUPS]
+        });
+        // The init message is all zero after the mode byte and clears the keys
+        if pkt[2..].iter().all(|b| *b == 0) {
+            for group in groups.iter_mut() {
+                *group = [Colour { r: 0, g: 0, b: 0 }; PER_KEY_GROUP_LEN];
+            }
+            return;
+        }
+        // Zoned keyboards send every zone in one packet as group 0
+        let group = if pkt[2] == 0x01 {
+            0
+        } else {
+            (pkt[6] >> 4) as usize
+        };
+        if let Some(group) = groups.get_mut(group) {
+            for (led, rgb) in group.iter_mut().zip(pkt[9..].chunks_exact(3)) {
+                *led = colour(rgb);
+            }
+        }
+    }
+}
+
+impl Display for AuraState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "power: {:02x?}, sets: {}, applies: {}",
+            self.power, self.sets, self.applies
+        )?;
+        if let Some(groups) = &self.per_key {
+            let lit = groups
+                .iter()
+                .flatten()
+                .filter(|c| c.r != 0 || c.g != 0 || c.b != 0)
+                .count();
+            return write!(f, ", per-key: {lit} LEDs lit");
+        }
+        for (zone, effect) in &self.effects {
+            write!(
+                f,
+                ", zone {zone}: {} {:?} {:?} speed {} direction {}",
+                effect.mode, effect.colour1, effect.colour2, effect.speed, effect.direction
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub struct VirtAuraKeyboard {
+    device: UHIDDevice<File>,
+    pub state: AuraState,
+}
+
+impl VirtAuraKeyboard {
+    pub fn new() -> Self {
+        Self {
+            device: create_device(
+                "ROG_Virtual N-KEY Device",
+                PROD_ID_NKEY,
+                vendor_report_descriptor(REPORT_ID, 0x79, 63),
+            ),
+            state: AuraState::default(),
+        }
+    }
+
+    /// Block for the next packet and decode it. Returns `true` if the state
+    /// changed.
+    pub fn read(&mut self) -> bool {
+        let Some(data) = read_report(&mut self.device) else {
+            return false;
+        };
+        let last = self.state.clone();
+        if !self.state.decode(&data) {
+            println!("Unknown aura packet: {:02x?}", &data[..data.len().min(16)]);
+        }
+        self.state != last
+    }
+}
+
+impl Default for VirtAuraKeyboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
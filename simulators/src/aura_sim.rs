@@ -0,0 +1,101 @@
+use std::env;
+use std::error::Error;
+
+use rog_aura::Colour;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+
+mod aura;
+mod virt_hid;
+use aura::*;
+
+const KEY_SIZE: u32 = 40;
+
+fn colour(c: Colour) -> Color {
+    Color::RGB(c.r, c.g, c.b)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().collect();
+    if args.iter().skip(1).any(|a| a != "--headless") {
+        println!("Optional arg is --headless");
+        return Ok(());
+    }
+
+    let mut dev = VirtAuraKeyboard::new();
+
+    // Anything else was rejected above
+    if args.len() > 1 {
+        loop {
+            if dev.read() {
+                println!("{}", dev.state);
+            }
+        }
+    }
+
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+    let window = video_subsystem
+        .window(
+            "Aura keyboard simulator",
+            KEY_SIZE * PER_KEY_GROUP_LEN as u32,
+            KEY_SIZE * PER_KEY_GROUPS as u32,
+        )
+        .position_centered()
+        .build()?;
+    let mut canvas = window.into_canvas().build()?;
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        // Blocking, the window only updates as packets arrive
+        if dev.read() {
+            println!("{}", dev.state);
+        }
+
+        canvas.set_draw_color(Color::RGB(0, 0, 0));
+        canvas.clear();
+        if let Some(groups) = &dev.state.per_key {
+            // One row per LED group, these are not the physical rows
+            for (y, group) in groups.iter().enumerate() {
+                for (x, led) in group.iter().enumerate() {
+                    canvas.set_draw_color(colour(*led));
+                    canvas.fill_rect(Rect::new(
+                        (x as u32 * KEY_SIZE) as i32 + 2,
+                        (y as u32 * KEY_SIZE) as i32 + 2,
+                        KEY_SIZE - 4,
+                        KEY_SIZE - 4,
+                    ))?;
+                }
+            }
+        } else {
+            // A horizontal band per zone in the primary colour
+            let count = dev.state.effects.len().max(1) as u32;
+            let height = KEY_SIZE * PER_KEY_GROUPS as u32 / count;
+            for (i, effect) in dev.state.effects.values().enumerate() {
+                canvas.set_draw_color(colour(effect.colour1));
+                canvas.fill_rect(Rect::new(
+                    0,
+                    (i as u32 * height) as i32,
+                    KEY_SIZE * PER_KEY_GROUP_LEN as u32,
+                    height,
+                ))?;
+            }
+        }
+        canvas.present();
+
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,10 +1,11 @@
 use std::env;
 use std::error::Error;
+use std::path::PathBuf;
 use std::str::FromStr;
 
 use log::error;
 use rog_anime::usb::{PROD_ID, VENDOR_ID};
-use rog_anime::{AnimeType, USB_PREFIX2};
+use rog_anime::{anime_descriptors, AnimeType};
 use sdl2::event::Event;
 use sdl2::keyboard::Keycode;
 use sdl2::pixels::Color;
@@ -12,18 +13,18 @@ use sdl2::rect::Rect;
 use uhid_virt::{Bus, CreateParams, UHIDDevice};
 
 mod animatrix;
+mod trace;
 use animatrix::*;
+use trace::{read_trace, TraceWriter};
 
 pub struct VirtAnimeMatrix {
     device: UHIDDevice<std::fs::File>,
-    buffer: [u8; 640],
     animatrix: AniMatrix,
 }
 
 impl VirtAnimeMatrix {
     pub fn new(model: AnimeType) -> Self {
         VirtAnimeMatrix {
-            buffer: [0; 640],
             animatrix: AniMatrix::new(model),
             device: UHIDDevice::create(CreateParams {
                 name: String::from("ROG_Virtual Anime Matrix"),
@@ -99,25 +100,141 @@ impl VirtAnimeMatrix {
     //     self.device.write(&reset).unwrap();
     // }
 
-    pub fn read(&mut self) {
+    /// Block until a report is written to the device
+    pub fn read(&mut self) -> Option<Vec<u8>> {
         if let Ok(uhid_virt::OutputEvent::Output { data }) = self.device.read() {
-            for (i, b) in self.buffer.iter_mut().enumerate() {
-                *b = 0;
-                if let Some(n) = data.get(i) {
-                    *b = *n;
-                }
+            return Some(data);
+        }
+        None
+    }
+}
+
+/// Options following the model name
+#[derive(Default)]
+struct Args {
+    /// Decode without opening a window
+    headless: bool,
+    /// Record every report received to this trace file
+    record: Option<PathBuf>,
+    /// Decode reports from this trace file instead of a virtual device
+    replay: Option<PathBuf>,
+    /// Write each complete frame in to this directory
+    dump: Option<PathBuf>,
+    /// Dump frames as PNG instead of text
+    png: bool,
+}
+
+impl Args {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut path = || {
+                args.next()
+                    .map(PathBuf::from)
+                    .ok_or_else(|| format!("{arg} requires a path"))
+            };
+            match arg.as_str() {
+                "--headless" => parsed.headless = true,
+                "--record" => parsed.record = Some(path()?),
+                "--replay" => parsed.replay = Some(path()?),
+                "--dump" => parsed.dump = Some(path()?),
+                "--png" => parsed.png = true,
+                _ => return Err(format!("Unknown option {arg}")),
+            }
+        }
+        if parsed.replay.is_some() {
+            parsed.headless = true;
+        }
+        Ok(parsed)
+    }
+}
+
+/// Writes each complete frame to a directory as text or PNG
+struct FrameDumper {
+    dir: PathBuf,
+    png: bool,
+    count: usize,
+}
+
+impl FrameDumper {
+    fn dump(&mut self, grid: &LedGrid) -> std::io::Result<()> {
+        let path = self.dir.join(format!(
+            "frame_{:05}.{}",
+            self.count,
+            if self.png { "png" } else { "txt" }
+        ));
+        self.count += 1;
+        if self.png {
+            grid.write_png(&path)
+        } else {
+            std::fs::write(path, grid.to_text())
+        }
+    }
+}
+
+/// Decode reports without a window, from a trace or the virtual device
+fn run_headless(anime_type: AnimeType, args: &Args) -> Result<(), Box<dyn Error>> {
+    let matrix = AniMatrix::new(anime_type);
+    let mut grid = matrix.new_grid();
+    let mut dumper = args.dump.as_ref().map(|dir| FrameDumper {
+        dir: dir.clone(),
+        png: args.png,
+        count: 0,
+    });
+    let mut on_report = |report: &[u8]| -> Result<(), Box<dyn Error>> {
+        if matrix.decode(report, &mut grid) {
+            if let Some(dumper) = dumper.as_mut() {
+                dumper.dump(&grid)?;
+            } else {
+                println!("{}", grid.to_text());
             }
         }
+        Ok(())
+    };
+
+    if let Some(replay) = &args.replay {
+        for report in read_trace(replay)? {
+            on_report(&report)?;
+        }
+        return Ok(());
+    }
+
+    let mut dev = VirtAnimeMatrix::new(anime_type);
+    let mut recorder = args
+        .record
+        .as_deref()
+        .map(TraceWriter::create)
+        .transpose()?;
+    loop {
+        if let Some(report) = dev.read() {
+            if let Some(recorder) = recorder.as_mut() {
+                recorder.record(&report)?;
+            }
+            on_report(&report)?;
+        }
     }
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
     let args: Vec<String> = env::args().collect();
     if args.len() <= 1 {
-        println!("Must supply arg, one of <GA401, GA402, GU604>");
+        let names: Vec<&str> = anime_descriptors()
+            .iter()
+            .map(|d| d.name.as_str())
+            .collect();
+        println!(
+            "Must supply arg, one of <{}>, then optionally --headless, --record <trace>, --replay \
+             <trace>, --dump <dir>, --png",
+            names.join(", ")
+        );
         return Ok(());
     }
     let anime_type = AnimeType::from_str(&args[1])?;
+    let options = Args::parse(&args[2..])?;
+    if options.headless {
+        return run_headless(anime_type, &options);
+    }
 
     let sdl_context = sdl2::init().unwrap();
     let video_subsystem = sdl_context.video().unwrap();
@@ -131,45 +248,47 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut canvas = window.into_canvas().build().unwrap();
 
     let mut dev = VirtAnimeMatrix::new(anime_type);
+    let mut recorder = options
+        .record
+        .as_deref()
+        .map(TraceWriter::create)
+        .transpose()?;
+    let mut grid = dev.animatrix.new_grid();
 
     canvas.set_draw_color(Color::RGB(0, 0, 0));
     canvas.clear();
     let mut event_pump = sdl_context.event_pump().unwrap();
     'running: loop {
-        dev.read(); // it's blocking, and damned hard to sync with arc/mutex
-                    // let one = dev.buffer[0..7] != USB_PREFIX2;
-        let index = dev.buffer[3];
-
-        let w = dev.animatrix.led_shape().horizontal * 6;
-        let h = dev.animatrix.led_shape().vertical * 6;
-        let mut y_offset = 0;
-        for (y_count, row) in dev.animatrix.rows().iter().enumerate() {
-            if row.0 == index {
-                let start = row.1;
-                let end = start + row.2;
-                if row.1 < 10 && row.2 < 15 {
-                    if index == 0x74 {
-                        y_offset = 1;
-                    } else if index == 0xe7 {
-                        y_offset = 2;
-                    }
+        // it's blocking, and damned hard to sync with arc/mutex
+        let complete = match dev.read() {
+            Some(report) => {
+                if let Some(recorder) = recorder.as_mut() {
+                    recorder.record(&report)?;
                 }
-                for (x_count, b) in dev.buffer[start..=end].iter().enumerate() {
-                    canvas.set_draw_color(Color::RGB(*b, *b, *b));
-
-                    let x: i32 = w + x_count as i32 * w
-                        - if (y_count + y_offset as usize) % 2 != 0 {
-                            0
-                        } else {
-                            w / 2
-                        }
-                        + row.3 * w;
-                    let y = y_count as i32 * h - y_offset * h;
+                dev.animatrix.decode(&report, &mut grid)
+            }
+            None => false,
+        };
+
+        if complete {
+            let w = dev.animatrix.led_shape().horizontal * 6;
+            let h = dev.animatrix.led_shape().vertical * 6;
+            for y in 0..grid.height() {
+                for x in 0..grid.width() {
+                    let Some(b) = grid.get(x, y) else {
+                        continue;
+                    };
+                    canvas.set_draw_color(Color::RGB(b, b, b));
+                    // Every second row is shifted by half an LED
+                    let stagger = if y % 2 != 0 { 0 } else { w / 2 };
+                    let x = w + x as i32 * w - stagger;
+                    let y = y as i32 * h;
                     canvas
                         .fill_rect(Rect::new(x, y, w as u32, h as u32))
                         .unwrap();
                 }
             }
+            canvas.present();
         }
 
         for event in event_pump.poll_iter() {
@@ -182,11 +301,6 @@ fn main() -> Result<(), Box<dyn Error>> {
                 _ => {}
             }
         }
-
-        if dev.buffer[0..7] == USB_PREFIX2 {
-            canvas.present();
-        }
-        // ::std::thread::sleep(Duration::from_millis(50));
     }
 
     Ok(())
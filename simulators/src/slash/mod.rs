@@ -0,0 +1,122 @@
+use std::fmt::Display;
+use std::fs::File;
+
+use rog_slash::usb::report_id;
+use rog_slash::{SlashMode, SlashType};
+use uhid_virt::UHIDDevice;
+
+use crate::virt_hid::{create_device, read_report, vendor_report_descriptor};
+
+/// The state of the lightbar as decoded from the packets `rog-slash` builds
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SlashState {
+    pub initialised: bool,
+    pub enabled: bool,
+    pub mode: Option<SlashMode>,
+    pub brightness: u8,
+    pub interval: u8,
+    pub show_on_boot: bool,
+    pub show_on_sleep: bool,
+    pub show_on_low_battery: bool,
+    pub show_on_shutdown: bool,
+    pub battery_saver: bool,
+    pub lid_closed: bool,
+    pub saves: u32,
+}
+
+impl SlashState {
+    /// Update from one packet. Returns `false` if the packet was not
+    /// recognised.
+    pub fn decode(&mut self, pkt: &[u8]) -> bool {
+        let byte = |i: usize| pkt.get(i).copied().unwrap_or_default();
+        match (byte(1), byte(2)) {
+            (0xd7, _) | (0xd2, 0x02) => self.initialised = true,
+            // Sent before a mode, no state of its own
+            (0xd2, 0x03) => {}
+            (0xd3, 0x04) => {
+                self.mode = SlashMode::list()
+                    .iter()
+                    .filter_map(|s| s.parse::<SlashMode>().ok())
+                    .find(|m| *m as u8 == byte(6));
+            }
+            (0xd3, 0x03) => match byte(5) {
+                0xab => {
+                    self.enabled = byte(8) != 0;
+                    self.brightness = byte(10);
+                    self.interval = byte(12);
+                }
+                0xa0 => self.show_on_boot = byte(8) != 0,
+                0xa1 => self.show_on_sleep = byte(8) == 0,
+                0xa2 => self.show_on_low_battery = byte(8) != 0,
+                0xa4 => self.show_on_shutdown = byte(8) != 0,
+                _ => return false,
+            },
+            (0xd4, _) => self.saves += 1,
+            (0xd8, 0x02) => self.enabled = byte(5) == 0x00,
+            (0xd8, 0x01) => self.battery_saver = byte(5) == 0x00,
+            (0xd8, 0x00) => self.lid_closed = byte(6) == 0x00,
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl Display for SlashState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mode = self
+            .mode
+            .map(|m| m.to_string())
+            .unwrap_or_else(|| "None".to_owned());
+        write!(
+            f,
+            "enabled: {}, mode: {mode}, brightness: {}, interval: {}, boot: {}, sleep: {}, low \
+             battery: {}, shutdown: {}, battery saver: {}, lid closed: {}, saves: {}",
+            self.enabled,
+            self.brightness,
+            self.interval,
+            self.show_on_boot,
+            self.show_on_sleep,
+            self.show_on_low_battery,
+            self.show_on_shutdown,
+            self.battery_saver,
+            self.lid_closed,
+            self.saves
+        )
+    }
+}
+
+pub struct VirtSlash {
+    device: UHIDDevice<File>,
+    slash_type: SlashType,
+    pub state: SlashState,
+}
+
+impl VirtSlash {
+    pub fn new(slash_type: SlashType) -> Self {
+        Self {
+            device: create_device(
+                "ROG_Virtual Slash",
+                slash_type.prod_id(),
+                vendor_report_descriptor(report_id(slash_type), 0x76, 32),
+            ),
+            slash_type,
+            state: SlashState::default(),
+        }
+    }
+
+    /// Block for the next packet and decode it. Returns `true` if the state
+    /// changed.
+    pub fn read(&mut self) -> bool {
+        let Some(data) = read_report(&mut self.device) else {
+            return false;
+        };
+        if data.first() != Some(&report_id(self.slash_type)) {
+            return false;
+        }
+        let last = self.state.clone();
+        if !self.state.decode(&data) {
+            println!("Unknown Slash packet: {:02x?}", &data[..data.len().min(16)]);
+        }
+        self.state != last
+    }
+}
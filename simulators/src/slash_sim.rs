@@ -0,0 +1,31 @@
+use std::env;
+use std::error::Error;
+use std::str::FromStr;
+
+use rog_slash::SlashType;
+
+mod slash;
+mod virt_hid;
+use slash::*;
+
+/// The segment layout of the lightbar is not known for any model, so the
+/// decoded state is printed rather than drawn
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().collect();
+    let slash_type = args
+        .get(1)
+        .map(|s| SlashType::from_str(s))
+        .transpose()?
+        .unwrap_or(SlashType::Unsupported);
+    if slash_type == SlashType::Unsupported {
+        println!("Must supply arg, one of <GA403, GA605, GU605>");
+        return Ok(());
+    }
+
+    let mut dev = VirtSlash::new(slash_type);
+    loop {
+        if dev.read() {
+            println!("{}", dev.state);
+        }
+    }
+}
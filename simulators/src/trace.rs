@@ -0,0 +1,69 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// Records each HID report as a line of hex bytes
+pub struct TraceWriter {
+    file: BufWriter<File>,
+}
+
+impl TraceWriter {
+    pub fn create(path: &Path) -> std::io::Result<Self> {
+        Ok(Self {
+            file: BufWriter::new(File::create(path)?),
+        })
+    }
+
+    pub fn record(&mut self, report: &[u8]) -> std::io::Result<()> {
+        writeln!(self.file, "{}", to_hex(report))?;
+        self.file.flush()
+    }
+}
+
+pub fn to_hex(report: &[u8]) -> String {
+    report
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parse one line of a trace, blank lines and lines starting with `#` are
+/// skipped
+pub fn from_hex(line: &str) -> Option<Vec<u8>> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    line.split_whitespace()
+        .map(|b| u8::from_str_radix(b, 16).ok())
+        .collect()
+}
+
+/// Read every report from a trace file
+pub fn read_trace(path: &Path) -> std::io::Result<Vec<Vec<u8>>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut reports = Vec::new();
+    for line in reader.lines() {
+        if let Some(report) = from_hex(&line?) {
+            reports.push(report);
+        }
+    }
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_hex, to_hex};
+
+    #[test]
+    fn hex_round_trip() {
+        let report = [
+            0x5e, 0xc0, 0x02, 0x01, 0x00, 0x73, 0x02, 0xff,
+        ];
+        assert_eq!(to_hex(&report), "5e c0 02 01 00 73 02 ff");
+        assert_eq!(from_hex(&to_hex(&report)).unwrap(), report);
+        assert!(from_hex("# comment").is_none());
+        assert!(from_hex("5e zz").is_none());
+    }
+}
@@ -0,0 +1,60 @@
+use std::fs::File;
+
+use log::error;
+use uhid_virt::{Bus, CreateParams, OutputEvent, UHIDDevice};
+
+/// A vendor defined collection with a single report of `len` bytes. The
+/// report is both Output and Feature so that writes to the hidraw node and
+/// feature reports both arrive at the virtual device.
+pub fn vendor_report_descriptor(report_id: u8, usage: u8, len: u8) -> Vec<u8> {
+    vec![
+        0x06, 0x31, 0xff, // Usage Page (Vendor Defined 0xFF31)
+        0x09, usage, // Usage
+        0xa1, 0x01, // Collection (Application)
+        0x85, report_id, //   Report ID
+        0x19, 0x00, //   Usage Minimum (0x00)
+        0x2a, 0xff, 0x00, //   Usage Maximum (0xFF)
+        0x15, 0x00, //   Logical Minimum (0)
+        0x26, 0xff, 0x00, //   Logical Maximum (255)
+        0x75, 0x08, //   Report Size (8)
+        0x95, len, //   Report Count
+        0x91, 0x00, //   Output (Data,Array,Abs)
+        0x95, len, //   Report Count
+        0xb1, 0x00, //   Feature (Data,Array,Abs)
+        0xc0, // End Collection
+    ]
+}
+
+/// Create a virtual ASUS USB HID device
+pub fn create_device(name: &str, product: u16, rd_data: Vec<u8>) -> UHIDDevice<File> {
+    UHIDDevice::create(CreateParams {
+        name: name.to_owned(),
+        phys: String::from(""),
+        uniq: String::from(""),
+        bus: Bus::USB,
+        vendor: 0x0b05,
+        product: product as u32,
+        version: 0,
+        country: 0,
+        rd_data,
+    })
+    .map_err(|err| error!("Could not create virtual device: {:?}", err))
+    .expect("Could not create virtual device")
+}
+
+/// Block until the host writes a report, either through the hidraw node or as
+/// a feature report
+pub fn read_report(device: &mut UHIDDevice<File>) -> Option<Vec<u8>> {
+    match device.read() {
+        Ok(OutputEvent::Output { data }) => Some(data),
+        Ok(OutputEvent::SetReport { id, data, .. }) => {
+            // The host blocks on the reply until its request times out
+            device
+                .write_set_report_reply(id, 0)
+                .map_err(|err| error!("Could not reply to set report: {:?}", err))
+                .ok();
+            Some(data)
+        }
+        _ => None,
+    }
+}